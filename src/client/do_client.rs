@@ -1,13 +1,28 @@
 use crate::client::do_json_protocol::{
-    DropletDataResponse, ListAppsResponse, ListDropletsResponse,
+    AlertPolicyRequest, AppBandwidthDailyRequest, AppBandwidthDailyResponse, AppHealthResponse,
+    DropletDataResponse, DropletNeighborsResponse, GetActionResponse, ListActionsResponse,
+    ListAlertPoliciesResponse, ListAppAlertsResponse, ListAppDeploymentsResponse, ListAppsResponse,
+    ListAutoscalePoolHistoryResponse, ListAutoscalePoolMembersResponse, ListAutoscalePoolsResponse,
+    ListCdnEndpointsResponse, ListCertificatesResponse, ListDatabaseBackupsResponse,
+    ListDatabaseEventsResponse, ListDatabaseFirewallRulesResponse, ListDatabasesResponse,
+    ListDomainRecordsResponse, ListDomainsResponse, ListDropletsResponse, ListFirewallsResponse,
+    ListFunctionNamespacesResponse, ListFunctionTriggersResponse, ListImagesResponse,
+    ListLoadBalancersResponse, ListProjectResourcesResponse, ListProjectsResponse,
+    ListRegionsResponse, ListReservedIpsResponse, ListSizesResponse, ListSnapshotsResponse,
+    ListSshKeysResponse, ListVolumesResponse, ListVpcPeeringsResponse, ListVpcsResponse,
+    SingleAlertPolicyResponse,
 };
 use crate::client::key_manager::{KeyManager, KeyType};
+use crate::client::spaces_signer;
+use crate::client::spaces_xml_protocol::ListBucketResult;
 use crate::config::config_model::{AgentMetricsType, AppSettings};
 use crate::metrics::utils::DROXPORTER_DEFAULT_BUCKETS;
 use async_trait::async_trait;
 use chrono::Utc;
 use prometheus::{HistogramOpts, Opts, Registry};
-use reqwest::StatusCode;
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::sync::Arc;
 use std::time::Instant;
 use url::Url;
@@ -22,6 +37,172 @@ pub trait DigitalOceanClient: Send + Sync {
 
     async fn list_apps(&self, per_page: u64, page: u64) -> anyhow::Result<ListAppsResponse>;
 
+    async fn list_app_deployments(
+        &self,
+        app_id: String,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListAppDeploymentsResponse>;
+
+    async fn list_app_alerts(&self, app_id: String) -> anyhow::Result<ListAppAlertsResponse>;
+
+    async fn get_app_health(&self, app_id: String) -> anyhow::Result<AppHealthResponse>;
+
+    async fn list_certificates(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListCertificatesResponse>;
+
+    async fn list_reserved_ips(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListReservedIpsResponse>;
+
+    async fn list_snapshots(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListSnapshotsResponse>;
+
+    async fn list_projects(&self, per_page: u64, page: u64)
+    -> anyhow::Result<ListProjectsResponse>;
+
+    async fn list_project_resources(
+        &self,
+        project_id: String,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListProjectResourcesResponse>;
+
+    async fn list_autoscale_pools(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListAutoscalePoolsResponse>;
+
+    async fn list_autoscale_pool_members(
+        &self,
+        pool_id: String,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListAutoscalePoolMembersResponse>;
+
+    async fn list_autoscale_pool_history(
+        &self,
+        pool_id: String,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListAutoscalePoolHistoryResponse>;
+
+    async fn list_firewalls(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListFirewallsResponse>;
+
+    async fn list_vpcs(&self, per_page: u64, page: u64) -> anyhow::Result<ListVpcsResponse>;
+
+    async fn list_vpc_peerings(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListVpcPeeringsResponse>;
+
+    async fn list_actions(&self, per_page: u64, page: u64) -> anyhow::Result<ListActionsResponse>;
+
+    async fn get_action(&self, action_id: u64) -> anyhow::Result<GetActionResponse>;
+
+    async fn get_droplet_neighbors(&self) -> anyhow::Result<DropletNeighborsResponse>;
+
+    async fn list_alert_policies(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListAlertPoliciesResponse>;
+
+    async fn create_alert_policy(
+        &self,
+        policy: AlertPolicyRequest,
+    ) -> anyhow::Result<SingleAlertPolicyResponse>;
+
+    async fn update_alert_policy(
+        &self,
+        uuid: String,
+        policy: AlertPolicyRequest,
+    ) -> anyhow::Result<SingleAlertPolicyResponse>;
+
+    async fn delete_alert_policy(&self, uuid: String) -> anyhow::Result<()>;
+
+    async fn list_databases(&self) -> anyhow::Result<ListDatabasesResponse>;
+
+    async fn list_database_backups(
+        &self,
+        database_id: String,
+    ) -> anyhow::Result<ListDatabaseBackupsResponse>;
+
+    async fn list_database_events(
+        &self,
+        database_id: String,
+    ) -> anyhow::Result<ListDatabaseEventsResponse>;
+
+    async fn list_domains(&self, per_page: u64, page: u64) -> anyhow::Result<ListDomainsResponse>;
+
+    async fn list_domain_records(
+        &self,
+        domain: String,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListDomainRecordsResponse>;
+
+    async fn list_load_balancers(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListLoadBalancersResponse>;
+
+    async fn list_cdn_endpoints(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListCdnEndpointsResponse>;
+
+    async fn list_regions(&self, per_page: u64, page: u64) -> anyhow::Result<ListRegionsResponse>;
+
+    async fn list_sizes(&self, per_page: u64, page: u64) -> anyhow::Result<ListSizesResponse>;
+
+    // only custom images and snapshots of the account
+    async fn list_private_images(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListImagesResponse>;
+
+    async fn list_function_namespaces(&self) -> anyhow::Result<ListFunctionNamespacesResponse>;
+
+    async fn list_function_triggers(
+        &self,
+        namespace: String,
+    ) -> anyhow::Result<ListFunctionTriggersResponse>;
+
+    async fn list_database_firewall_rules(
+        &self,
+        database_id: String,
+    ) -> anyhow::Result<ListDatabaseFirewallRulesResponse>;
+
+    async fn list_ssh_keys(&self, per_page: u64, page: u64) -> anyhow::Result<ListSshKeysResponse>;
+
+    async fn list_volumes(&self, per_page: u64, page: u64) -> anyhow::Result<ListVolumesResponse>;
+
+    // S3 ListObjectsV2 request signed with the Spaces access keys
+    async fn list_spaces_objects(
+        &self,
+        region: String,
+        bucket: String,
+        continuation_token: Option<String>,
+    ) -> anyhow::Result<ListBucketResult>;
+
     async fn get_droplet_bandwidth(
         &self,
         host_id: u64,
@@ -83,6 +264,11 @@ pub trait DigitalOceanClient: Send + Sync {
         start: chrono::DateTime<Utc>,
         end: chrono::DateTime<Utc>,
     ) -> anyhow::Result<AppDataResponse>;
+
+    async fn get_app_bandwidth_daily(
+        &self,
+        app_ids: Vec<String>,
+    ) -> anyhow::Result<AppBandwidthDailyResponse>;
 }
 
 #[derive(Eq, PartialEq, Copy, Clone)]
@@ -267,6 +453,125 @@ impl DigitalOceanClientImpl {
 
         Ok(res)
     }
+
+    async fn base_list_request<T: DeserializeOwned>(
+        &self,
+        request_type: RequestType,
+        request_name: &str,
+        base_url: &str,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<T> {
+        let mut url = Url::parse(base_url)?;
+        url.query_pairs_mut()
+            .append_pair("per_page", per_page.to_string().as_str())
+            .append_pair("page", page.to_string().as_str());
+
+        self.base_get_request(request_type, request_name, url).await
+    }
+
+    async fn base_get_request<T: DeserializeOwned>(
+        &self,
+        request_type: RequestType,
+        request_name: &str,
+        url: Url,
+    ) -> anyhow::Result<T> {
+        let bearer = self.token_manager.acquire_key(request_type.into())?;
+        let time = Instant::now();
+
+        let response = self.client.get(url).bearer_auth(bearer).send().await?;
+        self.metrics
+            .record_client_metrics(request_name, response.status().as_str(), time);
+
+        if response.status() != StatusCode::OK && response.status() != StatusCode::NO_CONTENT {
+            let status = response.status();
+            let body = response.text().await?;
+            let err = format!("Request failed with status code: {status}, body: {body}");
+            return Err(anyhow::Error::msg(err));
+        }
+
+        let res = response.json::<T>().await?;
+
+        Ok(res)
+    }
+
+    async fn base_post_request<B: Serialize + Sync, T: DeserializeOwned>(
+        &self,
+        request_type: RequestType,
+        request_name: &str,
+        url: Url,
+        body: &B,
+    ) -> anyhow::Result<T> {
+        self.base_body_request(Method::POST, request_type, request_name, url, body)
+            .await
+    }
+
+    async fn base_put_request<B: Serialize + Sync, T: DeserializeOwned>(
+        &self,
+        request_type: RequestType,
+        request_name: &str,
+        url: Url,
+        body: &B,
+    ) -> anyhow::Result<T> {
+        self.base_body_request(Method::PUT, request_type, request_name, url, body)
+            .await
+    }
+
+    async fn base_body_request<B: Serialize + Sync, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        request_type: RequestType,
+        request_name: &str,
+        url: Url,
+        body: &B,
+    ) -> anyhow::Result<T> {
+        let bearer = self.token_manager.acquire_key(request_type.into())?;
+        let time = Instant::now();
+
+        let response = self
+            .client
+            .request(method, url)
+            .bearer_auth(bearer)
+            .json(body)
+            .send()
+            .await?;
+        self.metrics
+            .record_client_metrics(request_name, response.status().as_str(), time);
+
+        if response.status() != StatusCode::OK && response.status() != StatusCode::CREATED {
+            let status = response.status();
+            let body = response.text().await?;
+            let err = format!("Request failed with status code: {status}, body: {body}");
+            return Err(anyhow::Error::msg(err));
+        }
+
+        let res = response.json::<T>().await?;
+
+        Ok(res)
+    }
+
+    async fn base_delete_request(
+        &self,
+        request_type: RequestType,
+        request_name: &str,
+        url: Url,
+    ) -> anyhow::Result<()> {
+        let bearer = self.token_manager.acquire_key(request_type.into())?;
+        let time = Instant::now();
+
+        let response = self.client.delete(url).bearer_auth(bearer).send().await?;
+        self.metrics
+            .record_client_metrics(request_name, response.status().as_str(), time);
+
+        if response.status() != StatusCode::OK && response.status() != StatusCode::NO_CONTENT {
+            let status = response.status();
+            let body = response.text().await?;
+            let err = format!("Request failed with status code: {status}, body: {body}");
+            return Err(anyhow::Error::msg(err));
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
@@ -290,6 +595,39 @@ pub enum RequestType {
     AppCpuPercentage,
     AppMemoryPercentage,
     AppRestartCount,
+    AppBandwidthDaily,
+    Certificates,
+    ReservedIps,
+    Snapshots,
+    Projects,
+    ProjectResources,
+    AutoscalePools,
+    AutoscalePoolMembers,
+    AutoscalePoolHistory,
+    Firewalls,
+    Vpcs,
+    VpcPeerings,
+    AppDeployments,
+    AppAlerts,
+    AppHealth,
+    Actions,
+    DropletNeighbors,
+    AlertPolicies,
+    Databases,
+    DatabaseBackups,
+    DatabaseEvents,
+    Domains,
+    DomainRecords,
+    LoadBalancers,
+    CdnEndpoints,
+    Regions,
+    Sizes,
+    Images,
+    FunctionNamespaces,
+    FunctionTriggers,
+    DatabaseFirewallRules,
+    SshKeys,
+    Volumes,
 }
 
 #[derive(Clone, Copy)]
@@ -347,6 +685,39 @@ impl From<RequestType> for KeyType {
             RequestType::AppCpuPercentage => KeyType::AppCpuPercentage,
             RequestType::AppMemoryPercentage => KeyType::AppMemoryPercentage,
             RequestType::AppRestartCount => KeyType::AppRestartCount,
+            RequestType::AppBandwidthDaily => KeyType::AppBandwidthDaily,
+            RequestType::Certificates => KeyType::Certificates,
+            RequestType::ReservedIps => KeyType::ReservedIps,
+            RequestType::Snapshots => KeyType::Snapshots,
+            RequestType::Projects => KeyType::Projects,
+            RequestType::ProjectResources => KeyType::Projects,
+            RequestType::AutoscalePools => KeyType::Autoscale,
+            RequestType::AutoscalePoolMembers => KeyType::Autoscale,
+            RequestType::AutoscalePoolHistory => KeyType::Autoscale,
+            RequestType::Firewalls => KeyType::Firewalls,
+            RequestType::Vpcs => KeyType::Vpcs,
+            RequestType::VpcPeerings => KeyType::Vpcs,
+            RequestType::AppDeployments => KeyType::AppDeployments,
+            RequestType::AppAlerts => KeyType::Apps,
+            RequestType::AppHealth => KeyType::Apps,
+            RequestType::Actions => KeyType::Actions,
+            RequestType::DropletNeighbors => KeyType::DropletNeighbors,
+            RequestType::AlertPolicies => KeyType::AlertPolicies,
+            RequestType::Databases => KeyType::Databases,
+            RequestType::DatabaseBackups => KeyType::Databases,
+            RequestType::DatabaseEvents => KeyType::DatabaseEvents,
+            RequestType::Domains => KeyType::Domains,
+            RequestType::DomainRecords => KeyType::Domains,
+            RequestType::LoadBalancers => KeyType::Domains,
+            RequestType::CdnEndpoints => KeyType::Cdn,
+            RequestType::Regions => KeyType::Sizes,
+            RequestType::Sizes => KeyType::Sizes,
+            RequestType::Images => KeyType::Images,
+            RequestType::FunctionNamespaces => KeyType::Functions,
+            RequestType::FunctionTriggers => KeyType::Functions,
+            RequestType::DatabaseFirewallRules => KeyType::Security,
+            RequestType::SshKeys => KeyType::Security,
+            RequestType::Volumes => KeyType::Waste,
         }
     }
 }
@@ -409,86 +780,654 @@ impl DigitalOceanClient for DigitalOceanClientImpl {
         Ok(res)
     }
 
-    async fn get_droplet_bandwidth(
+    async fn list_app_deployments(
         &self,
-        host_id: u64,
-        interface: NetworkInterface,
-        direction: NetworkDirection,
-        start: chrono::DateTime<Utc>,
-        end: chrono::DateTime<Utc>,
-    ) -> anyhow::Result<DropletDataResponse> {
-        let mut url = {
-            let base = self.config.droplet_metrics.base_url.as_str();
-            let str = format!("{base}/bandwidth"); // or path_segments_mut?
-            Url::parse(str.as_str())?
-        };
-
-        let interface = if interface == NetworkInterface::Private {
-            "private"
-        } else {
-            "public"
-        };
-        let direction = if direction == NetworkDirection::Inbound {
-            "inbound"
-        } else {
-            "outbound"
-        };
-
-        url.query_pairs_mut()
-            .append_pair("host_id", host_id.to_string().as_str())
-            .append_pair("interface", interface)
-            .append_pair("direction", direction)
-            .append_pair("start", start.timestamp().to_string().as_str())
-            .append_pair("end", end.timestamp().to_string().as_str());
+        app_id: String,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListAppDeploymentsResponse> {
+        let url = format!(
+            "{}/{}/deployments",
+            self.config.apps.url.trim_end_matches('/'),
+            app_id
+        );
+        self.base_list_request(
+            RequestType::AppDeployments,
+            "list_app_deployments",
+            url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
 
-        let bearer = self
-            .token_manager
-            .acquire_key(RequestType::DropletBandwidth.into())?;
-        let time = Instant::now();
+    async fn list_app_alerts(&self, app_id: String) -> anyhow::Result<ListAppAlertsResponse> {
+        let url = format!(
+            "{}/{}/alerts",
+            self.config.apps.url.trim_end_matches('/'),
+            app_id
+        );
+        self.base_get_request(RequestType::AppAlerts, "list_app_alerts", Url::parse(&url)?)
+            .await
+    }
 
-        let response = self.client.get(url).bearer_auth(bearer).send().await?;
+    async fn get_app_health(&self, app_id: String) -> anyhow::Result<AppHealthResponse> {
+        let url = format!(
+            "{}/{}/health",
+            self.config.apps.url.trim_end_matches('/'),
+            app_id
+        );
+        self.base_get_request(RequestType::AppHealth, "get_app_health", Url::parse(&url)?)
+            .await
+    }
 
-        self.metrics
-            .record_client_metrics("bandwidth", response.status().as_str(), time);
+    async fn list_certificates(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListCertificatesResponse> {
+        self.base_list_request(
+            RequestType::Certificates,
+            "list_certificates",
+            self.config.certificates.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
 
-        if response.status() != StatusCode::OK && response.status() != StatusCode::NO_CONTENT {
-            let status = response.status();
-            let body = response.text().await?;
-            let err = format!("Request failed with status code: {status}, body: {body}");
-            return Err(anyhow::Error::msg(err));
-        }
+    async fn list_reserved_ips(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListReservedIpsResponse> {
+        self.base_list_request(
+            RequestType::ReservedIps,
+            "list_reserved_ips",
+            self.config.reserved_ips.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
 
-        let res = response.json::<DropletDataResponse>().await?;
+    async fn list_snapshots(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListSnapshotsResponse> {
+        self.base_list_request(
+            RequestType::Snapshots,
+            "list_snapshots",
+            self.config.snapshots.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
 
-        Ok(res)
+    async fn list_projects(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListProjectsResponse> {
+        self.base_list_request(
+            RequestType::Projects,
+            "list_projects",
+            self.config.projects.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
     }
 
-    async fn get_droplet_cpu(
+    async fn list_project_resources(
         &self,
-        host_id: u64,
-        start: chrono::DateTime<Utc>,
-        end: chrono::DateTime<Utc>,
-    ) -> anyhow::Result<DropletDataResponse> {
-        self.base_droplet_metrics_request(RequestType::DropletCpu, host_id, start, end)
-            .await
+        project_id: String,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListProjectResourcesResponse> {
+        let url = format!(
+            "{}/{}/resources",
+            self.config.projects.url.trim_end_matches('/'),
+            project_id
+        );
+        self.base_list_request(
+            RequestType::ProjectResources,
+            "list_project_resources",
+            url.as_str(),
+            per_page,
+            page,
+        )
+        .await
     }
 
-    async fn get_droplet_file_system(
+    async fn list_autoscale_pools(
         &self,
-        host_id: u64,
-        request: FileSystemRequest,
-        start: chrono::DateTime<Utc>,
-        end: chrono::DateTime<Utc>,
-    ) -> anyhow::Result<DropletDataResponse> {
-        let request = match request {
-            FileSystemRequest::Free => RequestType::DropletFileSystemFree,
-            FileSystemRequest::Size => RequestType::DropletFileSystemSize,
-        };
-        self.base_droplet_metrics_request(request, host_id, start, end)
-            .await
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListAutoscalePoolsResponse> {
+        self.base_list_request(
+            RequestType::AutoscalePools,
+            "list_autoscale_pools",
+            self.config.autoscale.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
     }
 
-    async fn get_droplet_memory(
+    async fn list_autoscale_pool_members(
+        &self,
+        pool_id: String,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListAutoscalePoolMembersResponse> {
+        let url = format!(
+            "{}/{}/members",
+            self.config.autoscale.url.trim_end_matches('/'),
+            pool_id
+        );
+        self.base_list_request(
+            RequestType::AutoscalePoolMembers,
+            "list_autoscale_pool_members",
+            url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_autoscale_pool_history(
+        &self,
+        pool_id: String,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListAutoscalePoolHistoryResponse> {
+        let url = format!(
+            "{}/{}/history",
+            self.config.autoscale.url.trim_end_matches('/'),
+            pool_id
+        );
+        self.base_list_request(
+            RequestType::AutoscalePoolHistory,
+            "list_autoscale_pool_history",
+            url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_firewalls(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListFirewallsResponse> {
+        self.base_list_request(
+            RequestType::Firewalls,
+            "list_firewalls",
+            self.config.firewalls.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_vpcs(&self, per_page: u64, page: u64) -> anyhow::Result<ListVpcsResponse> {
+        self.base_list_request(
+            RequestType::Vpcs,
+            "list_vpcs",
+            self.config.vpcs.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_vpc_peerings(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListVpcPeeringsResponse> {
+        self.base_list_request(
+            RequestType::VpcPeerings,
+            "list_vpc_peerings",
+            self.config.vpcs.peerings_url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_actions(&self, per_page: u64, page: u64) -> anyhow::Result<ListActionsResponse> {
+        self.base_list_request(
+            RequestType::Actions,
+            "list_actions",
+            self.config.actions.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn get_action(&self, action_id: u64) -> anyhow::Result<GetActionResponse> {
+        let url = format!(
+            "{}/{}",
+            self.config.actions.url.trim_end_matches('/'),
+            action_id
+        );
+        self.base_get_request(RequestType::Actions, "get_action", Url::parse(&url)?)
+            .await
+    }
+
+    async fn get_droplet_neighbors(&self) -> anyhow::Result<DropletNeighborsResponse> {
+        self.base_get_request(
+            RequestType::DropletNeighbors,
+            "get_droplet_neighbors",
+            Url::parse(self.config.droplet_neighbors.url.as_str())?,
+        )
+        .await
+    }
+
+    async fn list_alert_policies(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListAlertPoliciesResponse> {
+        self.base_list_request(
+            RequestType::AlertPolicies,
+            "list_alert_policies",
+            self.config.alert_policies.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn create_alert_policy(
+        &self,
+        policy: AlertPolicyRequest,
+    ) -> anyhow::Result<SingleAlertPolicyResponse> {
+        self.base_post_request(
+            RequestType::AlertPolicies,
+            "create_alert_policy",
+            Url::parse(self.config.alert_policies.url.as_str())?,
+            &policy,
+        )
+        .await
+    }
+
+    async fn update_alert_policy(
+        &self,
+        uuid: String,
+        policy: AlertPolicyRequest,
+    ) -> anyhow::Result<SingleAlertPolicyResponse> {
+        let url = format!(
+            "{}/{}",
+            self.config.alert_policies.url.trim_end_matches('/'),
+            uuid
+        );
+        self.base_put_request(
+            RequestType::AlertPolicies,
+            "update_alert_policy",
+            Url::parse(&url)?,
+            &policy,
+        )
+        .await
+    }
+
+    async fn delete_alert_policy(&self, uuid: String) -> anyhow::Result<()> {
+        let url = format!(
+            "{}/{}",
+            self.config.alert_policies.url.trim_end_matches('/'),
+            uuid
+        );
+        self.base_delete_request(
+            RequestType::AlertPolicies,
+            "delete_alert_policy",
+            Url::parse(&url)?,
+        )
+        .await
+    }
+
+    async fn list_databases(&self) -> anyhow::Result<ListDatabasesResponse> {
+        self.base_get_request(
+            RequestType::Databases,
+            "list_databases",
+            Url::parse(self.config.databases.url.as_str())?,
+        )
+        .await
+    }
+
+    async fn list_database_backups(
+        &self,
+        database_id: String,
+    ) -> anyhow::Result<ListDatabaseBackupsResponse> {
+        let url = format!(
+            "{}/{}/backups",
+            self.config.databases.url.trim_end_matches('/'),
+            database_id
+        );
+        self.base_get_request(
+            RequestType::DatabaseBackups,
+            "list_database_backups",
+            Url::parse(&url)?,
+        )
+        .await
+    }
+
+    async fn list_database_events(
+        &self,
+        database_id: String,
+    ) -> anyhow::Result<ListDatabaseEventsResponse> {
+        let url = format!(
+            "{}/{}/events",
+            self.config.databases.url.trim_end_matches('/'),
+            database_id
+        );
+        self.base_get_request(
+            RequestType::DatabaseEvents,
+            "list_database_events",
+            Url::parse(&url)?,
+        )
+        .await
+    }
+
+    async fn list_domains(&self, per_page: u64, page: u64) -> anyhow::Result<ListDomainsResponse> {
+        self.base_list_request(
+            RequestType::Domains,
+            "list_domains",
+            self.config.domains.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_domain_records(
+        &self,
+        domain: String,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListDomainRecordsResponse> {
+        let url = format!(
+            "{}/{}/records",
+            self.config.domains.url.trim_end_matches('/'),
+            domain
+        );
+        self.base_list_request(
+            RequestType::DomainRecords,
+            "list_domain_records",
+            url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_load_balancers(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListLoadBalancersResponse> {
+        self.base_list_request(
+            RequestType::LoadBalancers,
+            "list_load_balancers",
+            self.config.domains.load_balancers_url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_cdn_endpoints(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListCdnEndpointsResponse> {
+        self.base_list_request(
+            RequestType::CdnEndpoints,
+            "list_cdn_endpoints",
+            self.config.cdn.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_regions(&self, per_page: u64, page: u64) -> anyhow::Result<ListRegionsResponse> {
+        self.base_list_request(
+            RequestType::Regions,
+            "list_regions",
+            self.config.sizes.regions_url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_sizes(&self, per_page: u64, page: u64) -> anyhow::Result<ListSizesResponse> {
+        self.base_list_request(
+            RequestType::Sizes,
+            "list_sizes",
+            self.config.sizes.url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_private_images(
+        &self,
+        per_page: u64,
+        page: u64,
+    ) -> anyhow::Result<ListImagesResponse> {
+        let mut url = Url::parse(self.config.images.url.as_str())?;
+        url.query_pairs_mut().append_pair("private", "true");
+        self.base_list_request(
+            RequestType::Images,
+            "list_private_images",
+            url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_function_namespaces(&self) -> anyhow::Result<ListFunctionNamespacesResponse> {
+        self.base_get_request(
+            RequestType::FunctionNamespaces,
+            "list_function_namespaces",
+            Url::parse(self.config.functions.url.as_str())?,
+        )
+        .await
+    }
+
+    async fn list_function_triggers(
+        &self,
+        namespace: String,
+    ) -> anyhow::Result<ListFunctionTriggersResponse> {
+        let url = format!(
+            "{}/{}/triggers",
+            self.config.functions.url.trim_end_matches('/'),
+            namespace
+        );
+        self.base_get_request(
+            RequestType::FunctionTriggers,
+            "list_function_triggers",
+            Url::parse(&url)?,
+        )
+        .await
+    }
+
+    async fn list_database_firewall_rules(
+        &self,
+        database_id: String,
+    ) -> anyhow::Result<ListDatabaseFirewallRulesResponse> {
+        let url = format!(
+            "{}/{}/firewall",
+            self.config.databases.url.trim_end_matches('/'),
+            database_id
+        );
+        self.base_get_request(
+            RequestType::DatabaseFirewallRules,
+            "list_database_firewall_rules",
+            Url::parse(&url)?,
+        )
+        .await
+    }
+
+    async fn list_ssh_keys(&self, per_page: u64, page: u64) -> anyhow::Result<ListSshKeysResponse> {
+        self.base_list_request(
+            RequestType::SshKeys,
+            "list_ssh_keys",
+            self.config.security.ssh_keys_url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_volumes(&self, per_page: u64, page: u64) -> anyhow::Result<ListVolumesResponse> {
+        self.base_list_request(
+            RequestType::Volumes,
+            "list_volumes",
+            self.config.waste.volumes_url.as_str(),
+            per_page,
+            page,
+        )
+        .await
+    }
+
+    async fn list_spaces_objects(
+        &self,
+        region: String,
+        bucket: String,
+        continuation_token: Option<String>,
+    ) -> anyhow::Result<ListBucketResult> {
+        let spaces = &self.config.spaces;
+        let endpoint = spaces.endpoint.replace("{region}", region.as_str());
+        // path-style addressing works for any S3 compatible endpoint
+        let mut url = Url::parse(&format!("{}/{}", endpoint.trim_end_matches('/'), bucket))?;
+        let mut params = vec![("list-type", "2"), ("max-keys", "1000")];
+        if let Some(token) = continuation_token.as_deref() {
+            params.push(("continuation-token", token));
+        }
+        url.set_query(Some(&spaces_signer::canonical_query(&params)));
+
+        let signed = spaces_signer::sign_get_request(
+            &url,
+            region.as_str(),
+            spaces.access_key.as_str(),
+            spaces.secret_key.as_str(),
+            Utc::now(),
+        );
+        let time = Instant::now();
+
+        let response = self
+            .client
+            .get(url)
+            .header("authorization", signed.authorization)
+            .header("x-amz-date", signed.amz_date)
+            .header("x-amz-content-sha256", signed.content_sha256)
+            .send()
+            .await?;
+        self.metrics
+            .record_client_metrics("list_spaces_objects", response.status().as_str(), time);
+
+        if response.status() != StatusCode::OK {
+            let status = response.status();
+            let body = response.text().await?;
+            let err = format!("Request failed with status code: {status}, body: {body}");
+            return Err(anyhow::Error::msg(err));
+        }
+
+        let body = response.text().await?;
+        let res = quick_xml::de::from_str::<ListBucketResult>(body.as_str())?;
+        Ok(res)
+    }
+
+    async fn get_droplet_bandwidth(
+        &self,
+        host_id: u64,
+        interface: NetworkInterface,
+        direction: NetworkDirection,
+        start: chrono::DateTime<Utc>,
+        end: chrono::DateTime<Utc>,
+    ) -> anyhow::Result<DropletDataResponse> {
+        let mut url = {
+            let base = self.config.droplet_metrics.base_url.as_str();
+            let str = format!("{base}/bandwidth"); // or path_segments_mut?
+            Url::parse(str.as_str())?
+        };
+
+        let interface = if interface == NetworkInterface::Private {
+            "private"
+        } else {
+            "public"
+        };
+        let direction = if direction == NetworkDirection::Inbound {
+            "inbound"
+        } else {
+            "outbound"
+        };
+
+        url.query_pairs_mut()
+            .append_pair("host_id", host_id.to_string().as_str())
+            .append_pair("interface", interface)
+            .append_pair("direction", direction)
+            .append_pair("start", start.timestamp().to_string().as_str())
+            .append_pair("end", end.timestamp().to_string().as_str());
+
+        let bearer = self
+            .token_manager
+            .acquire_key(RequestType::DropletBandwidth.into())?;
+        let time = Instant::now();
+
+        let response = self.client.get(url).bearer_auth(bearer).send().await?;
+
+        self.metrics
+            .record_client_metrics("bandwidth", response.status().as_str(), time);
+
+        if response.status() != StatusCode::OK && response.status() != StatusCode::NO_CONTENT {
+            let status = response.status();
+            let body = response.text().await?;
+            let err = format!("Request failed with status code: {status}, body: {body}");
+            return Err(anyhow::Error::msg(err));
+        }
+
+        let res = response.json::<DropletDataResponse>().await?;
+
+        Ok(res)
+    }
+
+    async fn get_droplet_cpu(
+        &self,
+        host_id: u64,
+        start: chrono::DateTime<Utc>,
+        end: chrono::DateTime<Utc>,
+    ) -> anyhow::Result<DropletDataResponse> {
+        self.base_droplet_metrics_request(RequestType::DropletCpu, host_id, start, end)
+            .await
+    }
+
+    async fn get_droplet_file_system(
+        &self,
+        host_id: u64,
+        request: FileSystemRequest,
+        start: chrono::DateTime<Utc>,
+        end: chrono::DateTime<Utc>,
+    ) -> anyhow::Result<DropletDataResponse> {
+        let request = match request {
+            FileSystemRequest::Free => RequestType::DropletFileSystemFree,
+            FileSystemRequest::Size => RequestType::DropletFileSystemSize,
+        };
+        self.base_droplet_metrics_request(request, host_id, start, end)
+            .await
+    }
+
+    async fn get_droplet_memory(
         &self,
         host_id: u64,
         metric_type: MemoryRequest,
@@ -552,6 +1491,24 @@ impl DigitalOceanClient for DigitalOceanClientImpl {
         self.base_app_metrics_request(RequestType::AppRestartCount, app_id, start, end)
             .await
     }
+
+    async fn get_app_bandwidth_daily(
+        &self,
+        app_ids: Vec<String>,
+    ) -> anyhow::Result<AppBandwidthDailyResponse> {
+        let url = match self.config.app_metrics.bandwidth_daily.as_ref() {
+            Some(bandwidth_daily) => Url::parse(bandwidth_daily.url.as_str())?,
+            None => anyhow::bail!("App bandwidth_daily metrics are not configured"),
+        };
+        let body = AppBandwidthDailyRequest { app_ids };
+        self.base_post_request(
+            RequestType::AppBandwidthDaily,
+            "bandwidth_daily",
+            url,
+            &body,
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -584,6 +1541,7 @@ mod tests {
                     interval: Duration::from_secs(60),
                     types: vec![],
                     keys: vec![],
+                    transfer: false,
                 }),
                 cpu: Some(crate::config::config_model::CpuSettings {
                     enabled: true,
@@ -621,6 +1579,155 @@ mod tests {
                     interval: Duration::from_secs(60),
                     keys: vec![],
                 }),
+                bandwidth_daily: Some(crate::config::config_model::AppBandwidthDailySettings {
+                    enabled: true,
+                    interval: Duration::from_secs(60),
+                    keys: vec![],
+                    url: format!("{}/v2/apps/metrics/bandwidth_daily", server_url),
+                }),
+            },
+            app_deployments: crate::config::config_model::AppDeploymentSettings {
+                enabled: true,
+                keys: vec![],
+                interval: Duration::from_secs(60),
+            },
+            certificates: crate::config::config_model::CertificateSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/certificates", server_url),
+                interval: Duration::from_secs(60),
+            },
+            reserved_ips: crate::config::config_model::ReservedIpSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/reserved_ips", server_url),
+                interval: Duration::from_secs(60),
+            },
+            snapshots: crate::config::config_model::SnapshotSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/snapshots", server_url),
+                interval: Duration::from_secs(60),
+            },
+            projects: crate::config::config_model::ProjectSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/projects", server_url),
+                interval: Duration::from_secs(60),
+                label: true,
+            },
+            autoscale: crate::config::config_model::AutoscaleSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/droplets/autoscale", server_url),
+                interval: Duration::from_secs(60),
+            },
+            firewalls: crate::config::config_model::FirewallSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/firewalls", server_url),
+                interval: Duration::from_secs(60),
+            },
+            vpcs: crate::config::config_model::VpcSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/vpcs", server_url),
+                peerings: true,
+                peerings_url: format!("{}/v2/vpc_peerings", server_url),
+                interval: Duration::from_secs(60),
+            },
+            actions: crate::config::config_model::ActionSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/actions", server_url),
+                interval: Duration::from_secs(60),
+            },
+            droplet_neighbors: crate::config::config_model::DropletNeighborSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/reports/droplet_neighbors_ids", server_url),
+                interval: Duration::from_secs(60),
+            },
+            alert_policies: crate::config::config_model::AlertPolicySettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/monitoring/alerts", server_url),
+                sync: true,
+                prefix: "[droxporter] ".to_string(),
+                prune: false,
+                policies: vec![],
+                interval: Duration::from_secs(60),
+            },
+            databases: crate::config::config_model::DatabaseSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/databases", server_url),
+                interval: Duration::from_secs(60),
+            },
+            database_events: crate::config::config_model::DatabaseEventSettings {
+                enabled: true,
+                keys: vec![],
+                interval: Duration::from_secs(60),
+            },
+            domains: crate::config::config_model::DomainSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/domains", server_url),
+                load_balancers_url: format!("{}/v2/load_balancers", server_url),
+                interval: Duration::from_secs(60),
+            },
+            cdn: crate::config::config_model::CdnSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/cdn/endpoints", server_url),
+                interval: Duration::from_secs(60),
+            },
+            spaces: crate::config::config_model::SpacesSettings {
+                enabled: true,
+                access_key: "test-access-key".to_string(),
+                secret_key: "test-secret-key".to_string(),
+                endpoint: server_url.to_string(),
+                buckets: vec![],
+                interval: Duration::from_secs(60),
+            },
+            sizes: crate::config::config_model::SizeSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/sizes", server_url),
+                regions_url: format!("{}/v2/regions", server_url),
+                sizes: vec![],
+                interval: Duration::from_secs(60),
+            },
+            images: crate::config::config_model::ImageSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/images", server_url),
+                interval: Duration::from_secs(60),
+            },
+            functions: crate::config::config_model::FunctionSettings {
+                enabled: true,
+                keys: vec![],
+                url: format!("{}/v2/functions/namespaces", server_url),
+                interval: Duration::from_secs(60),
+            },
+            security: crate::config::config_model::SecuritySettings {
+                enabled: true,
+                keys: vec![],
+                ssh_keys_url: format!("{}/v2/account/keys", server_url),
+                certificate_expiry: Duration::from_secs(14 * 24 * 60 * 60),
+                interval: Duration::from_secs(60),
+            },
+            waste: crate::config::config_model::WasteSettings {
+                enabled: true,
+                keys: vec![],
+                volumes_url: format!("{}/v2/volumes", server_url),
+                snapshot_age: Duration::from_secs(30 * 24 * 60 * 60),
+                idle_cpu_percent: 2.0,
+                idle_window: Duration::from_secs(7 * 24 * 60 * 60),
+                volume_gigabyte_price: 0.10,
+                snapshot_gigabyte_price: 0.06,
+                reserved_ip_price: 5.0,
+                interval: Duration::from_secs(60),
             },
             exporter_metrics: crate::config::config_model::ExporterMetricsConfigs {
                 enabled: false,
@@ -839,4 +1946,579 @@ mod tests {
         assert_eq!(response.status, "success");
         assert_eq!(response.data.result.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_list_certificates_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/certificates?per_page=100&page=1")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"certificates":[{"id":"892071a0-bb95-49bc-8021-3afd67a210bf","name":"web-cert-01","not_after":"2017-02-22T00:23:00Z","sha1_fingerprint":"dfcc9f57d86bf58e321c2c6c31c7a971be244ac7","created_at":"2017-02-08T16:02:37Z","dns_names":["www.example.com","example.com"],"state":"verified","type":"custom"}],"links":{"pages":{}}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client.list_certificates(100, 1).await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.certificates.len(), 1);
+        assert_eq!(response.certificates[0].name, "web-cert-01");
+        assert_eq!(response.certificates[0].certificate_type, "custom");
+        assert_eq!(response.certificates[0].not_after.timestamp(), 1487722980);
+    }
+
+    #[tokio::test]
+    async fn test_list_reserved_ips_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/reserved_ips?per_page=100&page=1")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"reserved_ips":[{"ip":"45.55.96.47","droplet":{"id":123,"name":"test-droplet"},"region":{"slug":"nyc3"},"locked":false}],"links":{"pages":{}}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client.list_reserved_ips(100, 1).await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.reserved_ips.len(), 1);
+        assert_eq!(response.reserved_ips[0].ip, "45.55.96.47");
+        assert_eq!(
+            response.reserved_ips[0].droplet.as_ref().map(|x| x.id),
+            Some(123)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_snapshots_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/snapshots?per_page=100&page=1")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"snapshots":[{"id":"6372321","name":"web-01-1595954862243","created_at":"2020-07-28T16:47:44Z","regions":["nyc3","sfo3"],"resource_id":"200776916","resource_type":"droplet","min_disk_size":25,"size_gigabytes":2.34,"tags":[]}],"links":{"pages":{}}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client.list_snapshots(100, 1).await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.snapshots.len(), 1);
+        assert_eq!(response.snapshots[0].resource_type, "droplet");
+        assert_eq!(response.snapshots[0].min_disk_size, 25);
+        assert_eq!(response.snapshots[0].created_at.timestamp(), 1595954864);
+    }
+
+    #[tokio::test]
+    async fn test_list_project_resources_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock(
+                "GET",
+                "/v2/projects/4e1bfbc3-dc3e-41f2-a18f-1b4d7ba71679/resources?per_page=100&page=1",
+            )
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"resources":[{"urn":"do:droplet:13457723","assigned_at":"2018-09-28T19:26:37Z","status":"ok"}],"links":{"pages":{}}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client
+            .list_project_resources("4e1bfbc3-dc3e-41f2-a18f-1b4d7ba71679".to_string(), 100, 1)
+            .await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.resources.len(), 1);
+        assert_eq!(response.resources[0].urn, "do:droplet:13457723");
+    }
+
+    #[tokio::test]
+    async fn test_list_autoscale_pool_members_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock(
+                "GET",
+                "/v2/droplets/autoscale/0d3db13e-a604-4944-9827-7ec2642d32ac/members?per_page=100&page=1",
+            )
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"droplets":[{"droplet_id":123456,"created_at":"2020-11-19T20:27:18Z","updated_at":"2020-12-01T00:42:16Z","health_status":"healthy","status":"active","current_utilization":{"memory":0.35,"cpu":0.0007}}],"links":{"pages":{}}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client
+            .list_autoscale_pool_members("0d3db13e-a604-4944-9827-7ec2642d32ac".to_string(), 100, 1)
+            .await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.droplets.len(), 1);
+        assert_eq!(response.droplets[0].droplet_id, 123456);
+        assert_eq!(response.droplets[0].health_status, "healthy");
+    }
+
+    #[tokio::test]
+    async fn test_list_firewalls_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/firewalls?per_page=100&page=1")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"firewalls":[{"id":"fb6045f1-cf1d-4ca3-bfac-18832663025b","name":"firewall","status":"succeeded","inbound_rules":[{"protocol":"tcp","ports":"22","sources":{"addresses":["0.0.0.0/0"]}}],"outbound_rules":[],"droplet_ids":[],"tags":["web"]}],"links":{"pages":{}}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client.list_firewalls(100, 1).await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.firewalls.len(), 1);
+        assert_eq!(response.firewalls[0].tags, vec!["web".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_list_vpc_peerings_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/vpc_peerings?per_page=100&page=1")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"vpc_peerings":[{"id":"5a4981aa-9653-4bd1-bef5-d6bff52042e4","name":"example-vpc-peering","vpc_ids":["c140286f-e6ce-4131-8b7b-df4590ce8d6a","994a2735-dc84-11e8-b21c-3cfdfeaae000"],"created_at":"2020-03-13T19:20:47.442049222Z","status":"ACTIVE"}],"links":{"pages":{}}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client.list_vpc_peerings(100, 1).await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.vpc_peerings.len(), 1);
+        assert_eq!(response.vpc_peerings[0].status, "ACTIVE");
+        assert_eq!(response.vpc_peerings[0].vpc_ids.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_app_deployments_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/apps/app-123/deployments?per_page=20&page=1")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"deployments":[{"id":"dep-1","cause":"manual","phase":"ERROR","created_at":"2024-08-30T13:57:54Z","updated_at":"2024-08-30T13:58:37Z"}],"links":{"pages":{}}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client
+            .list_app_deployments("app-123".to_string(), 20, 1)
+            .await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.deployments.len(), 1);
+        assert_eq!(response.deployments[0].phase, "ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_get_app_bandwidth_daily_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v2/apps/metrics/bandwidth_daily")
+            .match_header("authorization", "Bearer test-api-key")
+            .match_body(mockito::Matcher::JsonString(
+                r#"{"app_ids":["app-1","app-2"]}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"app_bandwidth_usage":[{"app_id":"app-1","bandwidth_bytes":"513668"},{"app_id":"app-2","bandwidth_bytes":"254847"}],"date":"2023-01-17T00:00:00Z"}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client
+            .get_app_bandwidth_daily(vec!["app-1".to_string(), "app-2".to_string()])
+            .await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.app_bandwidth_usage.len(), 2);
+        assert_eq!(response.app_bandwidth_usage[0].bandwidth_bytes, "513668");
+    }
+
+    #[tokio::test]
+    async fn test_get_app_health_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/apps/app-123/health")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"app_health":{"components":[{"name":"web","replicas_desired":1,"replicas_ready":1,"state":"HEALTHY"}]}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client.get_app_health("app-123".to_string()).await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.app_health.components.len(), 1);
+        assert_eq!(response.app_health.components[0].state, "HEALTHY");
+    }
+
+    #[tokio::test]
+    async fn test_get_action_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/actions/36804637")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"action":{"id":36804637,"status":"completed","type":"power_cycle","resource_id":3164444,"resource_type":"droplet"}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client.get_action(36804637).await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.action.status, "completed");
+        assert_eq!(response.action.action_type, "power_cycle");
+    }
+
+    #[tokio::test]
+    async fn test_get_droplet_neighbors_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/reports/droplet_neighbors_ids")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"neighbor_ids":[[168671828,168663509,168671815],[168671883,168671750]]}"#,
+            )
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client.get_droplet_neighbors().await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.neighbor_ids.len(), 2);
+        assert_eq!(response.neighbor_ids[1], vec![168671883, 168671750]);
+    }
+
+    #[tokio::test]
+    async fn test_create_alert_policy_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v2/monitoring/alerts")
+            .match_header("authorization", "Bearer test-api-key")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"type":"v1/insights/droplet/cpu","description":"CPU Alert","value":80.0}"#
+                    .to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"policy":{"alerts":{"email":["bob@example.com"],"slack":[]},"compare":"GreaterThan","description":"CPU Alert","enabled":true,"entities":[],"tags":["production_droplets"],"type":"v1/insights/droplet/cpu","uuid":"78b3da62-27e5-49ba-ac70-5db0b5935c64","value":80,"window":"5m"}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let request = AlertPolicyRequest {
+            policy_type: "v1/insights/droplet/cpu".to_string(),
+            description: "CPU Alert".to_string(),
+            compare: "GreaterThan".to_string(),
+            value: 80_f64,
+            window: "5m".to_string(),
+            entities: vec![],
+            tags: vec!["production_droplets".to_string()],
+            alerts: Default::default(),
+            enabled: true,
+        };
+        let result = do_client.create_alert_policy(request).await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.policy.uuid, "78b3da62-27e5-49ba-ac70-5db0b5935c64");
+    }
+
+    #[tokio::test]
+    async fn test_delete_alert_policy_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock(
+                "DELETE",
+                "/v2/monitoring/alerts/78b3da62-27e5-49ba-ac70-5db0b5935c64",
+            )
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client
+            .delete_alert_policy("78b3da62-27e5-49ba-ac70-5db0b5935c64".to_string())
+            .await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_list_database_backups_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock(
+                "GET",
+                "/v2/databases/9cc10173-e9ea-4176-9dbc-a4cee4c4ff30/backups",
+            )
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"backups":[{"created_at":"2019-01-11T18:42:27Z","size_gigabytes":0.03357696}]}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client
+            .list_database_backups("9cc10173-e9ea-4176-9dbc-a4cee4c4ff30".to_string())
+            .await;
+        mock.assert_async().await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.backups.len(), 1);
+        assert_eq!(response.backups[0].size_gigabytes, 0.03357696);
+    }
+
+    #[tokio::test]
+    async fn test_list_private_images_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v2/images?private=true&per_page=100&page=1")
+            .match_header("authorization", "Bearer test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"images":[{"id":29280599,"name":"golden-2024-05","distribution":"Ubuntu","slug":null,"public":false,"regions":["nyc3"],"created_at":"2024-05-02T10:00:00Z","type":"custom","min_disk_size":20,"size_gigabytes":2.34,"status":"available"}],"links":{"pages":{}}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client.list_private_images(100, 1).await;
+        mock.assert_async().await;
+
+        let response = result.unwrap();
+        assert_eq!(response.images.len(), 1);
+        assert_eq!(response.images[0].name, "golden-2024-05");
+        assert_eq!(response.images[0].size_gigabytes, Some(2.34));
+    }
+
+    #[tokio::test]
+    async fn test_list_spaces_objects_success() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/assets")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("list-type".into(), "2".into()),
+                mockito::Matcher::UrlEncoded("max-keys".into(), "1000".into()),
+                mockito::Matcher::UrlEncoded("continuation-token".into(), "a/b+c=".into()),
+            ]))
+            .match_header(
+                "authorization",
+                mockito::Matcher::Regex(
+                    r"^AWS4-HMAC-SHA256 Credential=test-access-key/\d{8}/fra1/s3/aws4_request, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature=[0-9a-f]{64}$".to_string(),
+                ),
+            )
+            .match_header("x-amz-date", mockito::Matcher::Regex(r"^\d{8}T\d{6}Z$".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/xml")
+            .with_body(r#"<ListBucketResult><Name>assets</Name><IsTruncated>false</IsTruncated><Contents><Key>a.png</Key><Size>10</Size></Contents></ListBucketResult>"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config(&server.url());
+        let client = reqwest::Client::new();
+        let key_registry = prometheus::Registry::new();
+        let key_manager = KeyManagerImpl::new(config, key_registry).unwrap();
+        let registry = prometheus::Registry::new();
+
+        let do_client =
+            DigitalOceanClientImpl::new(config, client, Arc::new(key_manager), registry).unwrap();
+
+        let result = do_client
+            .list_spaces_objects(
+                "fra1".to_string(),
+                "assets".to_string(),
+                Some("a/b+c=".to_string()),
+            )
+            .await;
+        mock.assert_async().await;
+
+        let response = result.unwrap();
+        assert_eq!(response.contents.len(), 1);
+        assert_eq!(response.contents[0].size, 10);
+        assert!(!response.is_truncated);
+    }
 }
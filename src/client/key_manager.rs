@@ -69,6 +69,27 @@ pub enum KeyType {
     AppCpuPercentage,
     AppMemoryPercentage,
     AppRestartCount,
+    AppBandwidthDaily,
+    Certificates,
+    ReservedIps,
+    Snapshots,
+    Projects,
+    Autoscale,
+    Firewalls,
+    Vpcs,
+    AppDeployments,
+    Actions,
+    DropletNeighbors,
+    AlertPolicies,
+    Databases,
+    DatabaseEvents,
+    Domains,
+    Cdn,
+    Sizes,
+    Images,
+    Functions,
+    Security,
+    Waste,
 }
 
 impl KeyType {
@@ -85,6 +106,27 @@ impl KeyType {
             KeyType::AppCpuPercentage => "app_cpu_percentage",
             KeyType::AppMemoryPercentage => "app_memory_percentage",
             KeyType::AppRestartCount => "app_restart_count",
+            KeyType::AppBandwidthDaily => "app_bandwidth_daily",
+            KeyType::Certificates => "certificates",
+            KeyType::ReservedIps => "reserved_ips",
+            KeyType::Snapshots => "snapshots",
+            KeyType::Projects => "projects",
+            KeyType::Autoscale => "autoscale",
+            KeyType::Firewalls => "firewalls",
+            KeyType::Vpcs => "vpcs",
+            KeyType::AppDeployments => "app_deployments",
+            KeyType::Actions => "actions",
+            KeyType::DropletNeighbors => "droplet_neighbors",
+            KeyType::AlertPolicies => "alert_policies",
+            KeyType::Databases => "databases",
+            KeyType::DatabaseEvents => "database_events",
+            KeyType::Domains => "domains",
+            KeyType::Cdn => "cdn",
+            KeyType::Sizes => "sizes",
+            KeyType::Images => "images",
+            KeyType::Functions => "functions",
+            KeyType::Security => "security",
+            KeyType::Waste => "waste",
         }
     }
 }
@@ -123,6 +165,38 @@ impl KeyManagerState {
         if let Some(app_restart_count) = configs.app_metrics.restart_count.as_ref() {
             keys.insert(KeyType::AppRestartCount, app_restart_count.keys.clone());
         }
+        if let Some(app_bandwidth_daily) = configs.app_metrics.bandwidth_daily.as_ref() {
+            keys.insert(KeyType::AppBandwidthDaily, app_bandwidth_daily.keys.clone());
+        }
+        keys.insert(KeyType::Certificates, configs.certificates.keys.clone());
+        keys.insert(KeyType::ReservedIps, configs.reserved_ips.keys.clone());
+        keys.insert(KeyType::Snapshots, configs.snapshots.keys.clone());
+        keys.insert(KeyType::Projects, configs.projects.keys.clone());
+        keys.insert(KeyType::Autoscale, configs.autoscale.keys.clone());
+        keys.insert(KeyType::Firewalls, configs.firewalls.keys.clone());
+        keys.insert(KeyType::Vpcs, configs.vpcs.keys.clone());
+        keys.insert(
+            KeyType::AppDeployments,
+            configs.app_deployments.keys.clone(),
+        );
+        keys.insert(KeyType::Actions, configs.actions.keys.clone());
+        keys.insert(
+            KeyType::DropletNeighbors,
+            configs.droplet_neighbors.keys.clone(),
+        );
+        keys.insert(KeyType::AlertPolicies, configs.alert_policies.keys.clone());
+        keys.insert(KeyType::Databases, configs.databases.keys.clone());
+        keys.insert(
+            KeyType::DatabaseEvents,
+            configs.database_events.keys.clone(),
+        );
+        keys.insert(KeyType::Domains, configs.domains.keys.clone());
+        keys.insert(KeyType::Cdn, configs.cdn.keys.clone());
+        keys.insert(KeyType::Sizes, configs.sizes.keys.clone());
+        keys.insert(KeyType::Images, configs.images.keys.clone());
+        keys.insert(KeyType::Functions, configs.functions.keys.clone());
+        keys.insert(KeyType::Security, configs.security.keys.clone());
+        keys.insert(KeyType::Waste, configs.waste.keys.clone());
 
         // 10 minutes for small amount of initial limits
         let time: DateTime<Utc> = Utc::now() - Duration::minutes(10);
@@ -318,6 +392,7 @@ mod key_manager {
         configs.app_metrics.cpu_percentage = Some(Default::default());
         configs.app_metrics.memory_percentage = Some(Default::default());
         configs.app_metrics.restart_count = Some(Default::default());
+        configs.app_metrics.bandwidth_daily = Some(Default::default());
 
         configs.default_keys = vec!["default".into()];
 
@@ -333,7 +408,29 @@ mod key_manager {
         configs.app_metrics.memory_percentage.as_mut().unwrap().keys =
             vec!["app_memory_percentage".into()];
         configs.app_metrics.restart_count.as_mut().unwrap().keys = vec!["app_restart_count".into()];
+        configs.app_metrics.bandwidth_daily.as_mut().unwrap().keys =
+            vec!["app_bandwidth_daily".into()];
         configs.apps.keys = vec!["apps".into()];
+        configs.certificates.keys = vec!["certificates".into()];
+        configs.reserved_ips.keys = vec!["reserved_ips".into()];
+        configs.snapshots.keys = vec!["snapshots".into()];
+        configs.projects.keys = vec!["projects".into()];
+        configs.autoscale.keys = vec!["autoscale".into()];
+        configs.firewalls.keys = vec!["firewalls".into()];
+        configs.vpcs.keys = vec!["vpcs".into()];
+        configs.app_deployments.keys = vec!["app_deployments".into()];
+        configs.actions.keys = vec!["actions".into()];
+        configs.droplet_neighbors.keys = vec!["droplet_neighbors".into()];
+        configs.alert_policies.keys = vec!["alert_policies".into()];
+        configs.databases.keys = vec!["databases".into()];
+        configs.database_events.keys = vec!["database_events".into()];
+        configs.domains.keys = vec!["domains".into()];
+        configs.cdn.keys = vec!["cdn".into()];
+        configs.sizes.keys = vec!["sizes".into()];
+        configs.images.keys = vec!["images".into()];
+        configs.functions.keys = vec!["functions".into()];
+        configs.security.keys = vec!["security".into()];
+        configs.waste.keys = vec!["waste".into()];
 
         let manager = KeyManagerImpl::new(configs, Registry::new()).unwrap();
 
@@ -358,8 +455,50 @@ mod key_manager {
         assert_eq!(key, "app_memory_percentage".to_string());
         let key = manager.acquire_key(KeyType::AppRestartCount).unwrap();
         assert_eq!(key, "app_restart_count".to_string());
+        let key = manager.acquire_key(KeyType::AppBandwidthDaily).unwrap();
+        assert_eq!(key, "app_bandwidth_daily".to_string());
         let key = manager.acquire_key(KeyType::Apps).unwrap();
         assert_eq!(key, "apps".to_string());
+        let key = manager.acquire_key(KeyType::Certificates).unwrap();
+        assert_eq!(key, "certificates".to_string());
+        let key = manager.acquire_key(KeyType::ReservedIps).unwrap();
+        assert_eq!(key, "reserved_ips".to_string());
+        let key = manager.acquire_key(KeyType::Snapshots).unwrap();
+        assert_eq!(key, "snapshots".to_string());
+        let key = manager.acquire_key(KeyType::Projects).unwrap();
+        assert_eq!(key, "projects".to_string());
+        let key = manager.acquire_key(KeyType::Autoscale).unwrap();
+        assert_eq!(key, "autoscale".to_string());
+        let key = manager.acquire_key(KeyType::Firewalls).unwrap();
+        assert_eq!(key, "firewalls".to_string());
+        let key = manager.acquire_key(KeyType::Vpcs).unwrap();
+        assert_eq!(key, "vpcs".to_string());
+        let key = manager.acquire_key(KeyType::AppDeployments).unwrap();
+        assert_eq!(key, "app_deployments".to_string());
+        let key = manager.acquire_key(KeyType::Actions).unwrap();
+        assert_eq!(key, "actions".to_string());
+        let key = manager.acquire_key(KeyType::DropletNeighbors).unwrap();
+        assert_eq!(key, "droplet_neighbors".to_string());
+        let key = manager.acquire_key(KeyType::AlertPolicies).unwrap();
+        assert_eq!(key, "alert_policies".to_string());
+        let key = manager.acquire_key(KeyType::Databases).unwrap();
+        assert_eq!(key, "databases".to_string());
+        let key = manager.acquire_key(KeyType::DatabaseEvents).unwrap();
+        assert_eq!(key, "database_events".to_string());
+        let key = manager.acquire_key(KeyType::Domains).unwrap();
+        assert_eq!(key, "domains".to_string());
+        let key = manager.acquire_key(KeyType::Cdn).unwrap();
+        assert_eq!(key, "cdn".to_string());
+        let key = manager.acquire_key(KeyType::Sizes).unwrap();
+        assert_eq!(key, "sizes".to_string());
+        let key = manager.acquire_key(KeyType::Images).unwrap();
+        assert_eq!(key, "images".to_string());
+        let key = manager.acquire_key(KeyType::Functions).unwrap();
+        assert_eq!(key, "functions".to_string());
+        let key = manager.acquire_key(KeyType::Security).unwrap();
+        assert_eq!(key, "security".to_string());
+        let key = manager.acquire_key(KeyType::Waste).unwrap();
+        assert_eq!(key, "waste".to_string());
     }
 
     #[test]
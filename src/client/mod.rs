@@ -2,3 +2,5 @@ pub mod do_client;
 pub mod do_json_protocol;
 pub mod key_manager;
 pub mod rate_limiter;
+pub mod spaces_signer;
+pub mod spaces_xml_protocol;
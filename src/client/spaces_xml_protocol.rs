@@ -0,0 +1,55 @@
+use serde::Deserialize;
+
+// responses of the S3 compatible Spaces API
+
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct ListBucketResult {
+    #[serde(default)]
+    pub contents: Vec<SpacesObjectResponse>,
+    #[serde(default)]
+    pub is_truncated: bool,
+    pub next_continuation_token: Option<String>,
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct SpacesObjectResponse {
+    pub key: String,
+    pub size: u64,
+}
+
+#[cfg(test)]
+mod deserialize_test {
+    use crate::client::spaces_xml_protocol::{ListBucketResult, SpacesObjectResponse};
+
+    #[test]
+    fn deserialize_list_bucket_result() {
+        let xml_data = r#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>static-images</Name><Prefix></Prefix><KeyCount>2</KeyCount><MaxKeys>1000</MaxKeys><IsTruncated>true</IsTruncated><NextContinuationToken>1ueGcxLPRx1Tr/XYExHnhbYLgveDs2J/wm36Hy4vbOwM=</NextContinuationToken><Contents><Key>example.txt</Key><LastModified>2017-07-13T18:40:46.777Z</LastModified><ETag>"b3a92f49e7ae64acbf6b3e76f2040f5e"</ETag><Size>14</Size><StorageClass>STANDARD</StorageClass></Contents><Contents><Key>sammy.png</Key><LastModified>2017-07-14T17:44:03.597Z</LastModified><ETag>"fb08934ef619f205f272b0adfd6c018c"</ETag><Size>35369</Size><StorageClass>STANDARD</StorageClass></Contents></ListBucketResult>"#;
+        let deserialized_data: ListBucketResult = quick_xml::de::from_str(xml_data).unwrap();
+        let expected_result = ListBucketResult {
+            contents: vec![
+                SpacesObjectResponse {
+                    key: "example.txt".to_string(),
+                    size: 14,
+                },
+                SpacesObjectResponse {
+                    key: "sammy.png".to_string(),
+                    size: 35369,
+                },
+            ],
+            is_truncated: true,
+            next_continuation_token: Some(
+                "1ueGcxLPRx1Tr/XYExHnhbYLgveDs2J/wm36Hy4vbOwM=".to_string(),
+            ),
+        };
+
+        assert_eq!(deserialized_data, expected_result);
+
+        let empty: ListBucketResult = quick_xml::de::from_str(
+            r#"<ListBucketResult><Name>empty</Name><KeyCount>0</KeyCount><IsTruncated>false</IsTruncated></ListBucketResult>"#,
+        )
+        .unwrap();
+        assert!(empty.contents.is_empty());
+    }
+}
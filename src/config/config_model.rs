@@ -17,6 +17,48 @@ pub struct AppSettings {
     #[serde(default)]
     pub app_metrics: AppMetricsConfig,
     #[serde(default)]
+    pub app_deployments: AppDeploymentSettings,
+    #[serde(default)]
+    pub certificates: CertificateSettings,
+    #[serde(default)]
+    pub reserved_ips: ReservedIpSettings,
+    #[serde(default)]
+    pub snapshots: SnapshotSettings,
+    #[serde(default)]
+    pub projects: ProjectSettings,
+    #[serde(default)]
+    pub autoscale: AutoscaleSettings,
+    #[serde(default)]
+    pub firewalls: FirewallSettings,
+    #[serde(default)]
+    pub vpcs: VpcSettings,
+    #[serde(default)]
+    pub actions: ActionSettings,
+    #[serde(default)]
+    pub droplet_neighbors: DropletNeighborSettings,
+    #[serde(default)]
+    pub alert_policies: AlertPolicySettings,
+    #[serde(default)]
+    pub databases: DatabaseSettings,
+    #[serde(default)]
+    pub database_events: DatabaseEventSettings,
+    #[serde(default)]
+    pub domains: DomainSettings,
+    #[serde(default)]
+    pub cdn: CdnSettings,
+    #[serde(default)]
+    pub spaces: SpacesSettings,
+    #[serde(default)]
+    pub sizes: SizeSettings,
+    #[serde(default)]
+    pub images: ImageSettings,
+    #[serde(default)]
+    pub functions: FunctionSettings,
+    #[serde(default)]
+    pub security: SecuritySettings,
+    #[serde(default)]
+    pub waste: WasteSettings,
+    #[serde(default)]
     pub exporter_metrics: ExporterMetricsConfigs,
     #[serde(default)]
     pub endpoint: EndpointConfig,
@@ -112,6 +154,7 @@ pub struct AppMetricsConfig {
     pub cpu_percentage: Option<AppCpuPercentageSettings>,
     pub memory_percentage: Option<AppMemoryPercentageSettings>,
     pub restart_count: Option<AppRestartCountSettings>,
+    pub bandwidth_daily: Option<AppBandwidthDailySettings>,
 }
 
 #[derive(Deserialize, Clone, Default, Debug)]
@@ -168,6 +211,397 @@ pub struct AppPlatformSettings {
     pub metrics: Vec<AppMetricsTypes>,
 }
 
+// deployments are loaded from `{apps.url}/{app_id}/deployments`
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct AppDeploymentSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "duration_5_minutes")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct CertificateSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_certificates_url")]
+    pub url: String,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReservedIpSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_reserved_ips_url")]
+    pub url: String,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_snapshots_url")]
+    pub url: String,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProjectSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_projects_url")]
+    pub url: String,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+    // adds the `project` label to droplet and app series
+    #[serde(default)]
+    pub label: bool,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct AutoscaleSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_autoscale_url")]
+    pub url: String,
+    #[serde(default = "duration_5_minutes")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct FirewallSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_firewalls_url")]
+    pub url: String,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct VpcSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_vpcs_url")]
+    pub url: String,
+    // VPC peerings are loaded only if enabled
+    #[serde(default)]
+    pub peerings: bool,
+    #[serde(default = "default_vpc_peerings_url")]
+    pub peerings_url: String,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct ActionSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_actions_url")]
+    pub url: String,
+    #[serde(default = "duration_60_seconds")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct DropletNeighborSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_droplet_neighbors_url")]
+    pub url: String,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct AlertPolicySettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_alert_policies_url")]
+    pub url: String,
+    // if enabled, policies from the API are created, updated and deleted to match 'policies'.
+    // Policies are matched by description, which is prefixed to mark the policies managed by the exporter
+    #[serde(default)]
+    pub sync: bool,
+    #[serde(default = "default_alert_policy_prefix")]
+    pub prefix: String,
+    // if enabled, policies without the prefix are deleted during sync as well
+    #[serde(default)]
+    pub prune: bool,
+    #[serde(default)]
+    pub policies: Vec<AlertPolicyConfig>,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct AlertPolicyConfig {
+    pub description: String,
+    #[serde(rename = "type")]
+    pub policy_type: String,
+    pub compare: String,
+    pub value: f64,
+    pub window: String,
+    #[serde(default)]
+    pub entities: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub emails: Vec<String>,
+    #[serde(default)]
+    pub slack: Vec<AlertPolicySlackConfig>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct AlertPolicySlackConfig {
+    pub channel: String,
+    pub url: String,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct DatabaseSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_databases_url")]
+    pub url: String,
+    // one request per cluster for its backups
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+// events are loaded for the clusters from the database store, so `databases` should be enabled too
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct DatabaseEventSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    // one request per cluster
+    #[serde(default = "duration_5_minutes")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+// records are checked against the droplet and reserved IP stores, so `reserved-ips` should be enabled too
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct DomainSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_domains_url")]
+    pub url: String,
+    #[serde(default = "default_load_balancers_url")]
+    pub load_balancers_url: String,
+    // one request per domain for its records
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct CdnSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_cdn_url")]
+    pub url: String,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct SizeSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_sizes_url")]
+    pub url: String,
+    #[serde(default = "default_regions_url")]
+    pub regions_url: String,
+    // sizes to watch, all sizes if empty. Watched sizes are reported even if they disappear from the list
+    #[serde(default)]
+    pub sizes: Vec<String>,
+    // availability changes rarely
+    #[serde(default = "duration_6_hours")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+// only private images are loaded, droplet counts are taken from the droplet store
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImageSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_images_url")]
+    pub url: String,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct FunctionSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_functions_url")]
+    pub url: String,
+    // one request per namespace for its triggers
+    #[serde(default = "duration_5_minutes")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+// findings are built from the other stores, so only the checks of enabled sections are run
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct SecuritySettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_ssh_keys_url")]
+    pub ssh_keys_url: String,
+    // certificates expiring sooner are reported
+    #[serde(default = "duration_14_days")]
+    #[serde(with = "humantime_serde")]
+    pub certificate_expiry: std::time::Duration,
+    // one request per database cluster for its trusted sources
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+// billable but idle resources. Prices are in USD per month and only used for estimations
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct WasteSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub keys: Vec<Key>,
+    #[serde(default = "default_volumes_url")]
+    pub volumes_url: String,
+    // snapshots older than this are reported
+    #[serde(default = "duration_30_days")]
+    #[serde(with = "humantime_serde")]
+    pub snapshot_age: std::time::Duration,
+    // droplets with average cpu usage below this percentage over the window are reported.
+    // Requires droplet-metrics.cpu to be enabled
+    #[serde(default = "default_idle_cpu_percent")]
+    pub idle_cpu_percent: f64,
+    #[serde(default = "duration_7_days")]
+    #[serde(with = "humantime_serde")]
+    pub idle_window: std::time::Duration,
+    #[serde(default = "default_volume_gigabyte_price")]
+    pub volume_gigabyte_price: f64,
+    #[serde(default = "default_snapshot_gigabyte_price")]
+    pub snapshot_gigabyte_price: f64,
+    #[serde(default = "default_reserved_ip_price")]
+    pub reserved_ip_price: f64,
+    // one cpu request per active droplet
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+// Spaces use their own access keys instead of API tokens
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct SpacesSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub access_key: String,
+    #[serde(default)]
+    pub secret_key: String,
+    // any S3 compatible endpoint. {region} is replaced with the bucket's region
+    #[serde(default = "default_spaces_endpoint")]
+    pub endpoint: String,
+    #[serde(default)]
+    pub buckets: Vec<SpacesBucketConfig>,
+    // one request per 1000 objects of every bucket
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+}
+
+#[derive(Deserialize, Clone, Default, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SpacesBucketConfig {
+    pub name: String,
+    pub region: String,
+}
+
 #[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
 pub enum DropletMetricsTypes {
     #[serde(rename = "memory")]
@@ -178,12 +612,22 @@ pub enum DropletMetricsTypes {
     Disk,
     #[serde(rename = "status")]
     Status,
+    #[serde(rename = "backups")]
+    Backups,
+    #[serde(rename = "price")]
+    Price,
 }
 
 #[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
 pub enum AppMetricsTypes {
     #[serde(rename = "active_deployment_phase")]
     ActiveDeploymentPhase,
+    #[serde(rename = "component_info")]
+    ComponentInfo,
+    #[serde(rename = "alerts")]
+    Alerts,
+    #[serde(rename = "health")]
+    Health,
 }
 
 #[derive(Deserialize, Clone, Default, Debug)]
@@ -198,6 +642,9 @@ pub struct BandwidthSettings {
     pub interval: std::time::Duration,
     #[serde(default = "default_true")]
     pub enabled: bool,
+    // integrate public outbound bandwidth into the monthly transfer estimation
+    #[serde(default)]
+    pub transfer: bool,
 }
 
 #[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
@@ -336,10 +783,45 @@ pub struct AppRestartCountSettings {
     pub enabled: bool,
 }
 
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct AppBandwidthDailySettings {
+    #[serde(default)]
+    pub keys: Vec<String>,
+    // unlike other app metrics, it isn't a part of the monitoring API
+    #[serde(default = "default_app_bandwidth_daily_url")]
+    pub url: String,
+    #[serde(default = "duration_1_hour")]
+    #[serde(with = "humantime_serde")]
+    pub interval: std::time::Duration,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn duration_30_days() -> std::time::Duration {
+    std::time::Duration::from_secs(30 * 24 * 60 * 60)
+}
+
+fn duration_14_days() -> std::time::Duration {
+    std::time::Duration::from_secs(14 * 24 * 60 * 60)
+}
+
+fn duration_7_days() -> std::time::Duration {
+    std::time::Duration::from_secs(7 * 24 * 60 * 60)
+}
+
+fn duration_6_hours() -> std::time::Duration {
+    std::time::Duration::from_secs(6 * 60 * 60)
+}
+
 fn duration_1_hour() -> std::time::Duration {
     std::time::Duration::from_secs(60 * 60)
 }
 
+fn duration_5_minutes() -> std::time::Duration {
+    std::time::Duration::from_secs(5 * 60)
+}
+
 fn duration_5_seconds() -> std::time::Duration {
     std::time::Duration::from_secs(5)
 }
@@ -368,10 +850,122 @@ fn default_droplets_url() -> String {
     "https://api.digitalocean.com/v2/droplets".into()
 }
 
+fn default_app_bandwidth_daily_url() -> String {
+    "https://api.digitalocean.com/v2/apps/metrics/bandwidth_daily".into()
+}
+
 fn default_apps_url() -> String {
     "https://api.digitalocean.com/v2/apps".into()
 }
 
+fn default_certificates_url() -> String {
+    "https://api.digitalocean.com/v2/certificates".into()
+}
+
+fn default_reserved_ips_url() -> String {
+    "https://api.digitalocean.com/v2/reserved_ips".into()
+}
+
+fn default_snapshots_url() -> String {
+    "https://api.digitalocean.com/v2/snapshots".into()
+}
+
+fn default_projects_url() -> String {
+    "https://api.digitalocean.com/v2/projects".into()
+}
+
+fn default_autoscale_url() -> String {
+    "https://api.digitalocean.com/v2/droplets/autoscale".into()
+}
+
+fn default_firewalls_url() -> String {
+    "https://api.digitalocean.com/v2/firewalls".into()
+}
+
+fn default_vpcs_url() -> String {
+    "https://api.digitalocean.com/v2/vpcs".into()
+}
+
+fn default_vpc_peerings_url() -> String {
+    "https://api.digitalocean.com/v2/vpc_peerings".into()
+}
+
+fn default_actions_url() -> String {
+    "https://api.digitalocean.com/v2/actions".into()
+}
+
+fn default_droplet_neighbors_url() -> String {
+    "https://api.digitalocean.com/v2/reports/droplet_neighbors_ids".into()
+}
+
+fn default_alert_policies_url() -> String {
+    "https://api.digitalocean.com/v2/monitoring/alerts".into()
+}
+
+fn default_alert_policy_prefix() -> String {
+    "[droxporter] ".into()
+}
+
+fn default_databases_url() -> String {
+    "https://api.digitalocean.com/v2/databases".into()
+}
+
+fn default_domains_url() -> String {
+    "https://api.digitalocean.com/v2/domains".into()
+}
+
+fn default_load_balancers_url() -> String {
+    "https://api.digitalocean.com/v2/load_balancers".into()
+}
+
+fn default_cdn_url() -> String {
+    "https://api.digitalocean.com/v2/cdn/endpoints".into()
+}
+
+fn default_spaces_endpoint() -> String {
+    "https://{region}.digitaloceanspaces.com".into()
+}
+
+fn default_sizes_url() -> String {
+    "https://api.digitalocean.com/v2/sizes".into()
+}
+
+fn default_regions_url() -> String {
+    "https://api.digitalocean.com/v2/regions".into()
+}
+
+fn default_images_url() -> String {
+    "https://api.digitalocean.com/v2/images".into()
+}
+
+fn default_functions_url() -> String {
+    "https://api.digitalocean.com/v2/functions/namespaces".into()
+}
+
+fn default_ssh_keys_url() -> String {
+    "https://api.digitalocean.com/v2/account/keys".into()
+}
+
+fn default_volumes_url() -> String {
+    "https://api.digitalocean.com/v2/volumes".into()
+}
+
+fn default_idle_cpu_percent() -> f64 {
+    2.0
+}
+
+fn default_volume_gigabyte_price() -> f64 {
+    0.10
+}
+
+fn default_snapshot_gigabyte_price() -> f64 {
+    0.06
+}
+
+fn default_reserved_ip_price() -> f64 {
+    5.0
+}
+
 fn default_true() -> bool {
     true
 }
@@ -5,21 +5,43 @@ mod metrics;
 use crate::client::do_client::DigitalOceanClientImpl;
 use crate::client::key_manager::KeyManagerImpl;
 use crate::config::config_model::{AppSettings, SslSettings};
+use crate::metrics::action_store::ActionStoreImpl;
 use crate::metrics::agent_metrics::AgentMetricsImpl;
+use crate::metrics::alert_policy_store::AlertPolicyStoreImpl;
+use crate::metrics::app_deployment_store::AppDeploymentStoreImpl;
 use crate::metrics::app_metrics_loader::AppMetricsServiceImpl;
 use crate::metrics::app_store::AppStoreImpl;
+use crate::metrics::autoscale_store::AutoscaleStoreImpl;
+use crate::metrics::cdn_store::CdnStoreImpl;
+use crate::metrics::certificate_store::CertificateStoreImpl;
+use crate::metrics::database_event_store::DatabaseEventStoreImpl;
+use crate::metrics::database_store::DatabaseStoreImpl;
+use crate::metrics::domain_store::DomainStoreImpl;
 use crate::metrics::droplet_metrics_loader::DropletMetricsServiceImpl;
+use crate::metrics::droplet_neighbor_store::DropletNeighborStoreImpl;
 use crate::metrics::droplet_store::DropletStoreImpl;
+use crate::metrics::firewall_store::FirewallStoreImpl;
+use crate::metrics::function_store::FunctionStoreImpl;
+use crate::metrics::image_store::ImageStoreImpl;
 use crate::metrics::jobs_scheduler::{MetricsScheduler, MetricsSchedulerImpl};
+use crate::metrics::project_store::ProjectStoreImpl;
+use crate::metrics::reserved_ip_store::ReservedIpStoreImpl;
+use crate::metrics::security_store::SecurityStoreImpl;
+use crate::metrics::size_store::SizeStoreImpl;
+use crate::metrics::snapshot_store::SnapshotStoreImpl;
+use crate::metrics::spaces_store::SpacesStoreImpl;
+use crate::metrics::vpc_store::VpcStoreImpl;
+use crate::metrics::waste_store::{WasteReport, WasteStore, WasteStoreImpl};
 use poem::handler;
 use poem::listener::{Listener, RustlsCertificate, RustlsConfig, TcpListener};
 use poem::web::{
     headers,
     headers::{HeaderMapExt, authorization::Basic},
 };
-use poem::{EndpointExt, Route, Server};
+use poem::{EndpointExt, IntoResponse, Route, Server};
 use prometheus::Registry;
 use reqwest::StatusCode;
+use serde::Deserialize;
 use std::fs;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -31,12 +53,7 @@ use tracing::metadata::LevelFilter;
 #[global_allocator]
 static GLOBAL_MIMALLOC: mimalloc_rust::GlobalMiMalloc = mimalloc_rust::GlobalMiMalloc;
 
-#[handler]
-async fn prometheus_endpoint(
-    request: &poem::Request,
-    registry: poem::web::Data<&Registry>,
-    configs: poem::web::Data<&&'static AppSettings>,
-) -> poem::Result<String> {
+fn check_auth(request: &poem::Request, configs: &AppSettings) -> poem::Result<()> {
     // Simple basic auth check
     // I don't think that for a simple agent, it's worth using bcrypt or anything like that because:
     //   1. The information is not sensitive.
@@ -54,6 +71,16 @@ async fn prometheus_endpoint(
             return Err(poem::Error::from_status(StatusCode::UNAUTHORIZED));
         }
     }
+    Ok(())
+}
+
+#[handler]
+async fn prometheus_endpoint(
+    request: &poem::Request,
+    registry: poem::web::Data<&Registry>,
+    configs: poem::web::Data<&&'static AppSettings>,
+) -> poem::Result<String> {
+    check_auth(request, &configs)?;
 
     let encoder = prometheus::TextEncoder::new();
     let metric_families = registry.deref().gather();
@@ -63,6 +90,32 @@ async fn prometheus_endpoint(
     Ok(result)
 }
 
+#[derive(Deserialize)]
+struct WasteReportParams {
+    // json or text
+    format: Option<String>,
+}
+
+#[handler]
+async fn waste_report_endpoint(
+    request: &poem::Request,
+    params: poem::web::Query<WasteReportParams>,
+    waste_store: poem::web::Data<&Arc<dyn WasteStore>>,
+    configs: poem::web::Data<&&'static AppSettings>,
+) -> poem::Result<poem::Response> {
+    check_auth(request, &configs)?;
+    if !configs.waste.enabled {
+        return Err(poem::Error::from_status(StatusCode::NOT_FOUND));
+    }
+
+    let report = WasteReport::new(waste_store.list_waste());
+    match params.format.as_deref() {
+        None | Some("json") => Ok(poem::web::Json(report).into_response()),
+        Some("text") => Ok(report.to_text().into_response()),
+        Some(_) => Err(poem::Error::from_status(StatusCode::BAD_REQUEST)),
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let (non_blocking, _guard) = tracing_appender::non_blocking(std::io::stdout());
@@ -87,7 +140,7 @@ async fn main() -> anyhow::Result<()> {
         Registry::new_custom(trimmed_prefix, labels)?
     };
 
-    let scheduler = build_app(registry.clone(), configs)?;
+    let (scheduler, waste_store) = build_app(registry.clone(), configs)?;
     let scheduler = Arc::new(scheduler);
 
     tokio::spawn({
@@ -130,10 +183,100 @@ async fn main() -> anyhow::Result<()> {
         let scheduler = scheduler.clone();
         async move { scheduler.run_app_restart_count_metrics_loading().await }
     });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_app_bandwidth_daily_metrics_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_certificates_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_reserved_ips_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_snapshots_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_projects_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_autoscale_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_firewalls_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_vpcs_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_app_deployments_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_actions_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_droplet_neighbors_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_alert_policies_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_databases_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_database_events_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_domains_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_cdn_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_spaces_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_sizes_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_images_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_functions_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_security_loading().await }
+    });
+    tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_waste_loading().await }
+    });
 
     let route = Route::new()
         .at("/metrics", poem::get(prometheus_endpoint))
+        .at("/report/waste", poem::get(waste_report_endpoint))
         .data(registry)
+        .data(waste_store)
         .data(configs);
 
     info!("Starting server");
@@ -170,7 +313,7 @@ async fn main() -> anyhow::Result<()> {
 fn build_app(
     registry: Registry,
     configs: &'static AppSettings,
-) -> anyhow::Result<MetricsSchedulerImpl> {
+) -> anyhow::Result<(MetricsSchedulerImpl, Arc<dyn WasteStore>)> {
     let key_manager = KeyManagerImpl::new(configs, registry.clone())?;
     let client = DigitalOceanClientImpl::new(
         configs,
@@ -179,21 +322,115 @@ fn build_app(
         registry.clone(),
     )?;
     let agent_metrics = AgentMetricsImpl::new(configs, registry.clone());
-    let droplets_store =
-        DropletStoreImpl::new(Arc::new(client.clone()), configs, registry.clone())?;
+    let project_store = ProjectStoreImpl::new(Arc::new(client.clone()), registry.clone())?;
+    let droplets_store = DropletStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(project_store.clone()),
+        configs,
+        registry.clone(),
+    )?;
     let droplets_metrics_loader = DropletMetricsServiceImpl::new(
         Arc::new(client.clone()),
         Arc::new(droplets_store.clone()),
         configs,
         registry.clone(),
     )?;
-    let app_store = AppStoreImpl::new(Arc::new(client.clone()), configs, registry.clone())?;
+    let app_store = AppStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(project_store.clone()),
+        configs,
+        registry.clone(),
+    )?;
     let app_metrics_loader = AppMetricsServiceImpl::new(
         Arc::new(client.clone()),
         Arc::new(app_store.clone()),
         configs,
         registry.clone(),
     )?;
+    let app_deployment_store = AppDeploymentStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(app_store.clone()),
+        registry.clone(),
+    )?;
+    let certificate_store = CertificateStoreImpl::new(Arc::new(client.clone()), registry.clone())?;
+    let reserved_ip_store = ReservedIpStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(droplets_store.clone()),
+        registry.clone(),
+    )?;
+    let snapshot_store = SnapshotStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(droplets_store.clone()),
+        registry.clone(),
+    )?;
+    let autoscale_store = AutoscaleStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(droplets_store.clone()),
+        registry.clone(),
+    )?;
+    let firewall_store = FirewallStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(droplets_store.clone()),
+        registry.clone(),
+    )?;
+    let vpc_store = VpcStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(droplets_store.clone()),
+        configs,
+        registry.clone(),
+    )?;
+    let action_store = ActionStoreImpl::new(Arc::new(client.clone()), registry.clone())?;
+    let droplet_neighbor_store = DropletNeighborStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(droplets_store.clone()),
+        registry.clone(),
+    )?;
+    let alert_policy_store =
+        AlertPolicyStoreImpl::new(Arc::new(client.clone()), configs, registry.clone())?;
+    let database_store = DatabaseStoreImpl::new(Arc::new(client.clone()), registry.clone())?;
+    let database_event_store = DatabaseEventStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(database_store.clone()),
+        registry.clone(),
+    )?;
+    let domain_store = DomainStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(droplets_store.clone()),
+        Arc::new(reserved_ip_store.clone()),
+        configs,
+        registry.clone(),
+    )?;
+    let cdn_store = CdnStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(certificate_store.clone()),
+        registry.clone(),
+    )?;
+    let spaces_store = SpacesStoreImpl::new(Arc::new(client.clone()), configs, registry.clone())?;
+    let size_store = SizeStoreImpl::new(Arc::new(client.clone()), configs, registry.clone())?;
+    let image_store = ImageStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(droplets_store.clone()),
+        registry.clone(),
+    )?;
+    let function_store = FunctionStoreImpl::new(Arc::new(client.clone()), registry.clone())?;
+    let security_store = SecurityStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(droplets_store.clone()),
+        Arc::new(firewall_store.clone()),
+        Arc::new(database_store.clone()),
+        Arc::new(certificate_store.clone()),
+        configs,
+        registry.clone(),
+    )?;
+    // shared with the report endpoint
+    let waste_store: Arc<dyn WasteStore> = Arc::new(WasteStoreImpl::new(
+        Arc::new(client.clone()),
+        Arc::new(droplets_store.clone()),
+        Arc::new(reserved_ip_store.clone()),
+        Arc::new(snapshot_store.clone()),
+        configs,
+        registry.clone(),
+    )?);
 
     let scheduler: MetricsSchedulerImpl = MetricsSchedulerImpl::new(
         configs,
@@ -202,9 +439,30 @@ fn build_app(
         Arc::new(droplets_metrics_loader),
         Arc::new(app_metrics_loader),
         Arc::new(agent_metrics),
+        Arc::new(certificate_store.clone()),
+        Arc::new(reserved_ip_store.clone()),
+        Arc::new(snapshot_store.clone()),
+        Arc::new(project_store),
+        Arc::new(autoscale_store),
+        Arc::new(firewall_store.clone()),
+        Arc::new(vpc_store),
+        Arc::new(app_deployment_store),
+        Arc::new(action_store),
+        Arc::new(droplet_neighbor_store),
+        Arc::new(alert_policy_store),
+        Arc::new(database_store.clone()),
+        Arc::new(database_event_store),
+        Arc::new(domain_store),
+        Arc::new(cdn_store),
+        Arc::new(spaces_store),
+        Arc::new(size_store),
+        Arc::new(image_store),
+        Arc::new(function_store),
+        Arc::new(security_store),
+        waste_store.clone(),
         registry.clone(),
     )?;
-    Ok(scheduler)
+    Ok((scheduler, waste_store))
 }
 
 fn create_poem_tls_config(config: &SslSettings) -> anyhow::Result<RustlsConfig> {
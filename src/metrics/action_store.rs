@@ -0,0 +1,454 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::ActionResponse;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+use tracing::{error, info};
+
+// upper bound of requests for new actions per interval,
+// older actions are skipped if more than 1000 actions were created since the last poll
+const MAX_PAGES: u64 = 10;
+
+const IN_PROGRESS: &str = "in-progress";
+
+// in progress actions which failed to refresh this many times in a row are dropped,
+// e.g. the action is gone together with its resource and would never be finished
+const MAX_REFRESH_FAILURES: u32 = 3;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ActionStore: Send + Sync {
+    async fn load_actions(&self) -> anyhow::Result<()>;
+
+    fn record_action_metrics(&self);
+}
+
+#[derive(Clone)]
+pub struct BasicActionInfo {
+    pub id: u64,
+    pub status: String,
+    pub action_type: String,
+    pub resource_type: String,
+}
+
+impl From<ActionResponse> for BasicActionInfo {
+    fn from(value: ActionResponse) -> Self {
+        Self {
+            id: value.id,
+            status: value.status,
+            action_type: value.action_type,
+            resource_type: value.resource_type,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ActionState {
+    // the newest action id seen. None until the first load,
+    // so the history loaded on startup is not counted
+    last_seen: Option<u64>,
+    // actions to be refreshed one by one until they are finished
+    in_progress: HashMap<u64, BasicActionInfo>,
+    // count of failed refreshes in a row of in progress actions
+    refresh_failures: HashMap<u64, u32>,
+    // finished since the last recording
+    finished: Vec<BasicActionInfo>,
+}
+
+#[derive(Clone)]
+pub struct ActionStoreImpl {
+    state: Arc<RwLock<ActionState>>,
+    client: Arc<dyn DigitalOceanClient>,
+    metrics: ActionMetrics,
+}
+
+impl ActionStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            state: Arc::new(RwLock::new(ActionState::default())),
+            client,
+            metrics: ActionMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct ActionMetrics {
+    actions_counter: prometheus::CounterVec,
+    in_progress_gauge: prometheus::GaugeVec,
+}
+
+impl ActionMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let actions_counter = prometheus::CounterVec::new(
+            Opts::new(
+                "droxporter_actions_total",
+                "Count of finished actions seen by the exporter",
+            ),
+            &["type", "status", "resource_type"],
+        )?;
+        let in_progress_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_actions_in_progress",
+                "Count of actions in progress",
+            ),
+            &["type", "resource_type"],
+        )?;
+
+        registry.register(Box::new(actions_counter.clone()))?;
+        registry.register(Box::new(in_progress_gauge.clone()))?;
+
+        let result = Self {
+            actions_counter,
+            in_progress_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl ActionStoreImpl {
+    // actions are listed from newest to oldest
+    async fn load_new_actions(
+        &self,
+        last_seen: Option<u64>,
+    ) -> anyhow::Result<Vec<ActionResponse>> {
+        let mut result: Vec<ActionResponse> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next && page <= MAX_PAGES {
+            let loaded = self.client.list_actions(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            for action in loaded.actions {
+                if last_seen.is_some_and(|x| action.id <= x) {
+                    fetch_next = false;
+                    break;
+                }
+                result.push(action);
+            }
+            // the first page is enough to find the newest action on startup
+            if last_seen.is_none() {
+                break;
+            }
+            page += 1;
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl ActionStore for ActionStoreImpl {
+    async fn load_actions(&self) -> anyhow::Result<()> {
+        let (last_seen, in_progress) = {
+            let state = self.state.read();
+            let ids: Vec<u64> = state.in_progress.keys().copied().collect();
+            (state.last_seen, ids)
+        };
+
+        let new_actions = self.load_new_actions(last_seen).await?;
+        let mut refreshed: Vec<ActionResponse> = Vec::new();
+        let mut failed: Vec<u64> = Vec::new();
+        for id in in_progress {
+            // a single failed action shouldn't stop the feed
+            match self.client.get_action(id).await {
+                Ok(loaded) => refreshed.push(loaded.action),
+                Err(e) => {
+                    error!("Action {id} refresh failed with err {e}");
+                    failed.push(id);
+                }
+            }
+        }
+
+        let mut state = self.state.write();
+        let first = state.last_seen.is_none();
+        if let Some(newest) = new_actions.iter().map(|x| x.id).max() {
+            state.last_seen = Some(newest.max(state.last_seen.unwrap_or_default()));
+        } else if first {
+            // no actions at all, everything from now on is new
+            state.last_seen = Some(0);
+        }
+        for action in refreshed.iter() {
+            state.refresh_failures.remove(&action.id);
+        }
+        for id in failed {
+            let failures = state.refresh_failures.entry(id).or_default();
+            *failures += 1;
+            if *failures >= MAX_REFRESH_FAILURES {
+                info!("Action {id} is dropped after {MAX_REFRESH_FAILURES} failed refreshes");
+                state.refresh_failures.remove(&id);
+                state.in_progress.remove(&id);
+            }
+        }
+        for action in new_actions.into_iter().chain(refreshed) {
+            let action = BasicActionInfo::from(action);
+            if action.status == IN_PROGRESS {
+                state.in_progress.insert(action.id, action);
+            } else if state.in_progress.remove(&action.id).is_some() || !first {
+                state.finished.push(action);
+            }
+        }
+        Ok(())
+    }
+
+    fn record_action_metrics(&self) {
+        let mut state = self.state.write();
+
+        for action in state.finished.drain(..) {
+            self.metrics
+                .actions_counter
+                .with_label_values(&[
+                    action.action_type.as_str(),
+                    action.status.as_str(),
+                    action.resource_type.as_str(),
+                ])
+                .inc();
+        }
+
+        let mut in_progress: HashMap<(&str, &str), u64> = HashMap::default();
+        for action in state.in_progress.values() {
+            *in_progress
+                .entry((action.action_type.as_str(), action.resource_type.as_str()))
+                .or_default() += 1;
+        }
+        for ((action_type, resource_type), count) in in_progress.iter() {
+            self.metrics
+                .in_progress_gauge
+                .with_label_values(&[action_type, resource_type])
+                .set(*count as f64);
+        }
+
+        // finished actions are not in progress anymore
+        let valid_series: HashSet<&(&str, &str)> = in_progress.keys().collect();
+        utils::retain_gauge_series(&self.metrics.in_progress_gauge, |labels| {
+            let key = (
+                labels.get("type").copied().unwrap_or_default(),
+                labels.get("resource_type").copied().unwrap_or_default(),
+            );
+            valid_series.contains(&key)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{GetActionResponse, Links, ListActionsResponse};
+    use prometheus::core::Collector;
+
+    fn action(id: u64, action_type: &str, status: &str) -> ActionResponse {
+        ActionResponse {
+            id,
+            status: status.to_string(),
+            action_type: action_type.to_string(),
+            resource_id: Some(3164444),
+            resource_type: "droplet".to_string(),
+        }
+    }
+
+    fn actions(actions: Vec<ActionResponse>) -> ListActionsResponse {
+        ListActionsResponse {
+            actions,
+            links: Links::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_count_only_new_actions() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        let mut seq = mockall::Sequence::new();
+        mock_client
+            .expect_list_actions()
+            .withf(|per_page, page| *per_page == 100 && *page == 1)
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| {
+                Ok(actions(vec![
+                    action(3, "resize", "in-progress"),
+                    action(2, "reboot", "completed"),
+                ]))
+            });
+        mock_client
+            .expect_list_actions()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| {
+                Ok(actions(vec![
+                    action(5, "power_cycle", "errored"),
+                    action(4, "snapshot", "in-progress"),
+                    action(3, "resize", "in-progress"),
+                    action(2, "reboot", "completed"),
+                ]))
+            });
+        mock_client
+            .expect_get_action()
+            .withf(|id| *id == 3)
+            .times(1)
+            .returning(|_| {
+                Ok(GetActionResponse {
+                    action: action(3, "resize", "completed"),
+                })
+            });
+
+        let registry = prometheus::Registry::new();
+        let store = ActionStoreImpl::new(Arc::new(mock_client), registry).unwrap();
+        let counter = |action_type: &str, status: &str| {
+            store
+                .metrics
+                .actions_counter
+                .with_label_values(&[action_type, status, "droplet"])
+                .get()
+        };
+        let in_progress = |action_type: &str| {
+            store
+                .metrics
+                .in_progress_gauge
+                .with_label_values(&[action_type, "droplet"])
+                .get()
+        };
+
+        store.load_actions().await.unwrap();
+        store.record_action_metrics();
+        // the history loaded on startup is not counted
+        assert_eq!(counter("reboot", "completed"), 0_f64);
+        assert_eq!(in_progress("resize"), 1_f64);
+
+        store.load_actions().await.unwrap();
+        store.record_action_metrics();
+        store.record_action_metrics();
+        assert_eq!(counter("reboot", "completed"), 0_f64);
+        assert_eq!(counter("resize", "completed"), 1_f64);
+        assert_eq!(counter("power_cycle", "errored"), 1_f64);
+        assert_eq!(in_progress("snapshot"), 1_f64);
+    }
+
+    #[tokio::test]
+    async fn test_stop_paging_on_last_seen_action() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        let mut seq = mockall::Sequence::new();
+        mock_client
+            .expect_list_actions()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(actions(vec![action(1, "reboot", "completed")])));
+        mock_client
+            .expect_list_actions()
+            .withf(|_, page| *page == 1)
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| {
+                Ok(ListActionsResponse {
+                    actions: vec![action(3, "reboot", "completed")],
+                    links: Links {
+                        pages: crate::client::do_json_protocol::Pages {
+                            next: Some("http://next".to_string()),
+                            ..Default::default()
+                        },
+                    },
+                })
+            });
+        mock_client
+            .expect_list_actions()
+            .withf(|_, page| *page == 2)
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| {
+                Ok(ListActionsResponse {
+                    actions: vec![
+                        action(2, "reboot", "completed"),
+                        action(1, "reboot", "completed"),
+                    ],
+                    links: Links {
+                        pages: crate::client::do_json_protocol::Pages {
+                            next: Some("http://next".to_string()),
+                            ..Default::default()
+                        },
+                    },
+                })
+            });
+
+        let registry = prometheus::Registry::new();
+        let store = ActionStoreImpl::new(Arc::new(mock_client), registry).unwrap();
+
+        store.load_actions().await.unwrap();
+        store.load_actions().await.unwrap();
+        store.record_action_metrics();
+
+        let reboots = store
+            .metrics
+            .actions_counter
+            .with_label_values(&["reboot", "completed", "droplet"])
+            .get();
+        assert_eq!(reboots, 2_f64);
+    }
+
+    #[tokio::test]
+    async fn test_drop_action_after_failed_refreshes() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        let mut seq = mockall::Sequence::new();
+        mock_client
+            .expect_list_actions()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(actions(vec![action(1, "resize", "in-progress")])));
+        mock_client
+            .expect_list_actions()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| {
+                Ok(actions(vec![
+                    action(2, "reboot", "completed"),
+                    action(1, "resize", "in-progress"),
+                ]))
+            });
+        mock_client
+            .expect_list_actions()
+            .times(2)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(actions(vec![action(2, "reboot", "completed")])));
+        // the droplet has been destroyed
+        mock_client
+            .expect_get_action()
+            .withf(|id| *id == 1)
+            .times(3)
+            .returning(|_| Err(anyhow::anyhow!("Request failed with status code: 404")));
+
+        let registry = prometheus::Registry::new();
+        let store = ActionStoreImpl::new(Arc::new(mock_client), registry).unwrap();
+        let in_progress = || {
+            store
+                .metrics
+                .in_progress_gauge
+                .with_label_values(&["resize", "droplet"])
+                .get()
+        };
+
+        store.load_actions().await.unwrap();
+        // new actions are still loaded
+        store.load_actions().await.unwrap();
+        store.record_action_metrics();
+        let reboots = store
+            .metrics
+            .actions_counter
+            .with_label_values(&["reboot", "completed", "droplet"])
+            .get();
+        assert_eq!(reboots, 1_f64);
+        assert_eq!(in_progress(), 1_f64);
+
+        store.load_actions().await.unwrap();
+        store.load_actions().await.unwrap();
+        store.record_action_metrics();
+        let series = store.metrics.in_progress_gauge.collect();
+        assert_eq!(series[0].get_metric().len(), 0);
+    }
+}
@@ -0,0 +1,569 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::{
+    AlertPolicyAlerts, AlertPolicyRequest, AlertPolicyResponse, AlertPolicySlack,
+};
+use crate::config::config_model::{AlertPolicyConfig, AppSettings};
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+use tracing::info;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AlertPolicyStore: Send + Sync {
+    async fn load_policies(&self) -> anyhow::Result<()>;
+
+    fn record_policy_metrics(&self);
+}
+
+#[derive(Clone)]
+pub struct BasicAlertPolicyInfo {
+    pub description: String,
+    pub policy_type: String,
+    pub compare: String,
+    pub value: f64,
+    pub window: String,
+    pub tags: Vec<String>,
+    pub enabled: bool,
+}
+
+impl From<AlertPolicyResponse> for BasicAlertPolicyInfo {
+    fn from(value: AlertPolicyResponse) -> Self {
+        Self {
+            description: value.description,
+            policy_type: value.policy_type,
+            compare: value.compare,
+            value: value.value,
+            window: value.window,
+            tags: value.tags,
+            enabled: value.enabled,
+        }
+    }
+}
+
+// both sides are normalized, so the order of tags, entities and destinations doesn't matter
+fn normalize(mut request: AlertPolicyRequest) -> AlertPolicyRequest {
+    request.entities.sort();
+    request.tags.sort();
+    request.alerts.email.sort();
+    request
+        .alerts
+        .slack
+        .sort_by(|a, b| (&a.channel, &a.url).cmp(&(&b.channel, &b.url)));
+    request
+}
+
+// managed policies are marked with the prefix, so the others can be told apart
+fn to_request(policy: &AlertPolicyConfig, prefix: &str) -> AlertPolicyRequest {
+    normalize(AlertPolicyRequest {
+        policy_type: policy.policy_type.clone(),
+        description: format!("{prefix}{}", policy.description),
+        compare: policy.compare.clone(),
+        value: policy.value,
+        window: policy.window.clone(),
+        entities: policy.entities.clone(),
+        tags: policy.tags.clone(),
+        alerts: AlertPolicyAlerts {
+            email: policy.emails.clone(),
+            slack: policy
+                .slack
+                .iter()
+                .map(|x| AlertPolicySlack {
+                    channel: x.channel.clone(),
+                    url: x.url.clone(),
+                })
+                .collect(),
+        },
+        enabled: policy.enabled,
+    })
+}
+
+fn response_to_request(policy: &AlertPolicyResponse) -> AlertPolicyRequest {
+    normalize(AlertPolicyRequest {
+        policy_type: policy.policy_type.clone(),
+        description: policy.description.clone(),
+        compare: policy.compare.clone(),
+        value: policy.value,
+        window: policy.window.clone(),
+        entities: policy.entities.clone(),
+        tags: policy.tags.clone(),
+        alerts: policy.alerts.clone(),
+        enabled: policy.enabled,
+    })
+}
+
+#[derive(Clone)]
+pub struct AlertPolicyStoreImpl {
+    store: Arc<RwLock<Vec<BasicAlertPolicyInfo>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    configs: &'static AppSettings,
+    metrics: AlertPolicyMetrics,
+}
+
+impl AlertPolicyStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        configs: &'static AppSettings,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            client,
+            configs,
+            metrics: AlertPolicyMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct AlertPolicyMetrics {
+    info_gauge: prometheus::GaugeVec,
+    threshold_gauge: prometheus::GaugeVec,
+    sync_counter: prometheus::CounterVec,
+}
+
+impl AlertPolicyMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_alert_policy_info",
+                "Monitoring alert policy with its type, comparison, window, tags and state. Values is always 1.",
+            ),
+            &["policy", "type", "compare", "window", "tags", "enabled"],
+        )?;
+        let threshold_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_alert_policy_threshold",
+                "Value the monitoring alert policy compares the metric with",
+            ),
+            &["policy"],
+        )?;
+        let sync_counter = prometheus::CounterVec::new(
+            Opts::new(
+                "droxporter_alert_policy_sync_total",
+                "Count of alert policies changed by the exporter to match the config",
+            ),
+            &["operation"],
+        )?;
+
+        registry.register(Box::new(info_gauge.clone()))?;
+        registry.register(Box::new(threshold_gauge.clone()))?;
+        registry.register(Box::new(sync_counter.clone()))?;
+
+        let result = Self {
+            info_gauge,
+            threshold_gauge,
+            sync_counter,
+        };
+        Ok(result)
+    }
+}
+
+impl AlertPolicyStoreImpl {
+    fn save_policies(&self, policies: Vec<BasicAlertPolicyInfo>) {
+        *self.store.write() = policies;
+    }
+
+    async fn fetch_policies(&self) -> anyhow::Result<Vec<AlertPolicyResponse>> {
+        let mut result: Vec<AlertPolicyResponse> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_alert_policies(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.policies);
+            page += 1;
+        }
+        Ok(result)
+    }
+
+    // makes policies from the API match the config and returns the resulting policies
+    async fn sync_policies(
+        &self,
+        existing: Vec<AlertPolicyResponse>,
+    ) -> anyhow::Result<Vec<AlertPolicyResponse>> {
+        let settings = &self.configs.alert_policies;
+        // an empty or broken config must never wipe the policies of the account
+        if settings.policies.is_empty() {
+            anyhow::bail!("Alert policies sync is enabled, but no policies are configured");
+        }
+        if settings.prefix.is_empty() {
+            anyhow::bail!("Alert policies sync requires a prefix to mark the managed policies");
+        }
+
+        let mut desired: HashMap<String, AlertPolicyRequest> = HashMap::default();
+        for policy in settings.policies.iter() {
+            let request = to_request(policy, settings.prefix.as_str());
+            if desired
+                .insert(request.description.clone(), request)
+                .is_some()
+            {
+                anyhow::bail!(
+                    "Alert policy description '{}' is not unique",
+                    policy.description
+                );
+            }
+        }
+
+        let mut result: Vec<AlertPolicyResponse> = Vec::new();
+        let mut matched: HashSet<String> = HashSet::default();
+        for policy in existing {
+            let request = desired
+                .get(policy.description.as_str())
+                .filter(|_| !matched.contains(policy.description.as_str()));
+            let Some(request) = request else {
+                // policies created in the console or by other tools are kept unless pruning is enabled
+                let owned = policy.description.starts_with(settings.prefix.as_str());
+                if !owned && !settings.prune {
+                    result.push(policy);
+                    continue;
+                }
+                // removed from the config, duplicates of the managed ones and pruned policies
+                info!("Deleting alert policy '{}'", policy.description);
+                self.client.delete_alert_policy(policy.uuid.clone()).await?;
+                self.metrics
+                    .sync_counter
+                    .with_label_values(&["delete"])
+                    .inc();
+                continue;
+            };
+            matched.insert(policy.description.clone());
+
+            if *request == response_to_request(&policy) {
+                result.push(policy);
+                continue;
+            }
+            info!("Updating alert policy '{}'", policy.description);
+            let updated = self
+                .client
+                .update_alert_policy(policy.uuid.clone(), request.clone())
+                .await?;
+            self.metrics
+                .sync_counter
+                .with_label_values(&["update"])
+                .inc();
+            result.push(updated.policy);
+        }
+
+        for policy in settings.policies.iter() {
+            let request = to_request(policy, settings.prefix.as_str());
+            if matched.contains(request.description.as_str()) {
+                continue;
+            }
+            info!("Creating alert policy '{}'", request.description);
+            let created = self.client.create_alert_policy(request).await?;
+            self.metrics
+                .sync_counter
+                .with_label_values(&["create"])
+                .inc();
+            result.push(created.policy);
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl AlertPolicyStore for AlertPolicyStoreImpl {
+    async fn load_policies(&self) -> anyhow::Result<()> {
+        let mut policies = self.fetch_policies().await?;
+        if self.configs.alert_policies.sync {
+            policies = self.sync_policies(policies).await?;
+        }
+        self.save_policies(
+            policies
+                .into_iter()
+                .map(BasicAlertPolicyInfo::from)
+                .collect(),
+        );
+        Ok(())
+    }
+
+    fn record_policy_metrics(&self) {
+        let lock = self.store.read();
+
+        let mut valid_info: HashSet<Vec<String>> = HashSet::default();
+        let mut valid_policies: HashSet<&str> = HashSet::default();
+        for policy in lock.iter() {
+            let mut tags = policy.tags.clone();
+            tags.sort();
+            let labels = vec![
+                policy.description.clone(),
+                policy.policy_type.clone(),
+                policy.compare.clone(),
+                policy.window.clone(),
+                tags.join(","),
+                policy.enabled.to_string(),
+            ];
+            let label_refs: Vec<&str> = labels.iter().map(|x| x.as_str()).collect();
+            self.metrics
+                .info_gauge
+                .with_label_values(&label_refs)
+                .set(1_f64);
+            self.metrics
+                .threshold_gauge
+                .with_label_values(&[policy.description.as_str()])
+                .set(policy.value);
+            valid_info.insert(labels);
+            valid_policies.insert(policy.description.as_str());
+        }
+
+        // to prevent phantom policies after they were changed or removed
+        utils::retain_gauge_series(&self.metrics.info_gauge, |labels| {
+            let key: Vec<String> = ["policy", "type", "compare", "window", "tags", "enabled"]
+                .iter()
+                .map(|x| labels.get(x).copied().unwrap_or_default().to_string())
+                .collect();
+            valid_info.contains(&key)
+        });
+        utils::retain_gauge_series(&self.metrics.threshold_gauge, |labels| {
+            let policy = labels.get("policy").copied().unwrap_or_default();
+            valid_policies.contains(policy)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{
+        Links, ListAlertPoliciesResponse, SingleAlertPolicyResponse,
+    };
+
+    fn create_test_config(sync: bool, policies: Vec<AlertPolicyConfig>) -> &'static AppSettings {
+        let mut config = AppSettings::default();
+        config.alert_policies.enabled = true;
+        config.alert_policies.prefix = "[droxporter] ".to_string();
+        config.alert_policies.sync = sync;
+        config.alert_policies.policies = policies;
+        Box::leak(Box::new(config))
+    }
+
+    fn policy_config(description: &str, value: f64) -> AlertPolicyConfig {
+        AlertPolicyConfig {
+            description: description.to_string(),
+            policy_type: "v1/insights/droplet/cpu".to_string(),
+            compare: "GreaterThan".to_string(),
+            value,
+            window: "5m".to_string(),
+            entities: vec![],
+            tags: vec!["web".to_string(), "production".to_string()],
+            emails: vec!["bob@example.com".to_string()],
+            slack: vec![],
+            enabled: true,
+        }
+    }
+
+    fn policy(uuid: &str, description: &str, value: f64) -> AlertPolicyResponse {
+        AlertPolicyResponse {
+            uuid: uuid.to_string(),
+            policy_type: "v1/insights/droplet/cpu".to_string(),
+            description: description.to_string(),
+            compare: "GreaterThan".to_string(),
+            value,
+            window: "5m".to_string(),
+            entities: vec![],
+            tags: vec!["production".to_string(), "web".to_string()],
+            alerts: AlertPolicyAlerts {
+                email: vec!["bob@example.com".to_string()],
+                slack: vec![],
+            },
+            enabled: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_policy_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_alert_policies()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListAlertPoliciesResponse {
+                    policies: vec![policy("uuid-1", "CPU Alert", 80_f64)],
+                    links: Links::default(),
+                })
+            });
+        // policies are not changed without sync
+        mock_client.expect_delete_alert_policy().never();
+
+        let config = create_test_config(false, vec![]);
+        let registry = prometheus::Registry::new();
+        let store = AlertPolicyStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+
+        store.load_policies().await.unwrap();
+        store.record_policy_metrics();
+
+        let info = store
+            .metrics
+            .info_gauge
+            .with_label_values(&[
+                "CPU Alert",
+                "v1/insights/droplet/cpu",
+                "GreaterThan",
+                "5m",
+                "production,web",
+                "true",
+            ])
+            .get();
+        assert_eq!(info, 1_f64);
+        let threshold = store
+            .metrics
+            .threshold_gauge
+            .with_label_values(&["CPU Alert"])
+            .get();
+        assert_eq!(threshold, 80_f64);
+    }
+
+    #[tokio::test]
+    async fn test_sync_policies() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_alert_policies()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListAlertPoliciesResponse {
+                    policies: vec![
+                        policy("uuid-1", "[droxporter] CPU Alert", 80_f64),
+                        policy("uuid-2", "[droxporter] Memory Alert", 80_f64),
+                        policy("uuid-3", "[droxporter] Removed", 10_f64),
+                        // created in the console
+                        policy("uuid-5", "Foreign", 10_f64),
+                    ],
+                    links: Links::default(),
+                })
+            });
+        mock_client
+            .expect_update_alert_policy()
+            .withf(|uuid, request| uuid == "uuid-2" && request.value == 90_f64)
+            .times(1)
+            .returning(|_, _| {
+                Ok(SingleAlertPolicyResponse {
+                    policy: policy("uuid-2", "[droxporter] Memory Alert", 90_f64),
+                })
+            });
+        mock_client
+            .expect_delete_alert_policy()
+            .withf(|uuid| uuid == "uuid-3")
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_client
+            .expect_create_alert_policy()
+            .withf(|request| request.description == "[droxporter] Disk Alert")
+            .times(1)
+            .returning(|_| {
+                Ok(SingleAlertPolicyResponse {
+                    policy: policy("uuid-4", "[droxporter] Disk Alert", 95_f64),
+                })
+            });
+
+        let config = create_test_config(
+            true,
+            vec![
+                policy_config("CPU Alert", 80_f64),
+                policy_config("Memory Alert", 90_f64),
+                policy_config("Disk Alert", 95_f64),
+            ],
+        );
+        let registry = prometheus::Registry::new();
+        let store = AlertPolicyStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+
+        store.load_policies().await.unwrap();
+
+        let mut policies: Vec<String> = store
+            .store
+            .read()
+            .iter()
+            .map(|x| format!("{}:{}", x.description, x.value))
+            .collect();
+        policies.sort();
+        // the foreign policy survives the sync
+        assert_eq!(
+            policies,
+            vec![
+                "Foreign:10",
+                "[droxporter] CPU Alert:80",
+                "[droxporter] Disk Alert:95",
+                "[droxporter] Memory Alert:90",
+            ]
+        );
+
+        for operation in ["create", "update", "delete"] {
+            let count = store
+                .metrics
+                .sync_counter
+                .with_label_values(&[operation])
+                .get();
+            assert_eq!(count, 1_f64);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prune_policies() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_alert_policies()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListAlertPoliciesResponse {
+                    policies: vec![
+                        policy("uuid-1", "[droxporter] CPU Alert", 80_f64),
+                        policy("uuid-5", "Foreign", 10_f64),
+                    ],
+                    links: Links::default(),
+                })
+            });
+        mock_client
+            .expect_delete_alert_policy()
+            .withf(|uuid| uuid == "uuid-5")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut config = AppSettings::default();
+        config.alert_policies.enabled = true;
+        config.alert_policies.prefix = "[droxporter] ".to_string();
+        config.alert_policies.sync = true;
+        config.alert_policies.prune = true;
+        config.alert_policies.policies = vec![policy_config("CPU Alert", 80_f64)];
+        let config: &'static AppSettings = Box::leak(Box::new(config));
+        let registry = prometheus::Registry::new();
+        let store = AlertPolicyStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+
+        store.load_policies().await.unwrap();
+
+        let policies: Vec<String> = store
+            .store
+            .read()
+            .iter()
+            .map(|x| x.description.clone())
+            .collect();
+        assert_eq!(policies, vec!["[droxporter] CPU Alert"]);
+    }
+
+    #[tokio::test]
+    async fn test_sync_without_policies() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_alert_policies()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListAlertPoliciesResponse {
+                    policies: vec![policy("uuid-1", "[droxporter] CPU Alert", 80_f64)],
+                    links: Links::default(),
+                })
+            });
+        // an empty list is treated as a broken config, not as a request to delete everything
+        mock_client.expect_delete_alert_policy().never();
+
+        let config = create_test_config(true, vec![]);
+        let registry = prometheus::Registry::new();
+        let store = AlertPolicyStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+
+        assert!(store.load_policies().await.is_err());
+    }
+}
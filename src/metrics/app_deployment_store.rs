@@ -0,0 +1,454 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::AppDeploymentResponse;
+use crate::metrics::app_store::AppStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+
+// deployments are listed from newest to oldest, so the first page is enough to catch up
+// on everything finished since the previous interval
+const DEPLOYMENTS_PER_PAGE: u64 = 20;
+
+// final phases counted by `droxporter_app_deployments_total`
+const FINAL_PHASES: [&str; 3] = ["ACTIVE", "ERROR", "CANCELED"];
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AppDeploymentStore: Send + Sync {
+    async fn load_deployments(&self) -> anyhow::Result<()>;
+
+    fn record_deployment_metrics(&self);
+}
+
+#[derive(Clone)]
+pub struct BasicAppDeploymentInfo {
+    pub id: String,
+    pub app: String,
+    pub cause: String,
+    pub phase: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub phase_last_updated_at: DateTime<Utc>,
+}
+
+impl BasicAppDeploymentInfo {
+    fn new(app: &str, value: AppDeploymentResponse) -> Self {
+        let updated_at = value.updated_at.unwrap_or(value.created_at);
+        Self {
+            id: value.id,
+            app: app.to_string(),
+            cause: value.cause,
+            phase: value.phase,
+            created_at: value.created_at,
+            updated_at,
+            phase_last_updated_at: value.phase_last_updated_at.unwrap_or(updated_at),
+        }
+    }
+
+    // a superseded deployment has been active before
+    fn final_phase(&self) -> Option<&'static str> {
+        match self.phase.as_str() {
+            "ACTIVE" | "SUPERSEDED" => Some("ACTIVE"),
+            "ERROR" => Some("ERROR"),
+            "CANCELED" => Some("CANCELED"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AppDeploymentStoreImpl {
+    // recent deployments of every app, newest first
+    store: Arc<RwLock<Vec<BasicAppDeploymentInfo>>>,
+    // app -> ids of deployments already counted. The first load of an app only seeds it,
+    // so the history of apps seen on startup or added later is not counted as new deployments
+    counted: Arc<RwLock<HashMap<String, HashSet<String>>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    app_store: Arc<dyn AppStore>,
+    metrics: AppDeploymentMetrics,
+}
+
+impl AppDeploymentStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        app_store: Arc<dyn AppStore>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            counted: Arc::new(RwLock::new(HashMap::default())),
+            client,
+            app_store,
+            metrics: AppDeploymentMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct AppDeploymentMetrics {
+    info_gauge: prometheus::GaugeVec,
+    created_gauge: prometheus::GaugeVec,
+    updated_gauge: prometheus::GaugeVec,
+    duration_gauge: prometheus::GaugeVec,
+    deployments_counter: prometheus::CounterVec,
+}
+
+impl AppDeploymentMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_last_deployment_info",
+                "The last deployment of the app with its cause and phase. Values is always 1.",
+            ),
+            &["app", "cause", "phase"],
+        )?;
+        let created_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_last_deployment_created_timestamp_seconds",
+                "Unix timestamp of the last deployment creation",
+            ),
+            &["app"],
+        )?;
+        let updated_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_last_deployment_updated_timestamp_seconds",
+                "Unix timestamp of the last deployment update",
+            ),
+            &["app"],
+        )?;
+        let duration_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_last_deployment_duration_seconds",
+                "Seconds between the last deployment creation and its last phase change",
+            ),
+            &["app"],
+        )?;
+        let deployments_counter = prometheus::CounterVec::new(
+            Opts::new(
+                "droxporter_app_deployments_total",
+                "Count of finished deployments observed by the exporter",
+            ),
+            &["app", "phase"],
+        )?;
+
+        registry.register(Box::new(info_gauge.clone()))?;
+        registry.register(Box::new(created_gauge.clone()))?;
+        registry.register(Box::new(updated_gauge.clone()))?;
+        registry.register(Box::new(duration_gauge.clone()))?;
+        registry.register(Box::new(deployments_counter.clone()))?;
+
+        let result = Self {
+            info_gauge,
+            created_gauge,
+            updated_gauge,
+            duration_gauge,
+            deployments_counter,
+        };
+        Ok(result)
+    }
+}
+
+impl AppDeploymentStoreImpl {
+    fn save_deployments(&self, deployments: Vec<BasicAppDeploymentInfo>) {
+        *self.store.write() = deployments;
+    }
+}
+
+#[async_trait]
+impl AppDeploymentStore for AppDeploymentStoreImpl {
+    async fn load_deployments(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicAppDeploymentInfo> = Vec::new();
+        for app in self.app_store.list_apps() {
+            let loaded = self
+                .client
+                .list_app_deployments(app.id.clone(), DEPLOYMENTS_PER_PAGE, 1)
+                .await?;
+            result.extend(
+                loaded
+                    .deployments
+                    .into_iter()
+                    .map(|x| BasicAppDeploymentInfo::new(app.name.as_str(), x)),
+            );
+        }
+        self.save_deployments(result);
+        Ok(())
+    }
+
+    fn record_deployment_metrics(&self) {
+        let lock = self.store.read();
+
+        let mut last: HashMap<&str, &BasicAppDeploymentInfo> = HashMap::default();
+        for deployment in lock.iter() {
+            let entry = last.entry(deployment.app.as_str()).or_insert(deployment);
+            if entry.created_at < deployment.created_at {
+                *entry = deployment;
+            }
+        }
+
+        for (app, deployment) in last.iter() {
+            self.metrics
+                .info_gauge
+                .with_label_values(&[app, deployment.cause.as_str(), deployment.phase.as_str()])
+                .set(1_f64);
+            self.metrics
+                .created_gauge
+                .with_label_values(&[app])
+                .set(deployment.created_at.timestamp() as f64);
+            self.metrics
+                .updated_gauge
+                .with_label_values(&[app])
+                .set(deployment.updated_at.timestamp() as f64);
+            let duration = deployment.phase_last_updated_at - deployment.created_at;
+            self.metrics
+                .duration_gauge
+                .with_label_values(&[app])
+                .set(duration.num_milliseconds().max(0) as f64 / 1000_f64);
+            // to have a zero value to calculate increase() against
+            for phase in FINAL_PHASES {
+                self.metrics
+                    .deployments_counter
+                    .with_label_values(&[app, phase])
+                    .inc_by(0_f64);
+            }
+        }
+
+        let mut loaded: HashMap<&str, Vec<&BasicAppDeploymentInfo>> = HashMap::default();
+        for deployment in lock.iter() {
+            loaded
+                .entry(deployment.app.as_str())
+                .or_default()
+                .push(deployment);
+        }
+        let mut counted = self.counted.write();
+        for (app, deployments) in loaded.iter() {
+            let finished = deployments
+                .iter()
+                .filter_map(|x| x.final_phase().map(|phase| (x.id.as_str(), phase)));
+            let Some(app_counted) = counted.get_mut(*app) else {
+                let seed = finished.map(|(id, _)| id.to_string()).collect();
+                counted.insert(app.to_string(), seed);
+                continue;
+            };
+            for (id, phase) in finished {
+                if app_counted.insert(id.to_string()) {
+                    self.metrics
+                        .deployments_counter
+                        .with_label_values(&[app, phase])
+                        .inc();
+                }
+            }
+            // deployments which have left the first page won't be seen again
+            app_counted.retain(|x| deployments.iter().any(|d| d.id == *x));
+        }
+        counted.retain(|app, _| loaded.contains_key(app.as_str()));
+
+        let apps: HashSet<&str> = last.keys().copied().collect();
+
+        // to prevent phantom apps and deployments
+        utils::retain_gauge_series(&self.metrics.info_gauge, |labels| {
+            let app = labels.get("app").copied().unwrap_or_default();
+            last.get(app).is_some_and(|x| {
+                labels.get("cause").copied() == Some(x.cause.as_str())
+                    && labels.get("phase").copied() == Some(x.phase.as_str())
+            })
+        });
+        utils::remove_old_apps_for_gauge_metric(&self.metrics.created_gauge, &apps);
+        utils::remove_old_apps_for_gauge_metric(&self.metrics.updated_gauge, &apps);
+        utils::remove_old_apps_for_gauge_metric(&self.metrics.duration_gauge, &apps);
+        utils::remove_old_apps_for_counter_metric(&self.metrics.deployments_counter, &apps);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{Links, ListAppDeploymentsResponse};
+    use crate::metrics::app_store::{BasicAppInfo, MockAppStore};
+
+    fn deployment(id: &str, phase: &str, created_at: &str) -> AppDeploymentResponse {
+        AppDeploymentResponse {
+            id: id.to_string(),
+            cause: "manual".to_string(),
+            phase: phase.to_string(),
+            created_at: created_at.parse().unwrap(),
+            updated_at: None,
+            phase_last_updated_at: None,
+        }
+    }
+
+    fn create_app_store() -> MockAppStore {
+        let mut mock_store = MockAppStore::new();
+        mock_store.expect_list_apps().returning(|| {
+            vec![BasicAppInfo {
+                id: "app-123".to_string(),
+                name: "web".to_string(),
+                active_deployment_phase: "ACTIVE".to_string(),
+                ..Default::default()
+            }]
+        });
+        mock_store
+    }
+
+    #[tokio::test]
+    async fn test_record_last_deployment_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        mock_client
+            .expect_list_app_deployments()
+            .withf(|id, per_page, page| id == "app-123" && *per_page == 20 && *page == 1)
+            .times(1)
+            .returning(|_, _, _| {
+                let mut last = deployment("dep-2", "ERROR", "2024-08-30T13:57:54Z");
+                last.phase_last_updated_at = Some("2024-08-30T13:58:33.500Z".parse().unwrap());
+                Ok(ListAppDeploymentsResponse {
+                    deployments: vec![
+                        last,
+                        deployment("dep-1", "SUPERSEDED", "2024-08-29T13:57:54Z"),
+                    ],
+                    links: Links::default(),
+                })
+            });
+
+        let registry = prometheus::Registry::new();
+        let store = AppDeploymentStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(create_app_store()),
+            registry,
+        )
+        .unwrap();
+        store.load_deployments().await.unwrap();
+        store.record_deployment_metrics();
+
+        let info = store
+            .metrics
+            .info_gauge
+            .with_label_values(&["web", "manual", "ERROR"])
+            .get();
+        assert_eq!(info, 1_f64);
+        let duration = store
+            .metrics
+            .duration_gauge
+            .with_label_values(&["web"])
+            .get();
+        assert_eq!(duration, 39.5_f64);
+        let created = store
+            .metrics
+            .created_gauge
+            .with_label_values(&["web"])
+            .get();
+        assert_eq!(created, 1725026274_f64);
+    }
+
+    #[tokio::test]
+    async fn test_count_only_new_finished_deployments() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        let mut seq = mockall::Sequence::new();
+        mock_client
+            .expect_list_app_deployments()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| {
+                Ok(ListAppDeploymentsResponse {
+                    deployments: vec![
+                        deployment("dep-2", "DEPLOYING", "2024-08-30T13:57:54Z"),
+                        deployment("dep-1", "ERROR", "2024-08-29T13:57:54Z"),
+                    ],
+                    links: Links::default(),
+                })
+            });
+        mock_client
+            .expect_list_app_deployments()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| {
+                Ok(ListAppDeploymentsResponse {
+                    deployments: vec![
+                        deployment("dep-3", "CANCELED", "2024-08-31T13:57:54Z"),
+                        deployment("dep-2", "ACTIVE", "2024-08-30T13:57:54Z"),
+                        deployment("dep-1", "ERROR", "2024-08-29T13:57:54Z"),
+                    ],
+                    links: Links::default(),
+                })
+            });
+
+        let registry = prometheus::Registry::new();
+        let store = AppDeploymentStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(create_app_store()),
+            registry,
+        )
+        .unwrap();
+        let counter = |phase: &str| {
+            store
+                .metrics
+                .deployments_counter
+                .with_label_values(&["web", phase])
+                .get()
+        };
+
+        store.load_deployments().await.unwrap();
+        store.record_deployment_metrics();
+        // the history loaded on startup is not counted
+        assert_eq!(counter("ERROR"), 0_f64);
+
+        store.load_deployments().await.unwrap();
+        store.record_deployment_metrics();
+        store.record_deployment_metrics();
+        assert_eq!(counter("ERROR"), 0_f64);
+        assert_eq!(counter("ACTIVE"), 1_f64);
+        assert_eq!(counter("CANCELED"), 1_f64);
+    }
+
+    #[test]
+    fn test_seed_apps_loaded_after_first_recording() {
+        let registry = prometheus::Registry::new();
+        let store = AppDeploymentStoreImpl::new(
+            Arc::new(MockDigitalOceanClient::new()),
+            Arc::new(create_app_store()),
+            registry,
+        )
+        .unwrap();
+        let counter = |phase: &str| {
+            store
+                .metrics
+                .deployments_counter
+                .with_label_values(&["web", phase])
+                .get()
+        };
+
+        // apps are not loaded yet
+        store.record_deployment_metrics();
+
+        let history = vec![
+            BasicAppDeploymentInfo::new(
+                "web",
+                deployment("dep-2", "ACTIVE", "2024-08-30T13:57:54Z"),
+            ),
+            BasicAppDeploymentInfo::new(
+                "web",
+                deployment("dep-1", "ERROR", "2024-08-29T13:57:54Z"),
+            ),
+        ];
+        store.save_deployments(history.clone());
+        store.record_deployment_metrics();
+        assert_eq!(counter("ACTIVE"), 0_f64);
+        assert_eq!(counter("ERROR"), 0_f64);
+
+        let mut deployments = vec![BasicAppDeploymentInfo::new(
+            "web",
+            deployment("dep-3", "ACTIVE", "2024-08-31T13:57:54Z"),
+        )];
+        deployments.extend(history);
+        store.save_deployments(deployments);
+        store.record_deployment_metrics();
+        assert_eq!(counter("ACTIVE"), 1_f64);
+        assert_eq!(counter("ERROR"), 0_f64);
+    }
+}
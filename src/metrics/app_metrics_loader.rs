@@ -18,6 +18,7 @@ pub trait AppMetricsService: Send + Sync {
         interval_start: DateTime<Utc>,
         interval_end: DateTime<Utc>,
     ) -> anyhow::Result<()>;
+    async fn load_bandwidth_daily(&self) -> anyhow::Result<()>;
 }
 
 #[derive(Clone)]
@@ -48,6 +49,7 @@ struct LoaderAppMetrics {
     app_cpu_percentage: prometheus::GaugeVec,
     app_memory_percentage: prometheus::GaugeVec,
     app_restart_count: prometheus::CounterVec,
+    app_bandwidth_daily: prometheus::GaugeVec,
 }
 
 impl LoaderAppMetrics {
@@ -64,13 +66,22 @@ impl LoaderAppMetrics {
             Opts::new("droxporter_app_restart_count", "App restart count"),
             &["app", "app_component", "app_component_instance"],
         )?;
+        let app_bandwidth_daily = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_bandwidth_bytes_daily",
+                "App bandwidth usage in bytes for the day reported by the API",
+            ),
+            &["app"],
+        )?;
         registry.register(Box::new(app_cpu_percentage.clone()))?;
         registry.register(Box::new(app_memory_percentage.clone()))?;
         registry.register(Box::new(app_restart_count.clone()))?;
+        registry.register(Box::new(app_bandwidth_daily.clone()))?;
         let result = Self {
             app_cpu_percentage,
             app_memory_percentage,
             app_restart_count,
+            app_bandwidth_daily,
         };
         Ok(result)
     }
@@ -119,6 +130,9 @@ fn sum_for_app_metrics(metrics: &AppMetricsResponse) -> f64 {
         .sum()
 }
 
+// how many apps are requested at once by bandwidth_daily
+const BANDWIDTH_DAILY_BATCH_SIZE: usize = 100;
+
 fn metrics_read_interval() -> Duration {
     // It seems that DO has a 10..15 second interval between points, so I think an interval of 1 minute is reasonable.
     Duration::minutes(1)
@@ -211,6 +225,34 @@ impl AppMetricsService for AppMetricsServiceImpl {
 
         Ok(())
     }
+
+    async fn load_bandwidth_daily(&self) -> anyhow::Result<()> {
+        let apps = self.app_store.list_apps();
+        let names: ahash::HashMap<&str, &str> = apps
+            .iter()
+            .map(|x| (x.id.as_str(), x.name.as_str()))
+            .collect();
+
+        for batch in apps.chunks(BANDWIDTH_DAILY_BATCH_SIZE) {
+            let app_ids = batch.iter().map(|x| x.id.clone()).collect();
+            let res = self.client.get_app_bandwidth_daily(app_ids).await?;
+            for usage in res.app_bandwidth_usage {
+                let Some(app) = names.get(usage.app_id.as_str()) else {
+                    continue;
+                };
+                let value = usage.bandwidth_bytes.parse::<f64>().unwrap_or(0f64);
+                self.metrics
+                    .app_bandwidth_daily
+                    .with_label_values(&[app])
+                    .set(value);
+            }
+        }
+
+        let apps_names: ahash::HashSet<_> = apps.iter().map(|x| x.name.as_str()).collect();
+        utils::remove_old_apps_for_gauge_metric(&self.metrics.app_bandwidth_daily, &apps_names);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -218,7 +260,8 @@ mod tests {
     use super::*;
     use crate::client::do_client::MockDigitalOceanClient;
     use crate::client::do_json_protocol::{
-        AppDataResponse, AppDataResult, AppMetricMetaInfo, AppMetricsResponse, MetricPoint,
+        AppBandwidthDailyResponse, AppBandwidthUsageResponse, AppDataResponse, AppDataResult,
+        AppMetricMetaInfo, AppMetricsResponse, MetricPoint,
     };
     use crate::config::config_model::AppSettings;
     use crate::metrics::app_store::{BasicAppInfo, MockAppStore};
@@ -264,6 +307,12 @@ mod tests {
                     interval: StdDuration::from_secs(60),
                     keys: vec![],
                 }),
+                bandwidth_daily: Some(crate::config::config_model::AppBandwidthDailySettings {
+                    enabled: true,
+                    interval: StdDuration::from_secs(60),
+                    keys: vec![],
+                    url: "http://test.com/apps/metrics/bandwidth_daily".to_string(),
+                }),
             },
             exporter_metrics: crate::config::config_model::ExporterMetricsConfigs {
                 enabled: false,
@@ -280,6 +329,7 @@ mod tests {
                 prefix: None,
                 labels: std::collections::HashMap::new(),
             },
+            ..Default::default()
         };
         Box::leak(Box::new(config))
     }
@@ -293,6 +343,7 @@ mod tests {
             id: "app-123".to_string(),
             name: "test-app".to_string(),
             active_deployment_phase: "ACTIVE".to_string(),
+            ..Default::default()
         }];
 
         mock_store
@@ -348,6 +399,7 @@ mod tests {
             id: "app-456".to_string(),
             name: "test-app-2".to_string(),
             active_deployment_phase: "ACTIVE".to_string(),
+            ..Default::default()
         }];
 
         mock_store
@@ -403,6 +455,7 @@ mod tests {
             id: "app-789".to_string(),
             name: "test-app-3".to_string(),
             active_deployment_phase: "ACTIVE".to_string(),
+            ..Default::default()
         }];
 
         mock_store
@@ -457,4 +510,61 @@ mod tests {
         let result = service.load_restart_count(start, end).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_load_bandwidth_daily_in_batches() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        let mut mock_store = MockAppStore::new();
+
+        let apps: Vec<BasicAppInfo> = (0..150)
+            .map(|i| BasicAppInfo {
+                id: format!("app-{i}"),
+                name: format!("test-app-{i}"),
+                active_deployment_phase: "ACTIVE".to_string(),
+                ..Default::default()
+            })
+            .collect();
+
+        mock_store
+            .expect_list_apps()
+            .times(1)
+            .returning(move || apps.clone());
+
+        mock_client
+            .expect_get_app_bandwidth_daily()
+            .withf(|ids| ids.len() == 100 || ids.len() == 50)
+            .times(2)
+            .returning(|ids| {
+                Ok(AppBandwidthDailyResponse {
+                    app_bandwidth_usage: ids
+                        .iter()
+                        .map(|id| AppBandwidthUsageResponse {
+                            app_id: id.clone(),
+                            bandwidth_bytes: "513668".to_string(),
+                        })
+                        .collect(),
+                    date: None,
+                })
+            });
+
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let service = AppMetricsServiceImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_store),
+            config,
+            registry,
+        )
+        .unwrap();
+
+        let result = service.load_bandwidth_daily().await;
+        assert!(result.is_ok());
+
+        let value = service
+            .metrics
+            .app_bandwidth_daily
+            .with_label_values(&["test-app-149"])
+            .get();
+        assert_eq!(value, 513668_f64);
+    }
 }
@@ -1,12 +1,21 @@
 use crate::client::do_client::DigitalOceanClient;
-use crate::client::do_json_protocol::AppResponse;
+use crate::client::do_json_protocol::{
+    AppAlertResponse, AppComponentHealthResponse, AppComponentSpec, AppResponse,
+};
 use crate::config::config_model::{AppMetricsTypes, AppSettings};
+use crate::metrics::project_store::ProjectStore;
 use crate::metrics::utils;
-use ahash::HashSet;
+use ahash::{HashMap, HashSet};
 use async_trait::async_trait;
 use parking_lot::RwLock;
 use prometheus::Opts;
 use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::error;
+
+// details are loaded for several apps at once, but not for all of them to respect the API rate limit
+const APP_DETAILS_CONCURRENCY: usize = 8;
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -18,22 +27,108 @@ pub trait AppStore: Send + Sync {
     fn list_apps(&self) -> Vec<BasicAppInfo>;
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct BasicAppInfo {
     pub id: String,
     pub name: String,
     pub active_deployment_phase: String,
+    pub components: Vec<BasicAppComponentInfo>,
+    // loaded only if `alerts` metrics are enabled
+    pub alerts: Vec<BasicAppAlertInfo>,
+    // loaded only if `health` metrics are enabled
+    pub health: Vec<BasicAppComponentHealthInfo>,
+}
+
+#[derive(Clone, Default)]
+pub struct BasicAppComponentInfo {
+    pub name: String,
+    // service, worker, job, static_site or database
+    pub component_type: &'static str,
+    pub instance_size: String,
+    pub instance_count: Option<u64>,
+}
+
+impl BasicAppComponentInfo {
+    fn new(component_type: &'static str, value: AppComponentSpec) -> Self {
+        Self {
+            name: value.name,
+            component_type,
+            instance_size: value.instance_size_slug.unwrap_or_default(),
+            instance_count: value.instance_count,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct BasicAppAlertInfo {
+    // empty for app level alerts
+    pub component: String,
+    pub rule: String,
+    pub phase: String,
+    pub disabled: bool,
+    pub emails: usize,
+    pub slack_webhooks: usize,
+}
+
+impl From<AppAlertResponse> for BasicAppAlertInfo {
+    fn from(value: AppAlertResponse) -> Self {
+        Self {
+            component: value.component_name,
+            rule: value.spec.rule,
+            phase: value.phase,
+            disabled: value.spec.disabled,
+            emails: value.emails.len(),
+            slack_webhooks: value.slack_webhooks.len(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct BasicAppComponentHealthInfo {
+    pub component: String,
+    pub state: String,
+    pub replicas_desired: u64,
+    pub replicas_ready: u64,
+}
+
+impl From<AppComponentHealthResponse> for BasicAppComponentHealthInfo {
+    fn from(value: AppComponentHealthResponse) -> Self {
+        Self {
+            component: value.name,
+            state: value.state,
+            replicas_desired: value.replicas_desired,
+            replicas_ready: value.replicas_ready,
+        }
+    }
 }
 
 impl From<AppResponse> for BasicAppInfo {
     fn from(value: AppResponse) -> Self {
+        let spec = value.spec;
+        let components = [
+            ("service", spec.services),
+            ("worker", spec.workers),
+            ("job", spec.jobs),
+            ("static_site", spec.static_sites),
+            ("database", spec.databases),
+        ]
+        .into_iter()
+        .flat_map(|(component_type, components)| {
+            components
+                .into_iter()
+                .map(move |x| BasicAppComponentInfo::new(component_type, x))
+        })
+        .collect();
         Self {
             id: value.id,
-            name: value.spec.name,
+            name: spec.name,
             active_deployment_phase: match value.active_deployment {
                 Some(active_deployment) => active_deployment.phase,
                 None => "UNKNOWN".to_string(),
             },
+            components,
+            alerts: vec![],
+            health: vec![],
         }
     }
 }
@@ -42,6 +137,7 @@ impl From<AppResponse> for BasicAppInfo {
 pub struct AppStoreImpl {
     store: Arc<RwLock<Vec<BasicAppInfo>>>,
     client: Arc<dyn DigitalOceanClient>,
+    project_store: Arc<dyn ProjectStore>,
     configs: &'static AppSettings,
     metrics: AppMetrics,
 }
@@ -49,14 +145,17 @@ pub struct AppStoreImpl {
 impl AppStoreImpl {
     pub fn new(
         client: Arc<dyn DigitalOceanClient>,
+        project_store: Arc<dyn ProjectStore>,
         configs: &'static AppSettings,
         registry: prometheus::Registry,
     ) -> anyhow::Result<Self> {
+        let with_project = configs.projects.enabled && configs.projects.label;
         let result = Self {
             store: Arc::new(RwLock::new(vec![])),
             client,
+            project_store,
             configs,
-            metrics: AppMetrics::new(registry)?,
+            metrics: AppMetrics::new(registry, with_project)?,
         };
         Ok(result)
     }
@@ -65,21 +164,99 @@ impl AppStoreImpl {
 #[derive(Clone)]
 struct AppMetrics {
     active_gauge: prometheus::GaugeVec,
+    component_info_gauge: prometheus::GaugeVec,
+    component_instances_gauge: prometheus::GaugeVec,
+    alert_info_gauge: prometheus::GaugeVec,
+    alert_destinations_gauge: prometheus::GaugeVec,
+    component_health_gauge: prometheus::GaugeVec,
+    component_replicas_ready_gauge: prometheus::GaugeVec,
+    component_replicas_desired_gauge: prometheus::GaugeVec,
 }
 
 impl AppMetrics {
-    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+    fn new(registry: prometheus::Registry, with_project: bool) -> anyhow::Result<Self> {
+        let mut labels = vec!["app", "active_deployment_phase"];
+        if with_project {
+            labels.push("project");
+        }
         let active_gauge = prometheus::GaugeVec::new(
             Opts::new(
                 "droxporter_app_active_deployment_phase",
                 "The label active_deployment_phase indicates the current phase for the app. Values is always 1.",
             ),
-            &["app", "active_deployment_phase"],
+            &labels,
+        )?;
+
+        let component_info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_component_info",
+                "App component from the app spec with its type and instance size. Values is always 1.",
+            ),
+            &["app", "component", "type", "instance_size"],
+        )?;
+        let component_instances_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_component_desired_instances",
+                "Instance count of the app component from the app spec",
+            ),
+            &["app", "component"],
+        )?;
+
+        let alert_info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_alert_info",
+                "Configured app alert with its configuration phase. Values is always 1.",
+            ),
+            &["app", "component", "rule", "phase", "disabled"],
+        )?;
+        let alert_destinations_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_alert_destinations",
+                "Count of the app alert destinations by type",
+            ),
+            &["app", "component", "rule", "type"],
+        )?;
+        let component_health_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_component_health",
+                "Health state of the app component. Values is always 1.",
+            ),
+            &["app", "component", "state"],
+        )?;
+        let component_replicas_ready_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_component_replicas_ready",
+                "Count of ready replicas of the app component",
+            ),
+            &["app", "component"],
+        )?;
+        let component_replicas_desired_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_app_component_replicas_desired",
+                "Count of desired replicas of the app component, including autoscaling",
+            ),
+            &["app", "component"],
         )?;
 
         registry.register(Box::new(active_gauge.clone()))?;
+        registry.register(Box::new(component_info_gauge.clone()))?;
+        registry.register(Box::new(component_instances_gauge.clone()))?;
+        registry.register(Box::new(alert_info_gauge.clone()))?;
+        registry.register(Box::new(alert_destinations_gauge.clone()))?;
+        registry.register(Box::new(component_health_gauge.clone()))?;
+        registry.register(Box::new(component_replicas_ready_gauge.clone()))?;
+        registry.register(Box::new(component_replicas_desired_gauge.clone()))?;
 
-        let result = Self { active_gauge };
+        let result = Self {
+            active_gauge,
+            component_info_gauge,
+            component_instances_gauge,
+            alert_info_gauge,
+            alert_destinations_gauge,
+            component_health_gauge,
+            component_replicas_ready_gauge,
+            component_replicas_desired_gauge,
+        };
         Ok(result)
     }
 }
@@ -88,6 +265,52 @@ impl AppStoreImpl {
     fn save_apps(&self, apps: Vec<BasicAppInfo>) {
         *self.store.write() = apps;
     }
+
+    // one request per app for each of alerts and health.
+    // A single failed app shouldn't hide the inventory, so it keeps the previously loaded details
+    async fn load_app_details(
+        &self,
+        mut app: BasicAppInfo,
+        previous: Option<BasicAppInfo>,
+    ) -> BasicAppInfo {
+        let metrics = &self.configs.apps.metrics;
+        if metrics.contains(&AppMetricsTypes::Alerts) {
+            match self.client.list_app_alerts(app.id.clone()).await {
+                Ok(loaded) => {
+                    app.alerts = loaded
+                        .alerts
+                        .into_iter()
+                        .map(BasicAppAlertInfo::from)
+                        .collect()
+                }
+                Err(e) => {
+                    error!("Alerts loading for {} failed with err {e}", app.name);
+                    if let Some(previous) = previous.as_ref() {
+                        app.alerts = previous.alerts.clone();
+                    }
+                }
+            }
+        }
+        if metrics.contains(&AppMetricsTypes::Health) {
+            match self.client.get_app_health(app.id.clone()).await {
+                Ok(loaded) => {
+                    app.health = loaded
+                        .app_health
+                        .components
+                        .into_iter()
+                        .map(BasicAppComponentHealthInfo::from)
+                        .collect()
+                }
+                Err(e) => {
+                    error!("Health loading for {} failed with err {e}", app.name);
+                    if let Some(previous) = previous.as_ref() {
+                        app.health = previous.health.clone();
+                    }
+                }
+            }
+        }
+        app
+    }
 }
 
 #[async_trait]
@@ -103,7 +326,28 @@ impl AppStore for AppStoreImpl {
             result.extend(loaded.apps.into_iter().map(BasicAppInfo::from));
             page += 1;
         }
-        self.save_apps(result);
+
+        let mut previous: HashMap<String, BasicAppInfo> = self
+            .store
+            .read()
+            .iter()
+            .map(|x| (x.id.clone(), x.clone()))
+            .collect();
+        let semaphore = Arc::new(Semaphore::new(APP_DETAILS_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for (index, app) in result.into_iter().enumerate() {
+            let store = self.clone();
+            let previous = previous.remove(&app.id);
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                (index, store.load_app_details(app, previous).await)
+            });
+        }
+        let mut loaded = tasks.join_all().await;
+        // to keep the order of the API
+        loaded.sort_by_key(|(index, _)| *index);
+        self.save_apps(loaded.into_iter().map(|(_, app)| app).collect());
         Ok(())
     }
 
@@ -113,31 +357,224 @@ impl AppStore for AppStoreImpl {
             .apps
             .metrics
             .contains(&AppMetricsTypes::ActiveDeploymentPhase);
+        let enabled_component_info = self
+            .configs
+            .apps
+            .metrics
+            .contains(&AppMetricsTypes::ComponentInfo);
+        let enabled_alerts = self.configs.apps.metrics.contains(&AppMetricsTypes::Alerts);
+        let enabled_health = self.configs.apps.metrics.contains(&AppMetricsTypes::Health);
+        let enabled_project = self.configs.projects.enabled && self.configs.projects.label;
+
+        let lock = self.store.read();
+        // app name -> project name. Apps without a project get an empty label
+        let projects: HashMap<&str, String> = if enabled_project {
+            lock.iter()
+                .map(|x| {
+                    let urn = format!("do:app:{}", x.id);
+                    let project = self.project_store.get_project(&urn).unwrap_or_default();
+                    (x.name.as_str(), project)
+                })
+                .collect()
+        } else {
+            HashMap::default()
+        };
 
-        for app in self.store.read().iter() {
+        for app in lock.iter() {
             if enabled_active_deployment_phase {
-                self.metrics
-                    .active_gauge
-                    .with(&std::collections::HashMap::<
-                        &str,
-                        &str,
-                        std::hash::RandomState,
-                    >::from([
-                        ("app", app.name.as_ref()),
-                        (
-                            "active_deployment_phase",
-                            app.active_deployment_phase.as_ref(),
-                        ),
-                    ]))
-                    .set(1_f64);
+                let mut labels = std::collections::HashMap::from([
+                    ("app", app.name.as_str()),
+                    (
+                        "active_deployment_phase",
+                        app.active_deployment_phase.as_str(),
+                    ),
+                ]);
+                if let Some(project) = projects.get(app.name.as_str()) {
+                    labels.insert("project", project.as_str());
+                }
+                self.metrics.active_gauge.with(&labels).set(1_f64);
+            }
+            if enabled_component_info {
+                for component in app.components.iter() {
+                    self.metrics
+                        .component_info_gauge
+                        .with_label_values(&[
+                            app.name.as_str(),
+                            component.name.as_str(),
+                            component.component_type,
+                            component.instance_size.as_str(),
+                        ])
+                        .set(1_f64);
+                    if let Some(instance_count) = component.instance_count {
+                        self.metrics
+                            .component_instances_gauge
+                            .with_label_values(&[app.name.as_str(), component.name.as_str()])
+                            .set(instance_count as f64);
+                    }
+                }
+            }
+            if enabled_alerts {
+                for alert in app.alerts.iter() {
+                    let disabled = if alert.disabled { "true" } else { "false" };
+                    self.metrics
+                        .alert_info_gauge
+                        .with_label_values(&[
+                            app.name.as_str(),
+                            alert.component.as_str(),
+                            alert.rule.as_str(),
+                            alert.phase.as_str(),
+                            disabled,
+                        ])
+                        .set(1_f64);
+                    for (destination, count) in
+                        [("email", alert.emails), ("slack", alert.slack_webhooks)]
+                    {
+                        self.metrics
+                            .alert_destinations_gauge
+                            .with_label_values(&[
+                                app.name.as_str(),
+                                alert.component.as_str(),
+                                alert.rule.as_str(),
+                                destination,
+                            ])
+                            .set(count as f64);
+                    }
+                }
+            }
+            if enabled_health {
+                for health in app.health.iter() {
+                    let labels = [app.name.as_str(), health.component.as_str()];
+                    self.metrics
+                        .component_health_gauge
+                        .with_label_values(&[labels[0], labels[1], health.state.as_str()])
+                        .set(1_f64);
+                    self.metrics
+                        .component_replicas_ready_gauge
+                        .with_label_values(&labels)
+                        .set(health.replicas_ready as f64);
+                    self.metrics
+                        .component_replicas_desired_gauge
+                        .with_label_values(&labels)
+                        .set(health.replicas_desired as f64);
+                }
             }
         }
 
-        let lock = self.store.read();
         let apps: HashSet<_> = { lock.iter().map(|x| x.name.as_str()).collect() };
 
         // to prevent phantom apps
         utils::remove_old_apps_for_gauge_metric(&self.metrics.active_gauge, &apps);
+        // components can be removed, resized or scaled without removing the app
+        let components: HashSet<(&str, &str, &str, &str)> = lock
+            .iter()
+            .flat_map(|app| {
+                app.components.iter().map(|x| {
+                    (
+                        app.name.as_str(),
+                        x.name.as_str(),
+                        x.component_type,
+                        x.instance_size.as_str(),
+                    )
+                })
+            })
+            .collect();
+        let scaled_components: HashSet<(&str, &str)> = lock
+            .iter()
+            .flat_map(|app| {
+                app.components
+                    .iter()
+                    .filter(|x| x.instance_count.is_some())
+                    .map(|x| (app.name.as_str(), x.name.as_str()))
+            })
+            .collect();
+        utils::retain_gauge_series(&self.metrics.component_info_gauge, |labels| {
+            let key = (
+                labels.get("app").copied().unwrap_or_default(),
+                labels.get("component").copied().unwrap_or_default(),
+                labels.get("type").copied().unwrap_or_default(),
+                labels.get("instance_size").copied().unwrap_or_default(),
+            );
+            components.contains(&key)
+        });
+        utils::retain_gauge_series(&self.metrics.component_instances_gauge, |labels| {
+            let key = (
+                labels.get("app").copied().unwrap_or_default(),
+                labels.get("component").copied().unwrap_or_default(),
+            );
+            scaled_components.contains(&key)
+        });
+        // alerts can be reconfigured and health state changes over time
+        let alerts: HashSet<(&str, &str, &str, &str, &str)> = lock
+            .iter()
+            .flat_map(|app| {
+                app.alerts.iter().map(|x| {
+                    (
+                        app.name.as_str(),
+                        x.component.as_str(),
+                        x.rule.as_str(),
+                        x.phase.as_str(),
+                        if x.disabled { "true" } else { "false" },
+                    )
+                })
+            })
+            .collect();
+        utils::retain_gauge_series(&self.metrics.alert_info_gauge, |labels| {
+            let key = (
+                labels.get("app").copied().unwrap_or_default(),
+                labels.get("component").copied().unwrap_or_default(),
+                labels.get("rule").copied().unwrap_or_default(),
+                labels.get("phase").copied().unwrap_or_default(),
+                labels.get("disabled").copied().unwrap_or_default(),
+            );
+            alerts.contains(&key)
+        });
+        let alert_rules: HashSet<(&str, &str, &str)> =
+            alerts.iter().map(|x| (x.0, x.1, x.2)).collect();
+        utils::retain_gauge_series(&self.metrics.alert_destinations_gauge, |labels| {
+            let key = (
+                labels.get("app").copied().unwrap_or_default(),
+                labels.get("component").copied().unwrap_or_default(),
+                labels.get("rule").copied().unwrap_or_default(),
+            );
+            alert_rules.contains(&key)
+        });
+        let health: HashSet<(&str, &str, &str)> = lock
+            .iter()
+            .flat_map(|app| {
+                app.health
+                    .iter()
+                    .map(|x| (app.name.as_str(), x.component.as_str(), x.state.as_str()))
+            })
+            .collect();
+        utils::retain_gauge_series(&self.metrics.component_health_gauge, |labels| {
+            let key = (
+                labels.get("app").copied().unwrap_or_default(),
+                labels.get("component").copied().unwrap_or_default(),
+                labels.get("state").copied().unwrap_or_default(),
+            );
+            health.contains(&key)
+        });
+        let health_components: HashSet<(&str, &str)> = health.iter().map(|x| (x.0, x.1)).collect();
+        for gauge in [
+            &self.metrics.component_replicas_ready_gauge,
+            &self.metrics.component_replicas_desired_gauge,
+        ] {
+            utils::retain_gauge_series(gauge, |labels| {
+                let key = (
+                    labels.get("app").copied().unwrap_or_default(),
+                    labels.get("component").copied().unwrap_or_default(),
+                );
+                health_components.contains(&key)
+            });
+        }
+        // apps can be moved between projects
+        if enabled_project {
+            utils::retain_gauge_series(&self.metrics.active_gauge, |labels| {
+                let app = labels.get("app").copied().unwrap_or_default();
+                let project = labels.get("project").copied().unwrap_or_default();
+                projects.get(app).map(|x| x.as_str()) == Some(project)
+            });
+        }
     }
 
     fn list_apps(&self) -> Vec<BasicAppInfo> {
@@ -150,9 +587,11 @@ mod tests {
     use super::*;
     use crate::client::do_client::MockDigitalOceanClient;
     use crate::client::do_json_protocol::{
-        AppActiveDeployment, AppResponse, AppSpec, Links, ListAppsResponse, Pages,
+        AppActiveDeployment, AppAlertSpec, AppComponentSpec, AppHealth, AppHealthResponse,
+        AppResponse, AppSpec, Links, ListAppAlertsResponse, ListAppsResponse, Pages,
     };
     use crate::config::config_model::AppSettings;
+    use crate::metrics::project_store::MockProjectStore;
     use prometheus::core::Collector;
     use std::time::Duration as StdDuration;
 
@@ -169,7 +608,10 @@ mod tests {
                 keys: vec![],
                 url: "http://test.com/apps".to_string(),
                 interval: StdDuration::from_secs(60),
-                metrics: vec![crate::config::config_model::AppMetricsTypes::ActiveDeploymentPhase],
+                metrics: vec![
+                    crate::config::config_model::AppMetricsTypes::ActiveDeploymentPhase,
+                    crate::config::config_model::AppMetricsTypes::ComponentInfo,
+                ],
             },
             droplet_metrics: crate::config::config_model::DropletMetricsConfig {
                 base_url: "http://test.com/metrics".to_string(),
@@ -184,6 +626,7 @@ mod tests {
                 cpu_percentage: None,
                 memory_percentage: None,
                 restart_count: None,
+                bandwidth_daily: None,
             },
             exporter_metrics: crate::config::config_model::ExporterMetricsConfigs {
                 enabled: false,
@@ -200,6 +643,7 @@ mod tests {
                 prefix: None,
                 labels: std::collections::HashMap::new(),
             },
+            ..Default::default()
         };
         Box::leak(Box::new(config))
     }
@@ -219,6 +663,7 @@ mod tests {
                             id: "app-123".to_string(),
                             spec: AppSpec {
                                 name: "test-app-1".to_string(),
+                                ..Default::default()
                             },
                             active_deployment: Some(AppActiveDeployment {
                                 id: "dep-123".to_string(),
@@ -230,6 +675,7 @@ mod tests {
                             id: "app-456".to_string(),
                             spec: AppSpec {
                                 name: "test-app-2".to_string(),
+                                ..Default::default()
                             },
                             active_deployment: Some(AppActiveDeployment {
                                 id: "dep-456".to_string(),
@@ -251,7 +697,13 @@ mod tests {
 
         let config = create_test_config();
         let registry = prometheus::Registry::new();
-        let store = AppStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+        let store = AppStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
 
         let result = store.load_apps().await;
         assert!(result.is_ok());
@@ -281,6 +733,7 @@ mod tests {
                         id: "app-123".to_string(),
                         spec: AppSpec {
                             name: "test-app-1".to_string(),
+                            ..Default::default()
                         },
                         active_deployment: Some(AppActiveDeployment {
                             id: "dep-123".to_string(),
@@ -310,6 +763,7 @@ mod tests {
                         id: "app-456".to_string(),
                         spec: AppSpec {
                             name: "test-app-2".to_string(),
+                            ..Default::default()
                         },
                         active_deployment: None,
                     }],
@@ -326,7 +780,13 @@ mod tests {
 
         let config = create_test_config();
         let registry = prometheus::Registry::new();
-        let store = AppStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+        let store = AppStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
 
         let result = store.load_apps().await;
         assert!(result.is_ok());
@@ -344,7 +804,13 @@ mod tests {
         let mock_client = MockDigitalOceanClient::new();
         let config = create_test_config();
         let registry = prometheus::Registry::new();
-        let store = AppStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+        let store = AppStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
 
         // Manually populate store for testing metrics recording
         let apps = vec![
@@ -352,11 +818,13 @@ mod tests {
                 id: "app-123".to_string(),
                 name: "test-app".to_string(),
                 active_deployment_phase: "ACTIVE".to_string(),
+                ..Default::default()
             },
             BasicAppInfo {
                 id: "app-456".to_string(),
                 name: "test-app-2".to_string(),
                 active_deployment_phase: "SUPERSEDED".to_string(),
+                ..Default::default()
             },
         ];
         store.save_apps(apps);
@@ -379,9 +847,250 @@ mod tests {
         let mock_client = MockDigitalOceanClient::new();
         let config = create_test_config();
         let registry = prometheus::Registry::new();
-        let store = AppStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+        let store = AppStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
 
         let apps = store.list_apps();
         assert_eq!(apps.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_record_component_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        mock_client.expect_list_apps().returning(|_, _| {
+            Ok(ListAppsResponse {
+                apps: vec![AppResponse {
+                    id: "app-123".to_string(),
+                    spec: AppSpec {
+                        name: "test-app".to_string(),
+                        services: vec![AppComponentSpec {
+                            name: "web".to_string(),
+                            instance_count: Some(2),
+                            instance_size_slug: Some("apps-s-1vcpu-1gb".to_string()),
+                        }],
+                        static_sites: vec![AppComponentSpec {
+                            name: "docs".to_string(),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                    active_deployment: None,
+                }],
+                links: Links::default(),
+            })
+        });
+
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let store = AppStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
+        store.load_apps().await.unwrap();
+        store.record_app_metrics();
+
+        let info = store
+            .metrics
+            .component_info_gauge
+            .with_label_values(&["test-app", "web", "service", "apps-s-1vcpu-1gb"])
+            .get();
+        assert_eq!(info, 1_f64);
+        let info = store
+            .metrics
+            .component_info_gauge
+            .with_label_values(&["test-app", "docs", "static_site", ""])
+            .get();
+        assert_eq!(info, 1_f64);
+        let instances = store
+            .metrics
+            .component_instances_gauge
+            .with_label_values(&["test-app", "web"])
+            .get();
+        assert_eq!(instances, 2_f64);
+        // static sites have no instances
+        assert_eq!(
+            store.metrics.component_instances_gauge.collect()[0]
+                .get_metric()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_alert_and_health_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        mock_client.expect_list_apps().returning(|_, _| {
+            Ok(ListAppsResponse {
+                apps: vec![AppResponse {
+                    id: "app-123".to_string(),
+                    spec: AppSpec {
+                        name: "test-app".to_string(),
+                        ..Default::default()
+                    },
+                    active_deployment: None,
+                }],
+                links: Links::default(),
+            })
+        });
+        mock_client
+            .expect_list_app_alerts()
+            .withf(|id| id == "app-123")
+            .times(1)
+            .returning(|_| {
+                Ok(ListAppAlertsResponse {
+                    alerts: vec![AppAlertResponse {
+                        id: "alert-1".to_string(),
+                        spec: AppAlertSpec {
+                            rule: "CPU_UTILIZATION".to_string(),
+                            disabled: false,
+                        },
+                        component_name: "web".to_string(),
+                        emails: vec!["sammy@digitalocean.com".to_string()],
+                        slack_webhooks: vec![],
+                        phase: "ACTIVE".to_string(),
+                    }],
+                })
+            });
+        mock_client
+            .expect_get_app_health()
+            .withf(|id| id == "app-123")
+            .times(1)
+            .returning(|_| {
+                Ok(AppHealthResponse {
+                    app_health: AppHealth {
+                        components: vec![AppComponentHealthResponse {
+                            name: "web".to_string(),
+                            state: "UNHEALTHY".to_string(),
+                            replicas_desired: 2,
+                            replicas_ready: 1,
+                        }],
+                    },
+                })
+            });
+
+        let mut config = AppSettings::default();
+        config.apps.metrics = vec![AppMetricsTypes::Alerts, AppMetricsTypes::Health];
+        let config = Box::leak(Box::new(config));
+        let registry = prometheus::Registry::new();
+        let store = AppStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
+        store.load_apps().await.unwrap();
+        store.record_app_metrics();
+
+        let alert = store
+            .metrics
+            .alert_info_gauge
+            .with_label_values(&["test-app", "web", "CPU_UTILIZATION", "ACTIVE", "false"])
+            .get();
+        assert_eq!(alert, 1_f64);
+        let emails = store
+            .metrics
+            .alert_destinations_gauge
+            .with_label_values(&["test-app", "web", "CPU_UTILIZATION", "email"])
+            .get();
+        assert_eq!(emails, 1_f64);
+        let health = store
+            .metrics
+            .component_health_gauge
+            .with_label_values(&["test-app", "web", "UNHEALTHY"])
+            .get();
+        assert_eq!(health, 1_f64);
+        let ready = store
+            .metrics
+            .component_replicas_ready_gauge
+            .with_label_values(&["test-app", "web"])
+            .get();
+        assert_eq!(ready, 1_f64);
+        // active deployment phase is not enabled
+        assert!(
+            store.metrics.active_gauge.collect()[0]
+                .get_metric()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keep_apps_with_failed_health() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        mock_client.expect_list_apps().returning(|_, _| {
+            let app = |id: &str| AppResponse {
+                id: id.to_string(),
+                spec: AppSpec {
+                    name: format!("{id}-name"),
+                    ..Default::default()
+                },
+                active_deployment: None,
+            };
+            Ok(ListAppsResponse {
+                apps: vec![app("app-1"), app("app-2")],
+                links: Links::default(),
+            })
+        });
+        mock_client
+            .expect_get_app_health()
+            .withf(|id| id == "app-1")
+            .times(2)
+            .returning(|_| Err(anyhow::anyhow!("Request failed with status code: 500")));
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        mock_client
+            .expect_get_app_health()
+            .withf(|id| id == "app-2")
+            .times(2)
+            .returning(move |_| {
+                // only the first request succeeds
+                if calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed) > 0 {
+                    return Err(anyhow::anyhow!("Request failed with status code: 500"));
+                }
+                Ok(AppHealthResponse {
+                    app_health: AppHealth {
+                        components: vec![AppComponentHealthResponse {
+                            name: "web".to_string(),
+                            state: "HEALTHY".to_string(),
+                            replicas_desired: 1,
+                            replicas_ready: 1,
+                        }],
+                    },
+                })
+            });
+
+        let mut config = AppSettings::default();
+        config.apps.metrics = vec![AppMetricsTypes::Health];
+        let config = Box::leak(Box::new(config));
+        let registry = prometheus::Registry::new();
+        let store = AppStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
+        store.load_apps().await.unwrap();
+
+        let apps = store.list_apps();
+        assert_eq!(apps.len(), 2);
+        assert!(apps[0].health.is_empty());
+        assert_eq!(apps[1].health.len(), 1);
+
+        // the previous health is kept if it fails to load
+        store.load_apps().await.unwrap();
+        let apps = store.list_apps();
+        assert_eq!(apps[1].name, "app-2-name");
+        assert_eq!(apps[1].health.len(), 1);
+    }
 }
@@ -0,0 +1,582 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::{
+    AutoscalePoolHistoryEventResponse, AutoscalePoolMemberResponse, AutoscalePoolResponse,
+};
+use crate::metrics::droplet_store::DropletStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AutoscaleStore: Send + Sync {
+    async fn load_autoscale_pools(&self) -> anyhow::Result<()>;
+
+    fn record_autoscale_metrics(&self);
+}
+
+#[derive(Clone)]
+pub struct BasicAutoscalePoolInfo {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub min_instances: Option<u64>,
+    pub max_instances: Option<u64>,
+    pub target_instances: Option<u64>,
+    pub members: Vec<BasicAutoscaleMemberInfo>,
+    // the most recent scaling event
+    pub last_event: Option<BasicAutoscaleEventInfo>,
+}
+
+impl From<AutoscalePoolResponse> for BasicAutoscalePoolInfo {
+    fn from(value: AutoscalePoolResponse) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            status: value.status,
+            min_instances: value.config.min_instances,
+            max_instances: value.config.max_instances,
+            target_instances: value.config.target_number_instances,
+            members: vec![],
+            last_event: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BasicAutoscaleMemberInfo {
+    pub droplet_id: u64,
+    pub health_status: String,
+    pub status: String,
+}
+
+impl From<AutoscalePoolMemberResponse> for BasicAutoscaleMemberInfo {
+    fn from(value: AutoscalePoolMemberResponse) -> Self {
+        Self {
+            droplet_id: value.droplet_id,
+            health_status: value.health_status,
+            status: value.status,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BasicAutoscaleEventInfo {
+    pub reason: String,
+    pub status: String,
+    pub desired_instance_count: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AutoscalePoolHistoryEventResponse> for BasicAutoscaleEventInfo {
+    fn from(value: AutoscalePoolHistoryEventResponse) -> Self {
+        Self {
+            reason: value.reason,
+            status: value.status,
+            desired_instance_count: value.desired_instance_count,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AutoscaleStoreImpl {
+    store: Arc<RwLock<Vec<BasicAutoscalePoolInfo>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    droplet_store: Arc<dyn DropletStore>,
+    metrics: AutoscaleMetrics,
+}
+
+impl AutoscaleStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        droplet_store: Arc<dyn DropletStore>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            client,
+            droplet_store,
+            metrics: AutoscaleMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct AutoscaleMetrics {
+    min_instances_gauge: prometheus::GaugeVec,
+    max_instances_gauge: prometheus::GaugeVec,
+    target_instances_gauge: prometheus::GaugeVec,
+    desired_instances_gauge: prometheus::GaugeVec,
+    members_gauge: prometheus::GaugeVec,
+    status_gauge: prometheus::GaugeVec,
+    last_event_gauge: prometheus::GaugeVec,
+    member_gauge: prometheus::GaugeVec,
+}
+
+impl AutoscaleMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let min_instances_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_autoscale_pool_min_instances",
+                "Minimum number of instances of dynamic autoscale pool",
+            ),
+            &["pool"],
+        )?;
+        let max_instances_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_autoscale_pool_max_instances",
+                "Maximum number of instances of dynamic autoscale pool",
+            ),
+            &["pool"],
+        )?;
+        let target_instances_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_autoscale_pool_target_instances",
+                "Target number of instances of static autoscale pool",
+            ),
+            &["pool"],
+        )?;
+        let desired_instances_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_autoscale_pool_desired_instances",
+                "Desired number of instances from the last scaling event of autoscale pool",
+            ),
+            &["pool"],
+        )?;
+        let members_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_autoscale_pool_members",
+                "Current number of droplets in autoscale pool",
+            ),
+            &["pool"],
+        )?;
+        let status_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_autoscale_pool_status",
+                "The label status indicates the current status of autoscale pool. Values is always 1.",
+            ),
+            &["pool", "status"],
+        )?;
+        let last_event_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_autoscale_pool_last_scaling_event_timestamp_seconds",
+                "Creation time of the last scaling event of autoscale pool in seconds from epoch",
+            ),
+            &["pool", "reason", "status"],
+        )?;
+        let member_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_autoscale_pool_member_info",
+                "Droplet that belongs to autoscale pool. Values is always 1.",
+            ),
+            &["pool", "droplet", "health_status", "status"],
+        )?;
+
+        registry.register(Box::new(min_instances_gauge.clone()))?;
+        registry.register(Box::new(max_instances_gauge.clone()))?;
+        registry.register(Box::new(target_instances_gauge.clone()))?;
+        registry.register(Box::new(desired_instances_gauge.clone()))?;
+        registry.register(Box::new(members_gauge.clone()))?;
+        registry.register(Box::new(status_gauge.clone()))?;
+        registry.register(Box::new(last_event_gauge.clone()))?;
+        registry.register(Box::new(member_gauge.clone()))?;
+
+        let result = Self {
+            min_instances_gauge,
+            max_instances_gauge,
+            target_instances_gauge,
+            desired_instances_gauge,
+            members_gauge,
+            status_gauge,
+            last_event_gauge,
+            member_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl AutoscaleStoreImpl {
+    fn save_autoscale_pools(&self, pools: Vec<BasicAutoscalePoolInfo>) {
+        *self.store.write() = pools;
+    }
+
+    async fn load_members(&self, pool_id: &str) -> anyhow::Result<Vec<BasicAutoscaleMemberInfo>> {
+        let mut result: Vec<BasicAutoscaleMemberInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self
+                .client
+                .list_autoscale_pool_members(pool_id.to_string(), per_page, page)
+                .await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(
+                loaded
+                    .droplets
+                    .into_iter()
+                    .map(BasicAutoscaleMemberInfo::from),
+            );
+            page += 1;
+        }
+        Ok(result)
+    }
+
+    async fn load_last_event(
+        &self,
+        pool_id: &str,
+    ) -> anyhow::Result<Option<BasicAutoscaleEventInfo>> {
+        // history is returned newest first, so the first page is enough.
+        // The latest event is still searched within the page to not rely on the order of items
+        let per_page: u64 = 100u64;
+        let loaded = self
+            .client
+            .list_autoscale_pool_history(pool_id.to_string(), per_page, 1)
+            .await?;
+        let result = loaded
+            .history
+            .into_iter()
+            .max_by_key(|x| x.created_at)
+            .map(BasicAutoscaleEventInfo::from);
+        Ok(result)
+    }
+}
+
+fn retain_series(gauge: &prometheus::GaugeVec, names: &[&str], valid: &HashSet<Vec<String>>) {
+    utils::retain_gauge_series(gauge, |labels| {
+        let key: Vec<String> = names
+            .iter()
+            .map(|name| labels.get(name).copied().unwrap_or_default().to_string())
+            .collect();
+        valid.contains(&key)
+    });
+}
+
+#[async_trait]
+impl AutoscaleStore for AutoscaleStoreImpl {
+    async fn load_autoscale_pools(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicAutoscalePoolInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_autoscale_pools(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(
+                loaded
+                    .autoscale_pools
+                    .into_iter()
+                    .map(BasicAutoscalePoolInfo::from),
+            );
+            page += 1;
+        }
+
+        for pool in result.iter_mut() {
+            pool.members = self.load_members(pool.id.as_str()).await?;
+            pool.last_event = self.load_last_event(pool.id.as_str()).await?;
+        }
+        self.save_autoscale_pools(result);
+        Ok(())
+    }
+
+    fn record_autoscale_metrics(&self) {
+        let droplets: HashMap<u64, String> = self
+            .droplet_store
+            .list_droplets()
+            .into_iter()
+            .map(|x| (x.id, x.name))
+            .collect();
+
+        let lock = self.store.read();
+        let mut valid_pools: HashSet<Vec<String>> = HashSet::default();
+        let mut valid_statuses: HashSet<Vec<String>> = HashSet::default();
+        let mut valid_events: HashSet<Vec<String>> = HashSet::default();
+        let mut valid_members: HashSet<Vec<String>> = HashSet::default();
+        for pool in lock.iter() {
+            let name = pool.name.as_str();
+            let optional_gauges = [
+                (&self.metrics.min_instances_gauge, pool.min_instances),
+                (&self.metrics.max_instances_gauge, pool.max_instances),
+                (&self.metrics.target_instances_gauge, pool.target_instances),
+                (
+                    &self.metrics.desired_instances_gauge,
+                    pool.last_event.as_ref().map(|x| x.desired_instance_count),
+                ),
+            ];
+            for (gauge, value) in optional_gauges {
+                match value {
+                    Some(value) => gauge.with_label_values(&[name]).set(value as f64),
+                    None => {
+                        let _ = gauge.remove_label_values(&[name]);
+                    }
+                }
+            }
+            self.metrics
+                .members_gauge
+                .with_label_values(&[name])
+                .set(pool.members.len() as f64);
+            self.metrics
+                .status_gauge
+                .with_label_values(&[name, pool.status.as_str()])
+                .set(1_f64);
+            valid_pools.insert(vec![name.to_string()]);
+            valid_statuses.insert(vec![name.to_string(), pool.status.clone()]);
+
+            if let Some(event) = pool.last_event.as_ref() {
+                self.metrics
+                    .last_event_gauge
+                    .with_label_values(&[name, event.reason.as_str(), event.status.as_str()])
+                    .set(event.created_at.timestamp() as f64);
+                valid_events.insert(vec![
+                    name.to_string(),
+                    event.reason.clone(),
+                    event.status.clone(),
+                ]);
+            }
+
+            for member in pool.members.iter() {
+                // new members can be created after the last droplets loading, so we fall back to the id
+                let droplet = droplets
+                    .get(&member.droplet_id)
+                    .cloned()
+                    .unwrap_or_else(|| member.droplet_id.to_string());
+                self.metrics
+                    .member_gauge
+                    .with_label_values(&[
+                        name,
+                        droplet.as_str(),
+                        member.health_status.as_str(),
+                        member.status.as_str(),
+                    ])
+                    .set(1_f64);
+                valid_members.insert(vec![
+                    name.to_string(),
+                    droplet,
+                    member.health_status.clone(),
+                    member.status.clone(),
+                ]);
+            }
+        }
+
+        // to prevent phantom pools, members and stale statuses
+        for gauge in [
+            &self.metrics.min_instances_gauge,
+            &self.metrics.max_instances_gauge,
+            &self.metrics.target_instances_gauge,
+            &self.metrics.desired_instances_gauge,
+            &self.metrics.members_gauge,
+        ] {
+            retain_series(gauge, &["pool"], &valid_pools);
+        }
+        retain_series(
+            &self.metrics.status_gauge,
+            &["pool", "status"],
+            &valid_statuses,
+        );
+        retain_series(
+            &self.metrics.last_event_gauge,
+            &["pool", "reason", "status"],
+            &valid_events,
+        );
+        retain_series(
+            &self.metrics.member_gauge,
+            &["pool", "droplet", "health_status", "status"],
+            &valid_members,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{
+        AutoscalePoolConfigResponse, Links, ListAutoscalePoolHistoryResponse,
+        ListAutoscalePoolMembersResponse, ListAutoscalePoolsResponse, Pages,
+    };
+    use crate::metrics::droplet_store::{BasicDropletInfo, MockDropletStore};
+    use prometheus::core::Collector;
+
+    fn event(reason: &str, desired: u64, created_at: &str) -> AutoscalePoolHistoryEventResponse {
+        AutoscalePoolHistoryEventResponse {
+            history_event_id: format!("{reason}-{created_at}"),
+            current_instance_count: 1,
+            desired_instance_count: desired,
+            reason: reason.to_string(),
+            status: "success".to_string(),
+            created_at: created_at.parse().unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_autoscale_pools() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        mock_client
+            .expect_list_autoscale_pools()
+            .withf(|per_page, page| *per_page == 100 && *page == 1)
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListAutoscalePoolsResponse {
+                    autoscale_pools: vec![AutoscalePoolResponse {
+                        id: "pool-id".to_string(),
+                        name: "web-pool".to_string(),
+                        config: AutoscalePoolConfigResponse {
+                            min_instances: Some(1),
+                            max_instances: Some(3),
+                            target_number_instances: None,
+                        },
+                        status: "active".to_string(),
+                    }],
+                    links: Links::default(),
+                })
+            });
+        mock_client
+            .expect_list_autoscale_pool_members()
+            .withf(|id, per_page, page| id == "pool-id" && *per_page == 100 && *page == 1)
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(ListAutoscalePoolMembersResponse {
+                    droplets: vec![AutoscalePoolMemberResponse {
+                        droplet_id: 123,
+                        health_status: "healthy".to_string(),
+                        status: "active".to_string(),
+                    }],
+                    links: Links::default(),
+                })
+            });
+        mock_client
+            .expect_list_autoscale_pool_history()
+            .withf(|id, per_page, page| id == "pool-id" && *per_page == 100 && *page == 1)
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(ListAutoscalePoolHistoryResponse {
+                    history: vec![
+                        event("SCALE_UP", 3, "2024-11-20T10:00:00Z"),
+                        event("CONFIGURATION_CHANGE", 1, "2024-11-19T20:27:18Z"),
+                    ],
+                    // older events are not needed
+                    links: Links {
+                        pages: Pages {
+                            next: Some("http://next".to_string()),
+                            ..Default::default()
+                        },
+                    },
+                })
+            });
+
+        let registry = prometheus::Registry::new();
+        let store = AutoscaleStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockDropletStore::new()),
+            registry,
+        )
+        .unwrap();
+
+        let result = store.load_autoscale_pools().await;
+        assert!(result.is_ok());
+
+        let pools = store.store.read().clone();
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0].members.len(), 1);
+        let last_event = pools[0].last_event.as_ref().unwrap();
+        assert_eq!(last_event.reason, "SCALE_UP");
+        assert_eq!(last_event.desired_instance_count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_record_autoscale_metrics() {
+        let mock_client = MockDigitalOceanClient::new();
+        let mut mock_store = MockDropletStore::new();
+
+        mock_store.expect_list_droplets().returning(|| {
+            vec![BasicDropletInfo {
+                id: 123,
+                name: "web-pool-1".to_string(),
+                ..Default::default()
+            }]
+        });
+
+        let registry = prometheus::Registry::new();
+        let store =
+            AutoscaleStoreImpl::new(Arc::new(mock_client), Arc::new(mock_store), registry).unwrap();
+
+        let pool = BasicAutoscalePoolInfo {
+            id: "pool-id".to_string(),
+            name: "web-pool".to_string(),
+            status: "active".to_string(),
+            min_instances: Some(1),
+            max_instances: Some(2),
+            target_instances: None,
+            members: vec![
+                BasicAutoscaleMemberInfo {
+                    droplet_id: 123,
+                    health_status: "healthy".to_string(),
+                    status: "active".to_string(),
+                },
+                BasicAutoscaleMemberInfo {
+                    droplet_id: 456,
+                    health_status: "unknown".to_string(),
+                    status: "new".to_string(),
+                },
+            ],
+            last_event: Some(event("SCALE_UP", 2, "2024-11-20T10:00:00Z").into()),
+        };
+        store.save_autoscale_pools(vec![pool.clone()]);
+        store.record_autoscale_metrics();
+
+        let max = store
+            .metrics
+            .max_instances_gauge
+            .with_label_values(&["web-pool"])
+            .get();
+        let members = store
+            .metrics
+            .members_gauge
+            .with_label_values(&["web-pool"])
+            .get();
+        assert_eq!(max, members);
+        let member = store
+            .metrics
+            .member_gauge
+            .with_label_values(&["web-pool", "web-pool-1", "healthy", "active"])
+            .get();
+        assert_eq!(member, 1_f64);
+        let last_event = store
+            .metrics
+            .last_event_gauge
+            .with_label_values(&["web-pool", "SCALE_UP", "success"])
+            .get();
+        assert_eq!(last_event, 1732096800_f64);
+
+        // the new droplet became healthy
+        let mut pool = pool;
+        pool.members[1].health_status = "healthy".to_string();
+        pool.members[1].status = "active".to_string();
+        store.save_autoscale_pools(vec![pool]);
+        store.record_autoscale_metrics();
+
+        let metrics: Vec<_> = store
+            .metrics
+            .member_gauge
+            .collect()
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .collect();
+        assert_eq!(metrics.len(), 2);
+        let target: Vec<_> = store
+            .metrics
+            .target_instances_gauge
+            .collect()
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .collect();
+        assert!(target.is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::CdnEndpointResponse;
+use crate::metrics::certificate_store::CertificateStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait CdnStore: Send + Sync {
+    async fn load_endpoints(&self) -> anyhow::Result<()>;
+
+    fn record_endpoint_metrics(&self);
+}
+
+#[derive(Clone)]
+pub struct BasicCdnEndpointInfo {
+    pub origin: String,
+    pub endpoint: String,
+    pub ttl: u64,
+    pub custom_domain: Option<String>,
+    pub certificate_id: Option<String>,
+}
+
+impl From<CdnEndpointResponse> for BasicCdnEndpointInfo {
+    fn from(value: CdnEndpointResponse) -> Self {
+        Self {
+            origin: value.origin,
+            endpoint: value.endpoint,
+            ttl: value.ttl,
+            custom_domain: value.custom_domain,
+            certificate_id: value.certificate_id,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CdnStoreImpl {
+    store: Arc<RwLock<Vec<BasicCdnEndpointInfo>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    certificate_store: Arc<dyn CertificateStore>,
+    metrics: CdnMetrics,
+}
+
+impl CdnStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        certificate_store: Arc<dyn CertificateStore>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            client,
+            certificate_store,
+            metrics: CdnMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct CdnMetrics {
+    info_gauge: prometheus::GaugeVec,
+    ttl_gauge: prometheus::GaugeVec,
+}
+
+impl CdnMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_cdn_endpoint_info",
+                "Settings of CDN endpoint. Value is always 1.",
+            ),
+            &["origin", "endpoint", "custom_domain", "certificate"],
+        )?;
+        let ttl_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_cdn_endpoint_ttl_seconds",
+                "Cache TTL of CDN endpoint in seconds",
+            ),
+            &["origin"],
+        )?;
+
+        registry.register(Box::new(info_gauge.clone()))?;
+        registry.register(Box::new(ttl_gauge.clone()))?;
+
+        let result = Self {
+            info_gauge,
+            ttl_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl CdnStoreImpl {
+    fn save_endpoints(&self, endpoints: Vec<BasicCdnEndpointInfo>) {
+        *self.store.write() = endpoints;
+    }
+}
+
+#[async_trait]
+impl CdnStore for CdnStoreImpl {
+    async fn load_endpoints(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicCdnEndpointInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_cdn_endpoints(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.endpoints.into_iter().map(BasicCdnEndpointInfo::from));
+            page += 1;
+        }
+        self.save_endpoints(result);
+        Ok(())
+    }
+
+    fn record_endpoint_metrics(&self) {
+        // certificates unknown to the certificate store are reported with their ids
+        let certificates: HashMap<String, String> = self
+            .certificate_store
+            .list_certificates()
+            .into_iter()
+            .map(|x| (x.id, x.name))
+            .collect();
+
+        let lock = self.store.read();
+        let mut valid_info: HashSet<[&str; 4]> = HashSet::default();
+        for endpoint in lock.iter() {
+            let certificate = endpoint
+                .certificate_id
+                .as_deref()
+                .map(|id| certificates.get(id).map(|x| x.as_str()).unwrap_or(id))
+                .unwrap_or_default();
+            let labels = [
+                endpoint.origin.as_str(),
+                endpoint.endpoint.as_str(),
+                endpoint.custom_domain.as_deref().unwrap_or_default(),
+                certificate,
+            ];
+            self.metrics
+                .info_gauge
+                .with_label_values(&labels)
+                .set(1_f64);
+            valid_info.insert(labels);
+            self.metrics
+                .ttl_gauge
+                .with_label_values(&[endpoint.origin.as_str()])
+                .set(endpoint.ttl as f64);
+        }
+
+        // to prevent phantom endpoints and outdated settings
+        let origins: HashSet<&str> = lock.iter().map(|x| x.origin.as_str()).collect();
+        utils::retain_gauge_series(&self.metrics.ttl_gauge, |labels| {
+            let origin = labels.get("origin").copied().unwrap_or_default();
+            origins.contains(origin)
+        });
+        utils::retain_gauge_series(&self.metrics.info_gauge, |labels| {
+            let get = |name: &str| labels.get(name).copied().unwrap_or_default();
+            valid_info.contains(&[
+                get("origin"),
+                get("endpoint"),
+                get("custom_domain"),
+                get("certificate"),
+            ])
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{Links, ListCdnEndpointsResponse};
+    use crate::metrics::certificate_store::{BasicCertificateInfo, MockCertificateStore};
+
+    #[tokio::test]
+    async fn test_record_endpoint_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_cdn_endpoints()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListCdnEndpointsResponse {
+                    endpoints: vec![
+                        CdnEndpointResponse {
+                            id: "cdn-1".to_string(),
+                            origin: "static.nyc3.digitaloceanspaces.com".to_string(),
+                            endpoint: "static.nyc3.cdn.digitaloceanspaces.com".to_string(),
+                            ttl: 3600,
+                            custom_domain: Some("static.example.com".to_string()),
+                            certificate_id: Some("cert-1".to_string()),
+                        },
+                        CdnEndpointResponse {
+                            id: "cdn-2".to_string(),
+                            origin: "assets.fra1.digitaloceanspaces.com".to_string(),
+                            endpoint: "assets.fra1.cdn.digitaloceanspaces.com".to_string(),
+                            ttl: 86400,
+                            custom_domain: None,
+                            certificate_id: None,
+                        },
+                    ],
+                    links: Links::default(),
+                })
+            });
+
+        let mut mock_certificate_store = MockCertificateStore::new();
+        mock_certificate_store
+            .expect_list_certificates()
+            .returning(|| {
+                vec![BasicCertificateInfo {
+                    id: "cert-1".to_string(),
+                    name: "static-cert".to_string(),
+                    certificate_type: "lets_encrypt".to_string(),
+                    state: "verified".to_string(),
+                    not_after: "2030-01-01T00:00:00Z".parse().unwrap(),
+                    dns_names: vec!["static.example.com".to_string()],
+                }]
+            });
+
+        let registry = prometheus::Registry::new();
+        let store = CdnStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_certificate_store),
+            registry,
+        )
+        .unwrap();
+
+        store.load_endpoints().await.unwrap();
+        store.record_endpoint_metrics();
+
+        let info = store
+            .metrics
+            .info_gauge
+            .with_label_values(&[
+                "static.nyc3.digitaloceanspaces.com",
+                "static.nyc3.cdn.digitaloceanspaces.com",
+                "static.example.com",
+                "static-cert",
+            ])
+            .get();
+        assert_eq!(info, 1_f64);
+        let info = store
+            .metrics
+            .info_gauge
+            .with_label_values(&[
+                "assets.fra1.digitaloceanspaces.com",
+                "assets.fra1.cdn.digitaloceanspaces.com",
+                "",
+                "",
+            ])
+            .get();
+        assert_eq!(info, 1_f64);
+        let ttl = store
+            .metrics
+            .ttl_gauge
+            .with_label_values(&["assets.fra1.digitaloceanspaces.com"])
+            .get();
+        assert_eq!(ttl, 86400_f64);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::CertificateResponse;
+use crate::metrics::utils;
+use ahash::HashSet;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait CertificateStore: Send + Sync {
+    async fn load_certificates(&self) -> anyhow::Result<()>;
+
+    fn record_certificate_metrics(&self);
+
+    fn list_certificates(&self) -> Vec<BasicCertificateInfo>;
+}
+
+#[derive(Clone)]
+pub struct BasicCertificateInfo {
+    pub id: String,
+    pub name: String,
+    pub certificate_type: String,
+    pub state: String,
+    pub not_after: DateTime<Utc>,
+    pub dns_names: Vec<String>,
+}
+
+impl From<CertificateResponse> for BasicCertificateInfo {
+    fn from(value: CertificateResponse) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            certificate_type: value.certificate_type,
+            state: value.state,
+            not_after: value.not_after,
+            dns_names: value.dns_names,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CertificateStoreImpl {
+    store: Arc<RwLock<Vec<BasicCertificateInfo>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    metrics: CertificateMetrics,
+}
+
+impl CertificateStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            client,
+            metrics: CertificateMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct CertificateMetrics {
+    expiry_gauge: prometheus::GaugeVec,
+    state_gauge: prometheus::GaugeVec,
+}
+
+impl CertificateMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let expiry_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_certificate_expiry_timestamp_seconds",
+                "Expiration time (not_after) of the certificate in seconds from epoch",
+            ),
+            &["certificate", "type", "dns_names"],
+        )?;
+        let state_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_certificate_state",
+                "The label state indicates the current state of the certificate. Values is always 1.",
+            ),
+            &["certificate", "state"],
+        )?;
+
+        registry.register(Box::new(expiry_gauge.clone()))?;
+        registry.register(Box::new(state_gauge.clone()))?;
+
+        let result = Self {
+            expiry_gauge,
+            state_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl CertificateStoreImpl {
+    fn save_certificates(&self, certificates: Vec<BasicCertificateInfo>) {
+        *self.store.write() = certificates;
+    }
+}
+
+#[async_trait]
+impl CertificateStore for CertificateStoreImpl {
+    async fn load_certificates(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicCertificateInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_certificates(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(
+                loaded
+                    .certificates
+                    .into_iter()
+                    .map(BasicCertificateInfo::from),
+            );
+            page += 1;
+        }
+        self.save_certificates(result);
+        Ok(())
+    }
+
+    fn record_certificate_metrics(&self) {
+        let lock = self.store.read();
+
+        let mut valid_expiry: HashSet<(&str, &str, String)> = HashSet::default();
+        let mut valid_states: HashSet<(&str, &str)> = HashSet::default();
+        for certificate in lock.iter() {
+            let dns_names = certificate.dns_names.join(",");
+            self.metrics
+                .expiry_gauge
+                .with_label_values(&[
+                    certificate.name.as_str(),
+                    certificate.certificate_type.as_str(),
+                    dns_names.as_str(),
+                ])
+                .set(certificate.not_after.timestamp() as f64);
+            self.metrics
+                .state_gauge
+                .with_label_values(&[certificate.name.as_str(), certificate.state.as_str()])
+                .set(1_f64);
+
+            valid_expiry.insert((
+                certificate.name.as_str(),
+                certificate.certificate_type.as_str(),
+                dns_names,
+            ));
+            valid_states.insert((certificate.name.as_str(), certificate.state.as_str()));
+        }
+
+        // to prevent phantom certificates and stale states
+        utils::retain_gauge_series(&self.metrics.expiry_gauge, |labels| {
+            let key = (
+                labels.get("certificate").copied().unwrap_or_default(),
+                labels.get("type").copied().unwrap_or_default(),
+                labels
+                    .get("dns_names")
+                    .copied()
+                    .unwrap_or_default()
+                    .to_string(),
+            );
+            valid_expiry.contains(&key)
+        });
+        utils::retain_gauge_series(&self.metrics.state_gauge, |labels| {
+            let key = (
+                labels.get("certificate").copied().unwrap_or_default(),
+                labels.get("state").copied().unwrap_or_default(),
+            );
+            valid_states.contains(&key)
+        });
+    }
+
+    fn list_certificates(&self) -> Vec<BasicCertificateInfo> {
+        self.store.read().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{
+        CertificateResponse, Links, ListCertificatesResponse, Pages,
+    };
+    use prometheus::core::Collector;
+
+    fn certificate(name: &str, state: &str) -> CertificateResponse {
+        CertificateResponse {
+            id: format!("{name}-id"),
+            name: name.to_string(),
+            not_after: "2017-02-22T00:23:00Z".parse().unwrap(),
+            dns_names: vec!["www.example.com".to_string(), "example.com".to_string()],
+            state: state.to_string(),
+            certificate_type: "custom".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_certificates_multiple_pages() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        // First page
+        mock_client
+            .expect_list_certificates()
+            .withf(|per_page, page| *per_page == 100 && *page == 1)
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListCertificatesResponse {
+                    certificates: vec![certificate("cert-1", "verified")],
+                    links: Links {
+                        pages: Pages {
+                            next: Some("http://next".to_string()),
+                            ..Default::default()
+                        },
+                    },
+                })
+            });
+
+        // Second page
+        mock_client
+            .expect_list_certificates()
+            .withf(|per_page, page| *per_page == 100 && *page == 2)
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListCertificatesResponse {
+                    certificates: vec![certificate("cert-2", "pending")],
+                    links: Links::default(),
+                })
+            });
+
+        let registry = prometheus::Registry::new();
+        let store = CertificateStoreImpl::new(Arc::new(mock_client), registry).unwrap();
+
+        let result = store.load_certificates().await;
+        assert!(result.is_ok());
+
+        let certificates = store.list_certificates();
+        assert_eq!(certificates.len(), 2);
+        assert_eq!(certificates[0].name, "cert-1");
+        assert_eq!(certificates[0].state, "verified");
+        assert_eq!(certificates[1].name, "cert-2");
+        assert_eq!(certificates[1].state, "pending");
+    }
+
+    #[tokio::test]
+    async fn test_record_certificate_metrics_removes_stale_states() {
+        let mock_client = MockDigitalOceanClient::new();
+        let registry = prometheus::Registry::new();
+        let store = CertificateStoreImpl::new(Arc::new(mock_client), registry).unwrap();
+
+        store.save_certificates(vec![certificate("cert-1", "pending").into()]);
+        store.record_certificate_metrics();
+
+        store.save_certificates(vec![certificate("cert-1", "verified").into()]);
+        store.record_certificate_metrics();
+
+        let metrics: Vec<_> = store
+            .metrics
+            .state_gauge
+            .collect()
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .collect();
+        assert_eq!(metrics.len(), 1);
+        let state = metrics[0]
+            .get_label()
+            .iter()
+            .find(|l| l.name() == "state")
+            .map(|l| l.value());
+        assert_eq!(state, Some("verified"));
+
+        let expiry = store
+            .metrics
+            .expiry_gauge
+            .with_label_values(&["cert-1", "custom", "www.example.com,example.com"])
+            .get();
+        assert_eq!(expiry, 1487722980_f64);
+    }
+}
@@ -0,0 +1,397 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::DatabaseEventResponse;
+use crate::metrics::database_store::DatabaseStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+use tracing::error;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DatabaseEventStore: Send + Sync {
+    async fn load_events(&self) -> anyhow::Result<()>;
+
+    fn record_event_metrics(&self);
+}
+
+#[derive(Clone)]
+pub struct BasicDatabaseEventInfo {
+    pub id: String,
+    pub cluster: String,
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BasicDatabaseEventInfo {
+    fn new(cluster: &str, value: DatabaseEventResponse) -> Self {
+        Self {
+            id: value.id,
+            cluster: cluster.to_string(),
+            event_type: value.event_type,
+            created_at: value.create_time,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DatabaseEventStoreImpl {
+    // recent events of every cluster
+    store: Arc<RwLock<Vec<BasicDatabaseEventInfo>>>,
+    // cluster -> ids of events already counted. The first load of a cluster only seeds it,
+    // so the history of clusters seen on startup or added later is not counted as new events
+    counted: Arc<RwLock<HashMap<String, HashSet<String>>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    database_store: Arc<dyn DatabaseStore>,
+    metrics: DatabaseEventMetrics,
+}
+
+impl DatabaseEventStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        database_store: Arc<dyn DatabaseStore>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            counted: Arc::new(RwLock::new(HashMap::default())),
+            client,
+            database_store,
+            metrics: DatabaseEventMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct DatabaseEventMetrics {
+    events_counter: prometheus::CounterVec,
+    last_event_gauge: prometheus::GaugeVec,
+}
+
+impl DatabaseEventMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let events_counter = prometheus::CounterVec::new(
+            Opts::new(
+                "droxporter_database_events_total",
+                "Count of database cluster events observed by the exporter",
+            ),
+            &["cluster", "event_type"],
+        )?;
+        let last_event_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_database_last_event_timestamp_seconds",
+                "Unix timestamp of the newest database cluster event of the type",
+            ),
+            &["cluster", "event_type"],
+        )?;
+
+        registry.register(Box::new(events_counter.clone()))?;
+        registry.register(Box::new(last_event_gauge.clone()))?;
+
+        let result = Self {
+            events_counter,
+            last_event_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl DatabaseEventStoreImpl {
+    fn save_events(&self, events: Vec<BasicDatabaseEventInfo>) {
+        *self.store.write() = events;
+    }
+}
+
+#[async_trait]
+impl DatabaseEventStore for DatabaseEventStoreImpl {
+    async fn load_events(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicDatabaseEventInfo> = Vec::new();
+        for database in self.database_store.list_databases() {
+            // a single failed cluster shouldn't hide the events of the others
+            match self.client.list_database_events(database.id.clone()).await {
+                Ok(loaded) => result.extend(
+                    loaded
+                        .events
+                        .into_iter()
+                        .map(|x| BasicDatabaseEventInfo::new(database.name.as_str(), x)),
+                ),
+                Err(e) => {
+                    error!(
+                        "Database {} events loading failed with err {e}",
+                        database.name
+                    );
+                    // previous events are kept, so they are not counted again after a recovery
+                    let previous = self.store.read();
+                    result.extend(
+                        previous
+                            .iter()
+                            .filter(|x| x.cluster == database.name)
+                            .cloned(),
+                    );
+                }
+            }
+        }
+        self.save_events(result);
+        Ok(())
+    }
+
+    fn record_event_metrics(&self) {
+        let lock = self.store.read();
+
+        let mut last: HashMap<(&str, &str), DateTime<Utc>> = HashMap::default();
+        for event in lock.iter() {
+            let entry = last
+                .entry((event.cluster.as_str(), event.event_type.as_str()))
+                .or_insert(event.created_at);
+            *entry = (*entry).max(event.created_at);
+        }
+        for ((cluster, event_type), created_at) in last.iter() {
+            self.metrics
+                .last_event_gauge
+                .with_label_values(&[cluster, event_type])
+                .set(created_at.timestamp() as f64);
+            // to have a zero value to calculate increase() against
+            self.metrics
+                .events_counter
+                .with_label_values(&[cluster, event_type])
+                .inc_by(0_f64);
+        }
+
+        let mut loaded: HashMap<&str, Vec<&BasicDatabaseEventInfo>> = HashMap::default();
+        for event in lock.iter() {
+            loaded
+                .entry(event.cluster.as_str())
+                .or_default()
+                .push(event);
+        }
+        let mut counted = self.counted.write();
+        for (cluster, events) in loaded.iter() {
+            let Some(cluster_counted) = counted.get_mut(*cluster) else {
+                let seed = events.iter().map(|x| x.id.clone()).collect();
+                counted.insert(cluster.to_string(), seed);
+                continue;
+            };
+            for event in events.iter() {
+                if cluster_counted.insert(event.id.clone()) {
+                    self.metrics
+                        .events_counter
+                        .with_label_values(&[cluster, event.event_type.as_str()])
+                        .inc();
+                }
+            }
+            // events which are not returned anymore won't be seen again
+            cluster_counted.retain(|x| events.iter().any(|e| e.id == *x));
+        }
+        counted.retain(|cluster, _| loaded.contains_key(cluster.as_str()));
+
+        // to prevent phantom clusters. Counters of existing clusters are kept,
+        // even if their events are not returned anymore
+        let clusters: HashSet<&str> = last.keys().map(|(cluster, _)| *cluster).collect();
+        utils::retain_gauge_series(&self.metrics.last_event_gauge, |labels| {
+            let cluster = labels.get("cluster").copied().unwrap_or_default();
+            let event_type = labels.get("event_type").copied().unwrap_or_default();
+            last.contains_key(&(cluster, event_type))
+        });
+        utils::retain_counter_series(&self.metrics.events_counter, |labels| {
+            let cluster = labels.get("cluster").copied().unwrap_or_default();
+            clusters.contains(cluster)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::ListDatabaseEventsResponse;
+    use crate::metrics::database_store::{BasicDatabaseInfo, MockDatabaseStore};
+
+    fn event(id: &str, event_type: &str, create_time: &str) -> DatabaseEventResponse {
+        DatabaseEventResponse {
+            id: id.to_string(),
+            event_type: event_type.to_string(),
+            create_time: create_time.parse().unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_count_only_new_events() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        let mut seq = mockall::Sequence::new();
+        mock_client
+            .expect_list_database_events()
+            .withf(|id| id == "id-1")
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| {
+                Ok(ListDatabaseEventsResponse {
+                    events: vec![event("e-1", "cluster_create", "2020-10-29T15:57:38Z")],
+                })
+            });
+        mock_client
+            .expect_list_database_events()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| {
+                Ok(ListDatabaseEventsResponse {
+                    events: vec![
+                        event("e-1", "cluster_create", "2020-10-29T15:57:38Z"),
+                        event("e-2", "failover", "2023-10-30T15:57:38Z"),
+                    ],
+                })
+            });
+
+        let mut mock_database_store = MockDatabaseStore::new();
+        mock_database_store.expect_list_databases().returning(|| {
+            vec![BasicDatabaseInfo {
+                id: "id-1".to_string(),
+                name: "backend".to_string(),
+                ..Default::default()
+            }]
+        });
+
+        let registry = prometheus::Registry::new();
+        let store = DatabaseEventStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_database_store),
+            registry,
+        )
+        .unwrap();
+        let counter = |event_type: &str| {
+            store
+                .metrics
+                .events_counter
+                .with_label_values(&["backend", event_type])
+                .get()
+        };
+
+        store.load_events().await.unwrap();
+        store.record_event_metrics();
+        // the history loaded on startup is not counted
+        assert_eq!(counter("cluster_create"), 0_f64);
+
+        store.load_events().await.unwrap();
+        store.record_event_metrics();
+        store.record_event_metrics();
+        assert_eq!(counter("cluster_create"), 0_f64);
+        assert_eq!(counter("failover"), 1_f64);
+
+        let last_failover = store
+            .metrics
+            .last_event_gauge
+            .with_label_values(&["backend", "failover"])
+            .get();
+        assert_eq!(last_failover, 1698681458_f64);
+    }
+
+    #[tokio::test]
+    async fn test_keep_events_of_failed_cluster() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        let mut seq = mockall::Sequence::new();
+        mock_client
+            .expect_list_database_events()
+            .times(2)
+            .in_sequence(&mut seq)
+            .returning(|id| {
+                Ok(ListDatabaseEventsResponse {
+                    events: vec![event(
+                        &format!("{id}-create"),
+                        "cluster_create",
+                        "2020-10-29T15:57:38Z",
+                    )],
+                })
+            });
+        mock_client
+            .expect_list_database_events()
+            .withf(|id| id == "id-1")
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Err(anyhow::anyhow!("Request failed with status code: 500")));
+        mock_client
+            .expect_list_database_events()
+            .withf(|id| id == "id-2")
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| {
+                Ok(ListDatabaseEventsResponse {
+                    events: vec![
+                        event("id-2-create", "cluster_create", "2020-10-29T15:57:38Z"),
+                        event("id-2-failover", "failover", "2023-10-30T15:57:38Z"),
+                    ],
+                })
+            });
+
+        let mut mock_database_store = MockDatabaseStore::new();
+        mock_database_store.expect_list_databases().returning(|| {
+            let database = |id: &str, name: &str| BasicDatabaseInfo {
+                id: id.to_string(),
+                name: name.to_string(),
+                ..Default::default()
+            };
+            vec![database("id-1", "backend"), database("id-2", "analytics")]
+        });
+
+        let registry = prometheus::Registry::new();
+        let store = DatabaseEventStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_database_store),
+            registry,
+        )
+        .unwrap();
+
+        store.load_events().await.unwrap();
+        store.record_event_metrics();
+        store.load_events().await.unwrap();
+        store.record_event_metrics();
+
+        let failover = store
+            .metrics
+            .events_counter
+            .with_label_values(&["analytics", "failover"])
+            .get();
+        assert_eq!(failover, 1_f64);
+        let events = store.store.read();
+        assert!(events.iter().any(|x| x.id == "id-1-create"));
+    }
+
+    #[test]
+    fn test_seed_clusters_loaded_after_first_recording() {
+        let registry = prometheus::Registry::new();
+        let store = DatabaseEventStoreImpl::new(
+            Arc::new(MockDigitalOceanClient::new()),
+            Arc::new(MockDatabaseStore::new()),
+            registry,
+        )
+        .unwrap();
+        let counter = |event_type: &str| {
+            store
+                .metrics
+                .events_counter
+                .with_label_values(&["backend", event_type])
+                .get()
+        };
+
+        // clusters are not loaded yet
+        store.record_event_metrics();
+
+        let create = || event("e-1", "cluster_create", "2020-10-29T15:57:38Z");
+        store.save_events(vec![BasicDatabaseEventInfo::new("backend", create())]);
+        store.record_event_metrics();
+        assert_eq!(counter("cluster_create"), 0_f64);
+
+        store.save_events(vec![
+            BasicDatabaseEventInfo::new("backend", create()),
+            BasicDatabaseEventInfo::new(
+                "backend",
+                event("e-2", "failover", "2023-10-30T15:57:38Z"),
+            ),
+        ]);
+        store.record_event_metrics();
+        assert_eq!(counter("cluster_create"), 0_f64);
+        assert_eq!(counter("failover"), 1_f64);
+    }
+}
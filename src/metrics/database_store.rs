@@ -0,0 +1,287 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::{DatabaseBackupResponse, DatabaseResponse};
+use crate::metrics::utils;
+use ahash::HashSet;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+use tracing::error;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DatabaseStore: Send + Sync {
+    async fn load_databases(&self) -> anyhow::Result<()>;
+
+    fn record_database_metrics(&self);
+
+    fn list_databases(&self) -> Vec<BasicDatabaseInfo>;
+}
+
+#[derive(Clone, Default)]
+pub struct BasicDatabaseInfo {
+    pub id: String,
+    pub name: String,
+    // None if backups failed to load, e.g. the engine has no backups
+    pub backups: Option<BasicDatabaseBackupsInfo>,
+}
+
+impl From<DatabaseResponse> for BasicDatabaseInfo {
+    fn from(value: DatabaseResponse) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            backups: None,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct BasicDatabaseBackupsInfo {
+    pub count: usize,
+    // None if the cluster has no backups yet
+    pub last_backup_at: Option<DateTime<Utc>>,
+    pub last_backup_size_gigabytes: f64,
+}
+
+impl From<Vec<DatabaseBackupResponse>> for BasicDatabaseBackupsInfo {
+    fn from(value: Vec<DatabaseBackupResponse>) -> Self {
+        let last = value.iter().max_by_key(|x| x.created_at);
+        Self {
+            count: value.len(),
+            last_backup_at: last.map(|x| x.created_at),
+            last_backup_size_gigabytes: last.map(|x| x.size_gigabytes).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DatabaseStoreImpl {
+    store: Arc<RwLock<Vec<BasicDatabaseInfo>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    metrics: DatabaseMetrics,
+}
+
+impl DatabaseStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            client,
+            metrics: DatabaseMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct DatabaseMetrics {
+    last_backup_gauge: prometheus::GaugeVec,
+    last_backup_size_gauge: prometheus::GaugeVec,
+    backups_gauge: prometheus::GaugeVec,
+}
+
+impl DatabaseMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let last_backup_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_database_last_backup_timestamp_seconds",
+                "Creation time of the newest backup of the database cluster",
+            ),
+            &["cluster"],
+        )?;
+        let last_backup_size_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_database_last_backup_size_gigabytes",
+                "Size of the newest backup of the database cluster in gigabytes",
+            ),
+            &["cluster"],
+        )?;
+        let backups_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_database_backups",
+                "Count of backups of the database cluster",
+            ),
+            &["cluster"],
+        )?;
+
+        registry.register(Box::new(last_backup_gauge.clone()))?;
+        registry.register(Box::new(last_backup_size_gauge.clone()))?;
+        registry.register(Box::new(backups_gauge.clone()))?;
+
+        let result = Self {
+            last_backup_gauge,
+            last_backup_size_gauge,
+            backups_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl DatabaseStoreImpl {
+    fn save_databases(&self, databases: Vec<BasicDatabaseInfo>) {
+        *self.store.write() = databases;
+    }
+}
+
+#[async_trait]
+impl DatabaseStore for DatabaseStoreImpl {
+    async fn load_databases(&self) -> anyhow::Result<()> {
+        let loaded = self.client.list_databases().await?;
+        let mut result: Vec<BasicDatabaseInfo> = loaded
+            .databases
+            .unwrap_or_default()
+            .into_iter()
+            .map(BasicDatabaseInfo::from)
+            .collect();
+        for database in result.iter_mut() {
+            // a single cluster without backups shouldn't hide the others
+            match self.client.list_database_backups(database.id.clone()).await {
+                Ok(backups) => database.backups = Some(backups.backups.into()),
+                Err(e) => error!("Backups loading for {} failed with err {e}", database.name),
+            }
+        }
+        self.save_databases(result);
+        Ok(())
+    }
+
+    fn record_database_metrics(&self) {
+        let lock = self.store.read();
+
+        let mut valid_clusters: HashSet<&str> = HashSet::default();
+        let mut valid_last_backups: HashSet<&str> = HashSet::default();
+        for database in lock.iter() {
+            let Some(backups) = database.backups.as_ref() else {
+                continue;
+            };
+            let cluster = database.name.as_str();
+            valid_clusters.insert(cluster);
+            self.metrics
+                .backups_gauge
+                .with_label_values(&[cluster])
+                .set(backups.count as f64);
+
+            let Some(last_backup_at) = backups.last_backup_at else {
+                continue;
+            };
+            valid_last_backups.insert(cluster);
+            self.metrics
+                .last_backup_gauge
+                .with_label_values(&[cluster])
+                .set(last_backup_at.timestamp() as f64);
+            self.metrics
+                .last_backup_size_gauge
+                .with_label_values(&[cluster])
+                .set(backups.last_backup_size_gigabytes);
+        }
+
+        // to prevent phantom clusters
+        let retain = |gauge: &prometheus::GaugeVec, valid: &HashSet<&str>| {
+            utils::retain_gauge_series(gauge, |labels| {
+                let cluster = labels.get("cluster").copied().unwrap_or_default();
+                valid.contains(cluster)
+            });
+        };
+        retain(&self.metrics.backups_gauge, &valid_clusters);
+        retain(&self.metrics.last_backup_gauge, &valid_last_backups);
+        retain(&self.metrics.last_backup_size_gauge, &valid_last_backups);
+    }
+
+    fn list_databases(&self) -> Vec<BasicDatabaseInfo> {
+        self.store.read().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{ListDatabaseBackupsResponse, ListDatabasesResponse};
+
+    fn database(id: &str, name: &str, engine: &str) -> DatabaseResponse {
+        DatabaseResponse {
+            id: id.to_string(),
+            name: name.to_string(),
+            engine: engine.to_string(),
+            region: "nyc3".to_string(),
+            status: "online".to_string(),
+        }
+    }
+
+    fn backup(created_at: &str, size_gigabytes: f64) -> DatabaseBackupResponse {
+        DatabaseBackupResponse {
+            created_at: created_at.parse().unwrap(),
+            size_gigabytes,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_backup_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client.expect_list_databases().times(1).returning(|| {
+            Ok(ListDatabasesResponse {
+                databases: Some(vec![
+                    database("id-1", "backend", "pg"),
+                    database("id-2", "new", "mysql"),
+                    database("id-3", "cache", "valkey"),
+                ]),
+            })
+        });
+        mock_client
+            .expect_list_database_backups()
+            .returning(|id| match id.as_str() {
+                "id-1" => Ok(ListDatabaseBackupsResponse {
+                    backups: vec![
+                        backup("2019-01-12T18:42:29Z", 0.5),
+                        backup("2019-01-11T18:42:27Z", 0.25),
+                    ],
+                }),
+                "id-2" => Ok(ListDatabaseBackupsResponse { backups: vec![] }),
+                _ => Err(anyhow::Error::msg("backups are not supported")),
+            });
+
+        let registry = prometheus::Registry::new();
+        let store = DatabaseStoreImpl::new(Arc::new(mock_client), registry).unwrap();
+
+        store.load_databases().await.unwrap();
+        store.record_database_metrics();
+
+        let metrics = &store.metrics;
+        assert_eq!(
+            metrics
+                .last_backup_gauge
+                .with_label_values(&["backend"])
+                .get(),
+            1547318549_f64
+        );
+        assert_eq!(
+            metrics
+                .last_backup_size_gauge
+                .with_label_values(&["backend"])
+                .get(),
+            0.5
+        );
+        assert_eq!(
+            metrics.backups_gauge.with_label_values(&["backend"]).get(),
+            2_f64
+        );
+        assert_eq!(
+            metrics.backups_gauge.with_label_values(&["new"]).get(),
+            0_f64
+        );
+
+        let databases = store.list_databases();
+        assert_eq!(databases.len(), 3);
+        let last_backup_of_new = databases
+            .iter()
+            .find(|x| x.name == "new")
+            .and_then(|x| x.backups.as_ref())
+            .and_then(|x| x.last_backup_at);
+        assert_eq!(last_backup_of_new, None);
+        let cache_backups = databases.iter().find(|x| x.name == "cache").unwrap();
+        assert!(cache_backups.backups.is_none());
+    }
+}
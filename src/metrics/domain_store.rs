@@ -0,0 +1,554 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::{DomainRecordResponse, DomainResponse};
+use crate::config::config_model::AppSettings;
+use crate::metrics::droplet_store::DropletStore;
+use crate::metrics::reserved_ip_store::ReservedIpStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DomainStore: Send + Sync {
+    async fn load_domains(&self) -> anyhow::Result<()>;
+
+    fn record_domain_metrics(&self);
+}
+
+#[derive(Clone)]
+pub struct BasicDomainInfo {
+    pub name: String,
+    pub ttl: Option<u64>,
+    pub records: Vec<BasicDomainRecordInfo>,
+}
+
+impl From<DomainResponse> for BasicDomainInfo {
+    fn from(value: DomainResponse) -> Self {
+        Self {
+            name: value.name,
+            ttl: value.ttl,
+            records: vec![],
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BasicDomainRecordInfo {
+    pub record_type: String,
+    pub name: String,
+    pub data: String,
+    pub ttl: u64,
+}
+
+impl From<DomainRecordResponse> for BasicDomainRecordInfo {
+    fn from(value: DomainRecordResponse) -> Self {
+        Self {
+            record_type: value.record_type,
+            name: value.name,
+            data: value.data,
+            ttl: value.ttl,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DomainStoreImpl {
+    store: Arc<RwLock<Vec<BasicDomainInfo>>>,
+    // IPs of load balancers. They are not tracked by other stores
+    load_balancer_ips: Arc<RwLock<Vec<String>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    droplet_store: Arc<dyn DropletStore>,
+    reserved_ip_store: Arc<dyn ReservedIpStore>,
+    configs: &'static AppSettings,
+    metrics: DomainMetrics,
+}
+
+impl DomainStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        droplet_store: Arc<dyn DropletStore>,
+        reserved_ip_store: Arc<dyn ReservedIpStore>,
+        configs: &'static AppSettings,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            load_balancer_ips: Arc::new(RwLock::new(vec![])),
+            client,
+            droplet_store,
+            reserved_ip_store,
+            configs,
+            metrics: DomainMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct DomainMetrics {
+    records_gauge: prometheus::GaugeVec,
+    ttl_gauge: prometheus::GaugeVec,
+    record_min_ttl_gauge: prometheus::GaugeVec,
+    dangling_record_gauge: prometheus::GaugeVec,
+}
+
+impl DomainMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let records_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_domain_records",
+                "Count of DNS records of domain",
+            ),
+            &["domain", "type"],
+        )?;
+        let ttl_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_domain_ttl_seconds",
+                "Default TTL of domain records in seconds",
+            ),
+            &["domain"],
+        )?;
+        let record_min_ttl_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_domain_record_min_ttl_seconds",
+                "Minimal TTL of DNS records of domain in seconds",
+            ),
+            &["domain", "type"],
+        )?;
+        let dangling_record_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_domain_dangling_record",
+                "A or AAAA record points at an IP which doesn't belong to any droplet, reserved IP or load balancer. Value is always 1.",
+            ),
+            &["domain", "name", "type", "ip"],
+        )?;
+
+        registry.register(Box::new(records_gauge.clone()))?;
+        registry.register(Box::new(ttl_gauge.clone()))?;
+        registry.register(Box::new(record_min_ttl_gauge.clone()))?;
+        registry.register(Box::new(dangling_record_gauge.clone()))?;
+
+        let result = Self {
+            records_gauge,
+            ttl_gauge,
+            record_min_ttl_gauge,
+            dangling_record_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl DomainStoreImpl {
+    fn save_domains(&self, domains: Vec<BasicDomainInfo>) {
+        *self.store.write() = domains;
+    }
+
+    async fn load_records(&self, domain: &str) -> anyhow::Result<Vec<BasicDomainRecordInfo>> {
+        let mut result: Vec<BasicDomainRecordInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self
+                .client
+                .list_domain_records(domain.to_string(), per_page, page)
+                .await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(
+                loaded
+                    .domain_records
+                    .into_iter()
+                    .map(BasicDomainRecordInfo::from),
+            );
+            page += 1;
+        }
+        Ok(result)
+    }
+
+    async fn load_load_balancer_ips(&self) -> anyhow::Result<Vec<String>> {
+        let mut result: Vec<String> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_load_balancers(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(
+                loaded
+                    .load_balancers
+                    .into_iter()
+                    .map(|x| x.ip)
+                    .filter(|x| !x.is_empty()),
+            );
+            page += 1;
+        }
+        Ok(result)
+    }
+
+    // IPs are parsed to compare differently written IPv6 addresses.
+    // None until the stores the IPs come from have loaded, so that records are not
+    // reported as dangling just because nothing is known yet
+    fn known_ips(&self) -> Option<HashSet<IpAddr>> {
+        let reserved_ips_loaded =
+            !self.configs.reserved_ips.enabled || self.reserved_ip_store.is_loaded();
+        if !self.droplet_store.is_loaded() || !reserved_ips_loaded {
+            return None;
+        }
+        let droplet_ips = self
+            .droplet_store
+            .list_droplets()
+            .into_iter()
+            .flat_map(|x| x.ips);
+        let reserved_ips = self
+            .reserved_ip_store
+            .list_reserved_ips()
+            .into_iter()
+            .map(|x| x.ip);
+        let load_balancer_ips = self.load_balancer_ips.read().clone();
+        let result = droplet_ips
+            .chain(reserved_ips)
+            .chain(load_balancer_ips)
+            .filter_map(|x| x.parse::<IpAddr>().ok())
+            .collect();
+        Some(result)
+    }
+}
+
+#[async_trait]
+impl DomainStore for DomainStoreImpl {
+    async fn load_domains(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicDomainInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_domains(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.domains.into_iter().map(BasicDomainInfo::from));
+            page += 1;
+        }
+        for domain in result.iter_mut() {
+            domain.records = self.load_records(domain.name.as_str()).await?;
+        }
+        let load_balancer_ips = self.load_load_balancer_ips().await?;
+
+        *self.load_balancer_ips.write() = load_balancer_ips;
+        self.save_domains(result);
+        Ok(())
+    }
+
+    fn record_domain_metrics(&self) {
+        let known_ips = self.known_ips();
+        let lock = self.store.read();
+
+        let mut valid_domains: HashSet<&str> = HashSet::default();
+        let mut valid_types: HashSet<(&str, &str)> = HashSet::default();
+        let mut valid_dangling: HashSet<(&str, &str, &str, &str)> = HashSet::default();
+        for domain in lock.iter() {
+            let name = domain.name.as_str();
+            valid_domains.insert(name);
+            match domain.ttl {
+                Some(ttl) => self
+                    .metrics
+                    .ttl_gauge
+                    .with_label_values(&[name])
+                    .set(ttl as f64),
+                None => {
+                    let _ = self.metrics.ttl_gauge.remove_label_values(&[name]);
+                }
+            }
+
+            // record type -> (count, min ttl)
+            let mut types: HashMap<&str, (usize, u64)> = HashMap::default();
+            for record in domain.records.iter() {
+                let record_type = record.record_type.as_str();
+                let entry = types.entry(record_type).or_insert((0, record.ttl));
+                entry.0 += 1;
+                entry.1 = entry.1.min(record.ttl);
+
+                let Some(known_ips) = known_ips.as_ref() else {
+                    continue;
+                };
+                if record_type != "A" && record_type != "AAAA" {
+                    continue;
+                }
+                let Ok(ip) = record.data.parse::<IpAddr>() else {
+                    continue;
+                };
+                if known_ips.contains(&ip) {
+                    continue;
+                }
+                let labels = (
+                    name,
+                    record.name.as_str(),
+                    record_type,
+                    record.data.as_str(),
+                );
+                self.metrics
+                    .dangling_record_gauge
+                    .with_label_values(&[labels.0, labels.1, labels.2, labels.3])
+                    .set(1_f64);
+                valid_dangling.insert(labels);
+            }
+            for (record_type, (count, min_ttl)) in types {
+                valid_types.insert((name, record_type));
+                self.metrics
+                    .records_gauge
+                    .with_label_values(&[name, record_type])
+                    .set(count as f64);
+                self.metrics
+                    .record_min_ttl_gauge
+                    .with_label_values(&[name, record_type])
+                    .set(min_ttl as f64);
+            }
+        }
+
+        // to prevent phantom domains and records
+        utils::retain_gauge_series(&self.metrics.ttl_gauge, |labels| {
+            let domain = labels.get("domain").copied().unwrap_or_default();
+            valid_domains.contains(domain)
+        });
+        for gauge in [
+            &self.metrics.records_gauge,
+            &self.metrics.record_min_ttl_gauge,
+        ] {
+            utils::retain_gauge_series(gauge, |labels| {
+                let domain = labels.get("domain").copied().unwrap_or_default();
+                let record_type = labels.get("type").copied().unwrap_or_default();
+                valid_types.contains(&(domain, record_type))
+            });
+        }
+        utils::retain_gauge_series(&self.metrics.dangling_record_gauge, |labels| {
+            let get = |name: &str| labels.get(name).copied().unwrap_or_default();
+            valid_dangling.contains(&(get("domain"), get("name"), get("type"), get("ip")))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{
+        Links, ListDomainRecordsResponse, ListDomainsResponse, ListLoadBalancersResponse,
+        LoadBalancerResponse,
+    };
+    use crate::metrics::droplet_store::{BasicDropletInfo, MockDropletStore};
+    use crate::metrics::reserved_ip_store::{BasicReservedIpInfo, MockReservedIpStore};
+    use prometheus::core::Collector;
+
+    fn create_test_config() -> &'static AppSettings {
+        let mut config = AppSettings::default();
+        config.domains.enabled = true;
+        config.reserved_ips.enabled = true;
+        Box::leak(Box::new(config))
+    }
+
+    fn record(record_type: &str, name: &str, data: &str, ttl: u64) -> DomainRecordResponse {
+        DomainRecordResponse {
+            id: 1,
+            record_type: record_type.to_string(),
+            name: name.to_string(),
+            data: data.to_string(),
+            ttl,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_domain_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_domains()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListDomainsResponse {
+                    domains: vec![DomainResponse {
+                        name: "example.com".to_string(),
+                        ttl: Some(1800),
+                    }],
+                    links: Links::default(),
+                })
+            });
+        mock_client
+            .expect_list_domain_records()
+            .withf(|domain, _, _| domain == "example.com")
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(ListDomainRecordsResponse {
+                    domain_records: vec![
+                        record("NS", "@", "ns1.digitalocean.com", 1800),
+                        record("A", "@", "10.0.0.1", 3600),
+                        record("A", "www", "10.0.0.2", 300),
+                        record("A", "lb", "10.0.0.3", 300),
+                        record("AAAA", "@", "2a03:b0c0:0:1010::1", 3600),
+                        record("A", "old", "10.0.0.4", 3600),
+                    ],
+                    links: Links::default(),
+                })
+            });
+        mock_client
+            .expect_list_load_balancers()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListLoadBalancersResponse {
+                    load_balancers: vec![LoadBalancerResponse {
+                        id: "lb-1".to_string(),
+                        name: "lb".to_string(),
+                        ip: "10.0.0.3".to_string(),
+                    }],
+                    links: Links::default(),
+                })
+            });
+
+        let mut mock_droplet_store = MockDropletStore::new();
+        mock_droplet_store.expect_is_loaded().returning(|| true);
+        mock_droplet_store.expect_list_droplets().returning(|| {
+            vec![BasicDropletInfo {
+                id: 1,
+                name: "web".to_string(),
+                ips: vec![
+                    "10.0.0.1".to_string(),
+                    "2a03:b0c0:0:1010:0:0:0:1".to_string(),
+                ],
+                ..Default::default()
+            }]
+        });
+        let mut mock_reserved_ip_store = MockReservedIpStore::new();
+        mock_reserved_ip_store.expect_is_loaded().returning(|| true);
+        mock_reserved_ip_store
+            .expect_list_reserved_ips()
+            .returning(|| {
+                vec![BasicReservedIpInfo {
+                    ip: "10.0.0.2".to_string(),
+                    region: "nyc3".to_string(),
+                    locked: false,
+                    droplet_id: Some(1),
+                }]
+            });
+
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let store = DomainStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_droplet_store),
+            Arc::new(mock_reserved_ip_store),
+            config,
+            registry,
+        )
+        .unwrap();
+
+        store.load_domains().await.unwrap();
+        store.record_domain_metrics();
+
+        let metrics = &store.metrics;
+        assert_eq!(
+            metrics
+                .records_gauge
+                .with_label_values(&["example.com", "A"])
+                .get(),
+            4_f64
+        );
+        assert_eq!(
+            metrics
+                .record_min_ttl_gauge
+                .with_label_values(&["example.com", "A"])
+                .get(),
+            300_f64
+        );
+        assert_eq!(
+            metrics.ttl_gauge.with_label_values(&["example.com"]).get(),
+            1800_f64
+        );
+
+        let dangling: Vec<String> = metrics
+            .dangling_record_gauge
+            .collect()
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .flat_map(|m| {
+                m.get_label()
+                    .iter()
+                    .filter(|l| l.name() == "name")
+                    .map(|l| l.value().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(dangling, vec!["old".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_skip_dangling_records_before_stores_loaded() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client.expect_list_domains().returning(|_, _| {
+            Ok(ListDomainsResponse {
+                domains: vec![DomainResponse {
+                    name: "example.com".to_string(),
+                    ttl: Some(1800),
+                }],
+                links: Links::default(),
+            })
+        });
+        mock_client
+            .expect_list_domain_records()
+            .returning(|_, _, _| {
+                Ok(ListDomainRecordsResponse {
+                    domain_records: vec![record("A", "@", "10.0.0.1", 3600)],
+                    links: Links::default(),
+                })
+            });
+        mock_client.expect_list_load_balancers().returning(|_, _| {
+            Ok(ListLoadBalancersResponse {
+                load_balancers: vec![],
+                links: Links::default(),
+            })
+        });
+
+        let mut mock_droplet_store = MockDropletStore::new();
+        mock_droplet_store.expect_is_loaded().returning(|| true);
+        mock_droplet_store
+            .expect_list_droplets()
+            .returning(Vec::new);
+        // reserved IPs are enabled, but were not loaded yet
+        let mut mock_reserved_ip_store = MockReservedIpStore::new();
+        mock_reserved_ip_store
+            .expect_is_loaded()
+            .returning(|| false);
+        mock_reserved_ip_store
+            .expect_list_reserved_ips()
+            .returning(Vec::new);
+
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let store = DomainStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_droplet_store),
+            Arc::new(mock_reserved_ip_store),
+            config,
+            registry,
+        )
+        .unwrap();
+
+        store.load_domains().await.unwrap();
+        store.record_domain_metrics();
+
+        let metrics = &store.metrics;
+        assert_eq!(
+            metrics
+                .records_gauge
+                .with_label_values(&["example.com", "A"])
+                .get(),
+            1_f64
+        );
+        let dangling = metrics
+            .dangling_record_gauge
+            .collect()
+            .iter()
+            .map(|m| m.get_metric().len())
+            .sum::<usize>();
+        assert_eq!(dangling, 0);
+    }
+}
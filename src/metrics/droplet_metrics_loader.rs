@@ -8,10 +8,12 @@ use crate::client::do_json_protocol::{
 use crate::config::config_model::{
     AppSettings, BandwidthType, FilesystemTypes, LoadTypes, MemoryTypes,
 };
-use crate::metrics::droplet_store::DropletStore;
+use crate::metrics::droplet_store::{BasicDropletInfo, DropletStore};
 use crate::metrics::utils;
+use ahash::HashMap;
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use parking_lot::RwLock;
 use prometheus::Opts;
 use std::sync::Arc;
 
@@ -30,9 +32,28 @@ pub struct DropletMetricsServiceImpl {
     client: Arc<dyn DigitalOceanClient>,
     droplet_store: Arc<dyn DropletStore>,
     configs: &'static AppSettings,
+    transfer: Arc<RwLock<TransferState>>,
     metrics: LoaderDropletMetrics,
 }
 
+// public outbound transfer of the current calendar month, integrated from bandwidth samples
+#[derive(Default)]
+struct TransferState {
+    // start of the month the transfer belongs to
+    month_start: Option<DateTime<Utc>>,
+    droplets: HashMap<String, DropletTransfer>,
+}
+
+struct DropletTransfer {
+    last_sample_at: DateTime<Utc>,
+    bytes: f64,
+    // seconds of the month covered by the samples
+    covered_seconds: f64,
+}
+
+// DO transfer allowances are in TB of 1000 GiB
+const BYTES_PER_TRANSFER_TB: f64 = 1000_f64 * 1024_f64 * 1024_f64 * 1024_f64;
+
 impl DropletMetricsServiceImpl {
     pub fn new(
         client: Arc<dyn DigitalOceanClient>,
@@ -44,6 +65,7 @@ impl DropletMetricsServiceImpl {
             client,
             droplet_store,
             configs,
+            transfer: Arc::new(RwLock::new(TransferState::default())),
             metrics: LoaderDropletMetrics::new(registry)?,
         };
         Ok(result)
@@ -58,6 +80,11 @@ struct LoaderDropletMetrics {
     droplet_memory: prometheus::GaugeVec,
     #[allow(dead_code)]
     droplet_load: prometheus::GaugeVec,
+    droplet_transfer: prometheus::GaugeVec,
+    transfer_total: prometheus::Gauge,
+    transfer_allowance: prometheus::Gauge,
+    transfer_projected: prometheus::Gauge,
+    transfer_projected_overage: prometheus::Gauge,
 }
 
 impl LoaderDropletMetrics {
@@ -85,17 +112,50 @@ impl LoaderDropletMetrics {
             Opts::new("droxporter_droplet_load", "Load of droplet"),
             &["droplet", "metric_type"],
         )?;
+        let droplet_transfer = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_droplet_transfer_bytes",
+                "Estimated public outbound transfer of droplet in the current month",
+            ),
+            &["droplet"],
+        )?;
+        let transfer_total = prometheus::Gauge::new(
+            "droxporter_droplets_transfer_bytes",
+            "Estimated public outbound transfer of all droplets in the current month",
+        )?;
+        let transfer_allowance = prometheus::Gauge::new(
+            "droxporter_droplets_transfer_allowance_bytes",
+            "Pooled monthly transfer allowance of droplet sizes",
+        )?;
+        let transfer_projected = prometheus::Gauge::new(
+            "droxporter_droplets_transfer_projected_bytes",
+            "Projected public outbound transfer of all droplets by the end of the month",
+        )?;
+        let transfer_projected_overage = prometheus::Gauge::new(
+            "droxporter_droplets_transfer_projected_overage_bytes",
+            "Projected transfer above the pooled allowance by the end of the month",
+        )?;
         registry.register(Box::new(droplet_bandwidth.clone()))?;
         registry.register(Box::new(droplet_cpu.clone()))?;
         registry.register(Box::new(droplet_filesystem.clone()))?;
         registry.register(Box::new(droplet_memory.clone()))?;
         registry.register(Box::new(droplet_load.clone()))?;
+        registry.register(Box::new(droplet_transfer.clone()))?;
+        registry.register(Box::new(transfer_total.clone()))?;
+        registry.register(Box::new(transfer_allowance.clone()))?;
+        registry.register(Box::new(transfer_projected.clone()))?;
+        registry.register(Box::new(transfer_projected_overage.clone()))?;
         let result = Self {
             droplet_bandwidth,
             droplet_cpu,
             droplet_filesystem,
             droplet_memory,
             droplet_load,
+            droplet_transfer,
+            transfer_total,
+            transfer_allowance,
+            transfer_projected,
+            transfer_projected_overage,
         };
         Ok(result)
     }
@@ -150,6 +210,93 @@ fn metrics_read_interval() -> Duration {
     Duration::minutes(1)
 }
 
+fn month_bounds(time: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let (next_year, next_month) = if time.month() == 12 {
+        (time.year() + 1, 1)
+    } else {
+        (time.year(), time.month() + 1)
+    };
+    let start = Utc
+        .with_ymd_and_hms(time.year(), time.month(), 1, 0, 0, 0)
+        .unwrap();
+    let end = Utc
+        .with_ymd_and_hms(next_year, next_month, 1, 0, 0, 0)
+        .unwrap();
+    (start, end)
+}
+
+impl DropletMetricsServiceImpl {
+    // samples are droplet names with public outbound bandwidth in Mbps
+    fn record_transfer(
+        &self,
+        droplets: &[BasicDropletInfo],
+        samples: &[(String, f64)],
+        now: DateTime<Utc>,
+    ) {
+        let (month_start, month_end) = month_bounds(now);
+        let mut state = self.transfer.write();
+        if state.month_start != Some(month_start) {
+            state.month_start = Some(month_start);
+            state.droplets.clear();
+        }
+
+        for (droplet, mbps) in samples {
+            match state.droplets.get_mut(droplet) {
+                Some(transfer) => {
+                    // the sample is the latest rate, so it's applied to the whole gap
+                    let from = transfer.last_sample_at.max(month_start);
+                    let seconds = (now - from).num_milliseconds().max(0) as f64 / 1000_f64;
+                    transfer.bytes += mbps * 1_000_000_f64 / 8_f64 * seconds;
+                    transfer.covered_seconds += seconds;
+                    transfer.last_sample_at = now;
+                }
+                None => {
+                    let transfer = DropletTransfer {
+                        last_sample_at: now,
+                        bytes: 0_f64,
+                        covered_seconds: 0_f64,
+                    };
+                    state.droplets.insert(droplet.clone(), transfer);
+                }
+            }
+        }
+
+        let names: ahash::HashSet<_> = droplets.iter().map(|x| x.name.as_str()).collect();
+        state
+            .droplets
+            .retain(|name, _| names.contains(name.as_str()));
+
+        let month_seconds = (month_end - month_start).num_seconds() as f64;
+        let mut total = 0_f64;
+        let mut projected = 0_f64;
+        for (droplet, transfer) in state.droplets.iter() {
+            self.metrics
+                .droplet_transfer
+                .with_label_values(&[droplet.as_str()])
+                .set(transfer.bytes);
+            total += transfer.bytes;
+            // the average rate of the covered period is expected for the whole month,
+            // including the part before the exporter was started
+            if transfer.covered_seconds > 0_f64 {
+                projected += transfer.bytes / transfer.covered_seconds * month_seconds;
+            }
+        }
+        let allowance: f64 = droplets
+            .iter()
+            .map(|x| x.transfer * BYTES_PER_TRANSFER_TB)
+            .sum();
+
+        self.metrics.transfer_total.set(total);
+        self.metrics.transfer_allowance.set(allowance);
+        self.metrics.transfer_projected.set(projected);
+        self.metrics
+            .transfer_projected_overage
+            .set((projected - allowance).max(0_f64));
+
+        utils::remove_old_droplets(&self.metrics.droplet_transfer, &names);
+    }
+}
+
 // a lot of boilerplate. but I don't think it would be changing too often
 #[async_trait]
 impl DropletMetricsService for DropletMetricsServiceImpl {
@@ -191,6 +338,7 @@ impl DropletMetricsService for DropletMetricsServiceImpl {
         let interval_end = Utc::now();
         let interval_start = interval_end - metrics_read_interval();
 
+        let mut transfer_samples: Vec<(String, f64)> = Vec::new();
         let droplets = self.droplet_store.list_droplets();
         for droplet in droplets.iter() {
             for (interface, dir) in &metric_types {
@@ -205,6 +353,9 @@ impl DropletMetricsService for DropletMetricsServiceImpl {
                     )
                     .await?;
                 let value = extract_last_value(res);
+                if *interface == NetworkInterface::Public && *dir == NetworkDirection::Outbound {
+                    transfer_samples.push((droplet.name.clone(), value));
+                }
                 let interface = match interface {
                     NetworkInterface::Public => "public",
                     NetworkInterface::Private => "private",
@@ -225,6 +376,10 @@ impl DropletMetricsService for DropletMetricsServiceImpl {
         let droplets_names: ahash::HashSet<_> = droplets.iter().map(|x| x.name.as_str()).collect();
         utils::remove_old_droplets(&self.metrics.droplet_bandwidth, &droplets_names);
 
+        if bandwidth.transfer && enable_public_out {
+            self.record_transfer(&droplets, &transfer_samples, interval_end);
+        }
+
         Ok(())
     }
 
@@ -450,6 +605,7 @@ mod tests {
                     interval: StdDuration::from_secs(60),
                     types: vec![BandwidthType::PublicInbound],
                     keys: vec![],
+                    transfer: false,
                 }),
                 cpu: Some(CpuSettings {
                     enabled: true,
@@ -475,6 +631,7 @@ mod tests {
                 cpu_percentage: None,
                 memory_percentage: None,
                 restart_count: None,
+                bandwidth_daily: None,
             },
             exporter_metrics: crate::config::config_model::ExporterMetricsConfigs {
                 enabled: false,
@@ -491,6 +648,7 @@ mod tests {
                 prefix: None,
                 labels: std::collections::HashMap::new(),
             },
+            ..Default::default()
         };
         Box::leak(Box::new(config))
     }
@@ -508,6 +666,7 @@ mod tests {
             disk: 50,
             locked: false,
             status: "active".to_string(),
+            ..Default::default()
         }];
 
         mock_store
@@ -566,6 +725,7 @@ mod tests {
             disk: 100,
             locked: false,
             status: "active".to_string(),
+            ..Default::default()
         }];
 
         mock_store
@@ -627,6 +787,7 @@ mod tests {
             disk: 200,
             locked: false,
             status: "active".to_string(),
+            ..Default::default()
         }];
 
         mock_store
@@ -686,6 +847,7 @@ mod tests {
             disk: 25,
             locked: false,
             status: "active".to_string(),
+            ..Default::default()
         }];
 
         mock_store
@@ -734,4 +896,54 @@ mod tests {
         let result = service.load_filesystem_metrics().await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_record_transfer() {
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let service = DropletMetricsServiceImpl::new(
+            Arc::new(MockDigitalOceanClient::new()),
+            Arc::new(MockDropletStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
+
+        let droplets = vec![BasicDropletInfo {
+            id: 123,
+            name: "web".to_string(),
+            transfer: 1_f64,
+            ..Default::default()
+        }];
+        let samples = vec![("web".to_string(), 8_f64)];
+        let time = |x: &str| x.parse::<DateTime<Utc>>().unwrap();
+
+        // the first sample only starts the integration
+        service.record_transfer(&droplets, &samples, time("2024-04-15T00:00:00Z"));
+        assert_eq!(service.metrics.transfer_total.get(), 0_f64);
+
+        // 8 Mbps is 1 MB per second
+        service.record_transfer(&droplets, &samples, time("2024-04-15T00:01:40Z"));
+        let transfer = service
+            .metrics
+            .droplet_transfer
+            .with_label_values(&["web"])
+            .get();
+        assert_eq!(transfer, 100_000_000_f64);
+        assert_eq!(service.metrics.transfer_total.get(), 100_000_000_f64);
+        assert_eq!(
+            service.metrics.transfer_allowance.get(),
+            1000_f64 * 1024_f64 * 1024_f64 * 1024_f64
+        );
+        // 1 MB per second for the whole April, though only the second half was observed
+        assert_eq!(
+            service.metrics.transfer_projected.get(),
+            30_f64 * 24_f64 * 3600_f64 * 1_000_000_f64
+        );
+        assert!(service.metrics.transfer_projected_overage.get() > 0_f64);
+
+        // the transfer is reset with the new month
+        service.record_transfer(&droplets, &samples, time("2024-05-01T00:00:10Z"));
+        assert_eq!(service.metrics.transfer_total.get(), 0_f64);
+    }
 }
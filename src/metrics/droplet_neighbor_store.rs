@@ -0,0 +1,190 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::metrics::droplet_store::DropletStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DropletNeighborStore: Send + Sync {
+    async fn load_neighbors(&self) -> anyhow::Result<()>;
+
+    fn record_neighbor_metrics(&self);
+}
+
+#[derive(Clone)]
+pub struct DropletNeighborStoreImpl {
+    // groups of droplet ids sharing the same physical host
+    store: Arc<RwLock<Vec<Vec<u64>>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    droplet_store: Arc<dyn DropletStore>,
+    metrics: DropletNeighborMetrics,
+}
+
+impl DropletNeighborStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        droplet_store: Arc<dyn DropletStore>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            client,
+            droplet_store,
+            metrics: DropletNeighborMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct DropletNeighborMetrics {
+    shares_host_gauge: prometheus::GaugeVec,
+}
+
+impl DropletNeighborMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let shares_host_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_droplet_shares_host",
+                "Droplet shares physical hardware with other droplets of the group. Values is always 1.",
+            ),
+            &["droplet", "group"],
+        )?;
+
+        registry.register(Box::new(shares_host_gauge.clone()))?;
+
+        let result = Self { shares_host_gauge };
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl DropletNeighborStore for DropletNeighborStoreImpl {
+    async fn load_neighbors(&self) -> anyhow::Result<()> {
+        let loaded = self.client.get_droplet_neighbors().await?;
+        *self.store.write() = loaded.neighbor_ids;
+        Ok(())
+    }
+
+    fn record_neighbor_metrics(&self) {
+        let droplets = self.droplet_store.list_droplets();
+        let names: HashMap<u64, &str> = droplets.iter().map(|x| (x.id, x.name.as_str())).collect();
+
+        let groups = self.store.read();
+        let mut valid_series: HashSet<(String, String)> = HashSet::default();
+        for group in groups.iter() {
+            // droplets unknown to the droplet store are reported with their ids
+            let mut members: Vec<String> = group
+                .iter()
+                .map(|id| {
+                    names
+                        .get(id)
+                        .map(|x| x.to_string())
+                        .unwrap_or_else(|| id.to_string())
+                })
+                .collect();
+            members.sort();
+            let group_label = members.join(",");
+            for droplet in members {
+                self.metrics
+                    .shares_host_gauge
+                    .with_label_values(&[droplet.as_str(), group_label.as_str()])
+                    .set(1_f64);
+                valid_series.insert((droplet, group_label.clone()));
+            }
+        }
+
+        // to prevent phantom groups after droplets were moved or removed
+        utils::retain_gauge_series(&self.metrics.shares_host_gauge, |labels| {
+            let droplet = labels.get("droplet").copied().unwrap_or_default();
+            let group = labels.get("group").copied().unwrap_or_default();
+            valid_series.contains(&(droplet.to_string(), group.to_string()))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::DropletNeighborsResponse;
+    use crate::metrics::droplet_store::{BasicDropletInfo, MockDropletStore};
+    use prometheus::core::Collector;
+
+    fn droplet(id: u64, name: &str) -> BasicDropletInfo {
+        BasicDropletInfo {
+            id,
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_neighbors_joined_to_droplet_names() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        let mut seq = mockall::Sequence::new();
+        mock_client
+            .expect_get_droplet_neighbors()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| {
+                Ok(DropletNeighborsResponse {
+                    neighbor_ids: vec![vec![2, 1], vec![3, 4]],
+                })
+            });
+        mock_client
+            .expect_get_droplet_neighbors()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| {
+                Ok(DropletNeighborsResponse {
+                    neighbor_ids: vec![vec![1, 2]],
+                })
+            });
+
+        let mut mock_droplet_store = MockDropletStore::new();
+        mock_droplet_store.expect_list_droplets().returning(|| {
+            vec![
+                droplet(1, "db-primary"),
+                droplet(2, "db-replica"),
+                droplet(3, "web-1"),
+            ]
+        });
+
+        let registry = prometheus::Registry::new();
+        let store = DropletNeighborStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_droplet_store),
+            registry,
+        )
+        .unwrap();
+
+        store.load_neighbors().await.unwrap();
+        store.record_neighbor_metrics();
+
+        let gauge = &store.metrics.shares_host_gauge;
+        let series_count = || {
+            gauge
+                .collect()
+                .iter()
+                .map(|m| m.get_metric().len())
+                .sum::<usize>()
+        };
+        let value = gauge
+            .with_label_values(&["db-replica", "db-primary,db-replica"])
+            .get();
+        assert_eq!(value, 1_f64);
+        // unknown droplets are reported with their ids
+        let value = gauge.with_label_values(&["4", "4,web-1"]).get();
+        assert_eq!(value, 1_f64);
+        assert_eq!(series_count(), 4);
+
+        store.load_neighbors().await.unwrap();
+        store.record_neighbor_metrics();
+        assert_eq!(series_count(), 2);
+    }
+}
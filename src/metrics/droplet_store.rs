@@ -1,12 +1,15 @@
 use crate::client::do_client::DigitalOceanClient;
 use crate::client::do_json_protocol::DropletResponse;
 use crate::config::config_model::{AppSettings, DropletMetricsTypes};
+use crate::metrics::project_store::ProjectStore;
 use crate::metrics::utils;
-use ahash::HashSet;
+use ahash::{HashMap, HashSet};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use prometheus::Opts;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -16,9 +19,12 @@ pub trait DropletStore: Send + Sync {
     fn record_droplets_metrics(&self);
 
     fn list_droplets(&self) -> Vec<BasicDropletInfo>;
+
+    // true after the first successful load
+    fn is_loaded(&self) -> bool;
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 #[allow(dead_code)]
 pub struct BasicDropletInfo {
     pub id: u64,
@@ -28,6 +34,21 @@ pub struct BasicDropletInfo {
     pub disk: u64,
     pub locked: bool,
     pub status: String,
+    pub backup_ids: Vec<u64>,
+    // start of the next backup window. None if backups are disabled
+    pub next_backup_window: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    pub vpc_uuid: Option<String>,
+    // size and region slugs. Empty if missing in the response
+    pub size: String,
+    pub region: String,
+    pub price_hourly: f64,
+    pub price_monthly: f64,
+    // monthly transfer allowance in TB
+    pub transfer: f64,
+    // public and private IPv4 and IPv6 addresses
+    pub ips: Vec<String>,
+    pub image_id: Option<u64>,
 }
 
 impl From<DropletResponse> for BasicDropletInfo {
@@ -40,6 +61,35 @@ impl From<DropletResponse> for BasicDropletInfo {
             disk: value.disk,
             locked: value.locked,
             status: value.status,
+            backup_ids: value.backup_ids,
+            next_backup_window: value.next_backup_window.map(|x| x.start),
+            tags: value.tags,
+            vpc_uuid: value.vpc_uuid,
+            size: value
+                .size
+                .as_ref()
+                .map(|x| x.slug.clone())
+                .unwrap_or_default(),
+            region: value.region.map(|x| x.slug).unwrap_or_default(),
+            price_hourly: value
+                .size
+                .as_ref()
+                .map(|x| x.price_hourly)
+                .unwrap_or_default(),
+            price_monthly: value
+                .size
+                .as_ref()
+                .map(|x| x.price_monthly)
+                .unwrap_or_default(),
+            transfer: value.size.map(|x| x.transfer).unwrap_or_default(),
+            ips: value
+                .networks
+                .v4
+                .into_iter()
+                .chain(value.networks.v6)
+                .map(|x| x.ip_address)
+                .collect(),
+            image_id: value.image.map(|x| x.id),
         }
     }
 }
@@ -47,7 +97,9 @@ impl From<DropletResponse> for BasicDropletInfo {
 #[derive(Clone)]
 pub struct DropletStoreImpl {
     store: Arc<RwLock<Vec<BasicDropletInfo>>>,
+    loaded: Arc<AtomicBool>,
     client: Arc<dyn DigitalOceanClient>,
+    project_store: Arc<dyn ProjectStore>,
     configs: &'static AppSettings,
     metrics: DropletsMetrics,
 }
@@ -55,14 +107,18 @@ pub struct DropletStoreImpl {
 impl DropletStoreImpl {
     pub fn new(
         client: Arc<dyn DigitalOceanClient>,
+        project_store: Arc<dyn ProjectStore>,
         configs: &'static AppSettings,
         registry: prometheus::Registry,
     ) -> anyhow::Result<Self> {
+        let with_project = configs.projects.enabled && configs.projects.label;
         let result = Self {
             store: Arc::new(RwLock::new(vec![])),
+            loaded: Arc::new(AtomicBool::new(false)),
             client,
+            project_store,
             configs,
-            metrics: DropletsMetrics::new(registry)?,
+            metrics: DropletsMetrics::new(registry, with_project)?,
         };
         Ok(result)
     }
@@ -74,46 +130,110 @@ struct DropletsMetrics {
     vcpu_gauge: prometheus::GaugeVec,
     disk_gauge: prometheus::GaugeVec,
     status_gauge: prometheus::GaugeVec,
+    backups_enabled_gauge: prometheus::GaugeVec,
+    backups_count_gauge: prometheus::GaugeVec,
+    next_backup_window_gauge: prometheus::GaugeVec,
+    price_hourly_gauge: prometheus::GaugeVec,
+    price_monthly_gauge: prometheus::GaugeVec,
+    fleet_price_hourly_gauge: prometheus::GaugeVec,
 }
 
 impl DropletsMetrics {
-    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+    fn new(registry: prometheus::Registry, with_project: bool) -> anyhow::Result<Self> {
+        let labels = |names: &[&'static str]| {
+            let mut names = names.to_vec();
+            if with_project {
+                names.push("project");
+            }
+            names
+        };
         let memory_gauge = prometheus::GaugeVec::new(
             Opts::new(
                 "droxporter_droplet_memory_settings",
                 "Memory settings of droplet",
             ),
-            &["droplet"],
+            &labels(&["droplet"]),
         )?;
         let vcpu_gauge = prometheus::GaugeVec::new(
             Opts::new(
                 "droxporter_droplet_vcpu_settings",
                 "Cpu settings of droplet",
             ),
-            &["droplet"],
+            &labels(&["droplet"]),
         )?;
         let disk_gauge = prometheus::GaugeVec::new(
             Opts::new(
                 "droxporter_droplet_disk_settings",
                 "Disk settings of droplet",
             ),
-            &["droplet"],
+            &labels(&["droplet"]),
         )?;
         let status_gauge = prometheus::GaugeVec::new(
             Opts::new("droxporter_droplet_status", "Status of droplet"),
-            &["droplet", "status"],
+            &labels(&["droplet", "status"]),
+        )?;
+        let backups_enabled_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_droplet_backups_enabled",
+                "1 if backups are enabled for droplet, otherwise 0",
+            ),
+            &labels(&["droplet"]),
+        )?;
+        let backups_count_gauge = prometheus::GaugeVec::new(
+            Opts::new("droxporter_droplet_backups", "Count of droplet backups"),
+            &labels(&["droplet"]),
+        )?;
+        let next_backup_window_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_droplet_next_backup_window_timestamp_seconds",
+                "Start of the next backup window of droplet in seconds from epoch",
+            ),
+            &labels(&["droplet"]),
+        )?;
+        let price_hourly_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_droplet_price_hourly_dollars",
+                "Hourly price of droplet size in US dollars",
+            ),
+            &labels(&["droplet"]),
+        )?;
+        let price_monthly_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_droplet_price_monthly_dollars",
+                "Monthly price of droplet size in US dollars",
+            ),
+            &labels(&["droplet"]),
+        )?;
+        let fleet_price_hourly_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_droplets_price_hourly_dollars",
+                "Total hourly price of droplets in US dollars grouped by tag, region and size",
+            ),
+            &["tag", "region", "size"],
         )?;
 
         registry.register(Box::new(memory_gauge.clone()))?;
         registry.register(Box::new(vcpu_gauge.clone()))?;
         registry.register(Box::new(disk_gauge.clone()))?;
         registry.register(Box::new(status_gauge.clone()))?;
+        registry.register(Box::new(backups_enabled_gauge.clone()))?;
+        registry.register(Box::new(backups_count_gauge.clone()))?;
+        registry.register(Box::new(next_backup_window_gauge.clone()))?;
+        registry.register(Box::new(price_hourly_gauge.clone()))?;
+        registry.register(Box::new(price_monthly_gauge.clone()))?;
+        registry.register(Box::new(fleet_price_hourly_gauge.clone()))?;
 
         let result = Self {
             memory_gauge,
             vcpu_gauge,
             disk_gauge,
             status_gauge,
+            backups_enabled_gauge,
+            backups_count_gauge,
+            next_backup_window_gauge,
+            price_hourly_gauge,
+            price_monthly_gauge,
+            fleet_price_hourly_gauge,
         };
         Ok(result)
     }
@@ -122,6 +242,7 @@ impl DropletsMetrics {
 impl DropletStoreImpl {
     fn save_droplets(&self, droplets: Vec<BasicDropletInfo>) {
         *self.store.write() = droplets;
+        self.loaded.store(true, Ordering::Relaxed);
     }
 }
 
@@ -163,69 +284,164 @@ impl DropletStore for DropletStoreImpl {
             .droplets
             .metrics
             .contains(&DropletMetricsTypes::Status);
+        let enabled_backups = self
+            .configs
+            .droplets
+            .metrics
+            .contains(&DropletMetricsTypes::Backups);
+        let enabled_price = self
+            .configs
+            .droplets
+            .metrics
+            .contains(&DropletMetricsTypes::Price);
+        let enabled_project = self.configs.projects.enabled && self.configs.projects.label;
 
-        for droplet in self.store.read().iter() {
+        let lock = self.store.read();
+        // droplet name -> project name. Droplets without a project get an empty label
+        let projects: HashMap<&str, String> = if enabled_project {
+            lock.iter()
+                .map(|x| {
+                    let urn = format!("do:droplet:{}", x.id);
+                    let project = self.project_store.get_project(&urn).unwrap_or_default();
+                    (x.name.as_str(), project)
+                })
+                .collect()
+        } else {
+            HashMap::default()
+        };
+
+        for droplet in lock.iter() {
             let name = &droplet.name;
+            let mut labels = std::collections::HashMap::from([("droplet", name.as_str())]);
+            if let Some(project) = projects.get(name.as_str()) {
+                labels.insert("project", project.as_str());
+            }
+
             if enabled_memory {
                 self.metrics
                     .memory_gauge
-                    .with(&std::collections::HashMap::<
-                        &str,
-                        &str,
-                        std::hash::RandomState,
-                    >::from([("droplet", name.as_ref())]))
+                    .with(&labels)
                     .set(droplet.memory as f64);
             }
 
             if enabled_vcpu {
                 self.metrics
                     .vcpu_gauge
-                    .with(&std::collections::HashMap::<
-                        &str,
-                        &str,
-                        std::hash::RandomState,
-                    >::from([("droplet", name.as_ref())]))
+                    .with(&labels)
                     .set(droplet.vcpus as f64);
             }
 
             if enabled_disc {
                 self.metrics
                     .disk_gauge
-                    .with(&std::collections::HashMap::<
-                        &str,
-                        &str,
-                        std::hash::RandomState,
-                    >::from([("droplet", name.as_ref())]))
+                    .with(&labels)
                     .set(droplet.disk as f64);
             }
 
             if enabled_status {
+                let mut status_labels = labels.clone();
+                status_labels.insert("status", droplet.status.as_str());
+                self.metrics.status_gauge.with(&status_labels).set(1_f64);
+            }
+
+            if enabled_backups {
+                let backups_enabled = droplet.next_backup_window.is_some();
+                self.metrics
+                    .backups_enabled_gauge
+                    .with(&labels)
+                    .set(if backups_enabled { 1_f64 } else { 0_f64 });
+                self.metrics
+                    .backups_count_gauge
+                    .with(&labels)
+                    .set(droplet.backup_ids.len() as f64);
+                match droplet.next_backup_window {
+                    Some(window) => self
+                        .metrics
+                        .next_backup_window_gauge
+                        .with(&labels)
+                        .set(window.timestamp() as f64),
+                    None => {
+                        let _ = self.metrics.next_backup_window_gauge.remove(&labels);
+                    }
+                }
+            }
+
+            if enabled_price {
+                self.metrics
+                    .price_hourly_gauge
+                    .with(&labels)
+                    .set(droplet.price_hourly);
                 self.metrics
-                    .status_gauge
-                    .with(&std::collections::HashMap::<
-                        &str,
-                        &str,
-                        std::hash::RandomState,
-                    >::from([
-                        ("droplet", name.as_ref()),
-                        ("status", droplet.status.as_ref()),
-                    ]))
-                    .set(1_f64);
+                    .price_monthly_gauge
+                    .with(&labels)
+                    .set(droplet.price_monthly);
             }
         }
-        let lock = self.store.read();
+
+        // tag, region, size -> hourly price. Droplets with several tags are added to each
+        // of their tags, untagged droplets get an empty tag
+        let mut fleet: HashMap<(&str, &str, &str), f64> = HashMap::default();
+        if enabled_price {
+            for droplet in lock.iter() {
+                let tags: Vec<&str> = if droplet.tags.is_empty() {
+                    vec![""]
+                } else {
+                    droplet.tags.iter().map(|x| x.as_str()).collect()
+                };
+                for tag in tags {
+                    *fleet
+                        .entry((tag, droplet.region.as_str(), droplet.size.as_str()))
+                        .or_default() += droplet.price_hourly;
+                }
+            }
+        }
+        for ((tag, region, size), price) in fleet.iter() {
+            self.metrics
+                .fleet_price_hourly_gauge
+                .with_label_values(&[tag, region, size])
+                .set(*price);
+        }
+        // to prevent phantom groups after droplets were removed or resized
+        utils::retain_gauge_series(&self.metrics.fleet_price_hourly_gauge, |labels| {
+            let tag = labels.get("tag").copied().unwrap_or_default();
+            let region = labels.get("region").copied().unwrap_or_default();
+            let size = labels.get("size").copied().unwrap_or_default();
+            fleet.contains_key(&(tag, region, size))
+        });
         let droplets: HashSet<_> = { lock.iter().map(|x| x.name.as_str()).collect() };
 
-        // to prevent phantom droplets
-        utils::remove_old_droplets(&self.metrics.memory_gauge, &droplets);
-        utils::remove_old_droplets(&self.metrics.vcpu_gauge, &droplets);
-        utils::remove_old_droplets(&self.metrics.disk_gauge, &droplets);
-        utils::remove_old_droplets(&self.metrics.status_gauge, &droplets);
+        let gauges = [
+            &self.metrics.memory_gauge,
+            &self.metrics.vcpu_gauge,
+            &self.metrics.disk_gauge,
+            &self.metrics.status_gauge,
+            &self.metrics.backups_enabled_gauge,
+            &self.metrics.backups_count_gauge,
+            &self.metrics.next_backup_window_gauge,
+            &self.metrics.price_hourly_gauge,
+            &self.metrics.price_monthly_gauge,
+        ];
+        for gauge in gauges {
+            // to prevent phantom droplets
+            utils::remove_old_droplets(gauge, &droplets);
+            // droplets can be moved between projects
+            if enabled_project {
+                utils::retain_gauge_series(gauge, |labels| {
+                    let droplet = labels.get("droplet").copied().unwrap_or_default();
+                    let project = labels.get("project").copied().unwrap_or_default();
+                    projects.get(droplet).map(|x| x.as_str()) == Some(project)
+                });
+            }
+        }
     }
 
     fn list_droplets(&self) -> Vec<BasicDropletInfo> {
         self.store.read().clone()
     }
+
+    fn is_loaded(&self) -> bool {
+        self.loaded.load(Ordering::Relaxed)
+    }
 }
 
 #[cfg(test)]
@@ -234,6 +450,7 @@ mod tests {
     use crate::client::do_client::MockDigitalOceanClient;
     use crate::client::do_json_protocol::{DropletResponse, Links, ListDropletsResponse, Pages};
     use crate::config::config_model::AppSettings;
+    use crate::metrics::project_store::MockProjectStore;
     use prometheus::core::Collector;
     use std::time::Duration as StdDuration;
 
@@ -249,6 +466,8 @@ mod tests {
                     crate::config::config_model::DropletMetricsTypes::VCpu,
                     crate::config::config_model::DropletMetricsTypes::Disk,
                     crate::config::config_model::DropletMetricsTypes::Status,
+                    crate::config::config_model::DropletMetricsTypes::Backups,
+                    crate::config::config_model::DropletMetricsTypes::Price,
                 ],
             },
             apps: crate::config::config_model::AppPlatformSettings {
@@ -270,6 +489,7 @@ mod tests {
                 cpu_percentage: None,
                 memory_percentage: None,
                 restart_count: None,
+                bandwidth_daily: None,
             },
             exporter_metrics: crate::config::config_model::ExporterMetricsConfigs {
                 enabled: false,
@@ -286,6 +506,7 @@ mod tests {
                 prefix: None,
                 labels: std::collections::HashMap::new(),
             },
+            ..Default::default()
         };
         Box::leak(Box::new(config))
     }
@@ -309,6 +530,7 @@ mod tests {
                             disk: 25,
                             locked: false,
                             status: "active".to_string(),
+                            ..Default::default()
                         },
                         DropletResponse {
                             id: 456,
@@ -318,6 +540,7 @@ mod tests {
                             disk: 50,
                             locked: false,
                             status: "active".to_string(),
+                            ..Default::default()
                         },
                     ],
                     links: Links {
@@ -333,7 +556,13 @@ mod tests {
 
         let config = create_test_config();
         let registry = prometheus::Registry::new();
-        let store = DropletStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+        let store = DropletStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
 
         let result = store.load_droplets().await;
         assert!(result.is_ok());
@@ -365,6 +594,7 @@ mod tests {
                         disk: 25,
                         locked: false,
                         status: "active".to_string(),
+                        ..Default::default()
                     }],
                     links: Links {
                         pages: Pages {
@@ -392,6 +622,7 @@ mod tests {
                         disk: 50,
                         locked: false,
                         status: "active".to_string(),
+                        ..Default::default()
                     }],
                     links: Links {
                         pages: Pages {
@@ -406,7 +637,13 @@ mod tests {
 
         let config = create_test_config();
         let registry = prometheus::Registry::new();
-        let store = DropletStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+        let store = DropletStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
 
         let result = store.load_droplets().await;
         assert!(result.is_ok());
@@ -422,7 +659,13 @@ mod tests {
         let mock_client = MockDigitalOceanClient::new();
         let config = create_test_config();
         let registry = prometheus::Registry::new();
-        let store = DropletStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+        let store = DropletStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
 
         // Manually populate store for testing metrics recording
         let droplets = vec![
@@ -434,6 +677,7 @@ mod tests {
                 disk: 50,
                 locked: false,
                 status: "active".to_string(),
+                ..Default::default()
             },
             BasicDropletInfo {
                 id: 456,
@@ -443,6 +687,11 @@ mod tests {
                 disk: 100,
                 locked: true,
                 status: "off".to_string(),
+                backup_ids: vec![1, 2],
+                next_backup_window: Some("2019-12-04T00:00:00Z".parse().unwrap()),
+                tags: vec![],
+                vpc_uuid: None,
+                ..Default::default()
             },
         ];
         store.save_droplets(droplets);
@@ -458,6 +707,25 @@ mod tests {
         assert_eq!(droplets.len(), 2);
         assert_eq!(droplets[0].memory, 1024);
         assert_eq!(droplets[1].memory, 2048);
+
+        let backups_enabled = store
+            .metrics
+            .backups_enabled_gauge
+            .with_label_values(&["test-droplet"])
+            .get();
+        assert_eq!(backups_enabled, 0_f64);
+        let backups = store
+            .metrics
+            .backups_count_gauge
+            .with_label_values(&["test-droplet-2"])
+            .get();
+        assert_eq!(backups, 2_f64);
+        let next_window = store
+            .metrics
+            .next_backup_window_gauge
+            .with_label_values(&["test-droplet-2"])
+            .get();
+        assert_eq!(next_window, 1575417600_f64);
     }
 
     #[tokio::test]
@@ -465,9 +733,150 @@ mod tests {
         let mock_client = MockDigitalOceanClient::new();
         let config = create_test_config();
         let registry = prometheus::Registry::new();
-        let store = DropletStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+        let store = DropletStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
 
         let droplets = store.list_droplets();
         assert_eq!(droplets.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_record_droplets_metrics_with_project() {
+        let mut config = create_test_config().clone();
+        config.projects.enabled = true;
+        config.projects.label = true;
+        let config: &'static AppSettings = Box::leak(Box::new(config));
+
+        let project = Arc::new(parking_lot::Mutex::new("web".to_string()));
+        let mut mock_project_store = MockProjectStore::new();
+        mock_project_store.expect_get_project().returning({
+            let project = project.clone();
+            move |urn| (urn == "do:droplet:123").then(|| project.lock().clone())
+        });
+
+        let registry = prometheus::Registry::new();
+        let store = DropletStoreImpl::new(
+            Arc::new(MockDigitalOceanClient::new()),
+            Arc::new(mock_project_store),
+            config,
+            registry,
+        )
+        .unwrap();
+
+        store.save_droplets(vec![
+            BasicDropletInfo {
+                id: 123,
+                name: "test-droplet".to_string(),
+                memory: 1024,
+                ..Default::default()
+            },
+            BasicDropletInfo {
+                id: 456,
+                name: "test-droplet-2".to_string(),
+                memory: 2048,
+                ..Default::default()
+            },
+        ]);
+        store.record_droplets_metrics();
+
+        let memory = store
+            .metrics
+            .memory_gauge
+            .with_label_values(&["test-droplet", "web"])
+            .get();
+        assert_eq!(memory, 1024_f64);
+        let memory = store
+            .metrics
+            .memory_gauge
+            .with_label_values(&["test-droplet-2", ""])
+            .get();
+        assert_eq!(memory, 2048_f64);
+
+        // the droplet has been moved to another project
+        *project.lock() = "api".to_string();
+        store.record_droplets_metrics();
+
+        let projects: Vec<String> = store
+            .metrics
+            .memory_gauge
+            .collect()
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .flat_map(|m| {
+                m.get_label()
+                    .iter()
+                    .filter(|l| l.name() == "project")
+                    .map(|l| l.value().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(projects.len(), 2);
+        assert!(projects.contains(&"api".to_string()));
+        assert!(!projects.contains(&"web".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_record_price_metrics() {
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let store = DropletStoreImpl::new(
+            Arc::new(MockDigitalOceanClient::new()),
+            Arc::new(MockProjectStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
+
+        let droplet =
+            |id: u64, name: &str, tags: &[&str], size: &str, price_hourly: f64| BasicDropletInfo {
+                id,
+                name: name.to_string(),
+                tags: tags.iter().map(|x| x.to_string()).collect(),
+                size: size.to_string(),
+                region: "fra1".to_string(),
+                price_hourly,
+                price_monthly: price_hourly * 672_f64,
+                ..Default::default()
+            };
+        store.save_droplets(vec![
+            droplet(1, "web-1", &["web"], "s-1vcpu-2gb", 0.01786),
+            droplet(2, "web-2", &["web", "public"], "s-1vcpu-2gb", 0.01786),
+            droplet(3, "db", &[], "s-2vcpu-4gb", 0.03571),
+        ]);
+        store.record_droplets_metrics();
+
+        let price = store
+            .metrics
+            .price_hourly_gauge
+            .with_label_values(&["db"])
+            .get();
+        assert_eq!(price, 0.03571);
+        let fleet = |tag: &str, size: &str| {
+            store
+                .metrics
+                .fleet_price_hourly_gauge
+                .with_label_values(&[tag, "fra1", size])
+                .get()
+        };
+        assert_eq!(fleet("web", "s-1vcpu-2gb"), 0.01786 * 2_f64);
+        assert_eq!(fleet("public", "s-1vcpu-2gb"), 0.01786);
+        assert_eq!(fleet("", "s-2vcpu-4gb"), 0.03571);
+
+        // the database droplet was removed
+        store.save_droplets(vec![droplet(1, "web-1", &["web"], "s-1vcpu-2gb", 0.01786)]);
+        store.record_droplets_metrics();
+        let groups: usize = store
+            .metrics
+            .fleet_price_hourly_gauge
+            .collect()
+            .iter()
+            .map(|m| m.get_metric().len())
+            .sum();
+        assert_eq!(groups, 1);
+    }
 }
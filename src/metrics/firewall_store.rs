@@ -0,0 +1,404 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::FirewallResponse;
+use crate::metrics::droplet_store::{BasicDropletInfo, DropletStore};
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait FirewallStore: Send + Sync {
+    async fn load_firewalls(&self) -> anyhow::Result<()>;
+
+    fn record_firewall_metrics(&self);
+
+    fn list_firewalls(&self) -> Vec<BasicFirewallInfo>;
+
+    // true after the first successful load
+    fn is_loaded(&self) -> bool;
+}
+
+#[derive(Clone)]
+pub struct BasicFirewallInfo {
+    pub name: String,
+    pub status: String,
+    pub inbound_rules: usize,
+    pub outbound_rules: usize,
+    pub droplet_ids: Vec<u64>,
+    pub tags: Vec<String>,
+}
+
+impl From<FirewallResponse> for BasicFirewallInfo {
+    fn from(value: FirewallResponse) -> Self {
+        Self {
+            name: value.name,
+            status: value.status,
+            inbound_rules: value.inbound_rules.len(),
+            outbound_rules: value.outbound_rules.len(),
+            droplet_ids: value.droplet_ids,
+            tags: value.tags,
+        }
+    }
+}
+
+impl BasicFirewallInfo {
+    // a firewall is applied to droplets listed directly and to droplets with any of its tags
+    pub fn applies_to(&self, droplet: &BasicDropletInfo) -> bool {
+        self.droplet_ids.contains(&droplet.id) || droplet.tags.iter().any(|x| self.tags.contains(x))
+    }
+}
+
+#[derive(Clone)]
+pub struct FirewallStoreImpl {
+    store: Arc<RwLock<Vec<BasicFirewallInfo>>>,
+    loaded: Arc<AtomicBool>,
+    client: Arc<dyn DigitalOceanClient>,
+    droplet_store: Arc<dyn DropletStore>,
+    metrics: FirewallMetrics,
+}
+
+impl FirewallStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        droplet_store: Arc<dyn DropletStore>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            loaded: Arc::new(AtomicBool::new(false)),
+            client,
+            droplet_store,
+            metrics: FirewallMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct FirewallMetrics {
+    rules_gauge: prometheus::GaugeVec,
+    status_gauge: prometheus::GaugeVec,
+    droplets_gauge: prometheus::GaugeVec,
+    droplet_info_gauge: prometheus::GaugeVec,
+    tag_info_gauge: prometheus::GaugeVec,
+    firewalled_gauge: prometheus::GaugeVec,
+}
+
+impl FirewallMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let rules_gauge = prometheus::GaugeVec::new(
+            Opts::new("droxporter_firewall_rules", "Count of firewall rules"),
+            &["firewall", "direction"],
+        )?;
+        let status_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_firewall_status",
+                "The label status indicates the current status of the firewall. Values is always 1.",
+            ),
+            &["firewall", "status"],
+        )?;
+        let droplets_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_firewall_droplets",
+                "Count of droplets the firewall is applied to, directly or by tags",
+            ),
+            &["firewall"],
+        )?;
+        let droplet_info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_firewall_droplet_info",
+                "Droplet the firewall is assigned to directly. Values is always 1.",
+            ),
+            &["firewall", "droplet"],
+        )?;
+        let tag_info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_firewall_tag_info",
+                "Tag the firewall is assigned to. Values is always 1.",
+            ),
+            &["firewall", "tag"],
+        )?;
+        let firewalled_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_droplet_firewalled",
+                "1 if at least one firewall is applied to droplet, otherwise 0",
+            ),
+            &["droplet"],
+        )?;
+
+        registry.register(Box::new(rules_gauge.clone()))?;
+        registry.register(Box::new(status_gauge.clone()))?;
+        registry.register(Box::new(droplets_gauge.clone()))?;
+        registry.register(Box::new(droplet_info_gauge.clone()))?;
+        registry.register(Box::new(tag_info_gauge.clone()))?;
+        registry.register(Box::new(firewalled_gauge.clone()))?;
+
+        let result = Self {
+            rules_gauge,
+            status_gauge,
+            droplets_gauge,
+            droplet_info_gauge,
+            tag_info_gauge,
+            firewalled_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl FirewallStoreImpl {
+    fn save_firewalls(&self, firewalls: Vec<BasicFirewallInfo>) {
+        *self.store.write() = firewalls;
+        self.loaded.store(true, Ordering::Relaxed);
+    }
+}
+
+fn firewall_label<'a>(labels: &std::collections::HashMap<&'a str, &'a str>) -> &'a str {
+    labels.get("firewall").copied().unwrap_or_default()
+}
+
+#[async_trait]
+impl FirewallStore for FirewallStoreImpl {
+    async fn load_firewalls(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicFirewallInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_firewalls(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.firewalls.into_iter().map(BasicFirewallInfo::from));
+            page += 1;
+        }
+        self.save_firewalls(result);
+        Ok(())
+    }
+
+    fn record_firewall_metrics(&self) {
+        let droplets = self.droplet_store.list_droplets();
+        let droplet_names: HashMap<u64, &str> =
+            droplets.iter().map(|x| (x.id, x.name.as_str())).collect();
+
+        let lock = self.store.read();
+        let mut valid_firewalls: HashSet<&str> = HashSet::default();
+        let mut valid_statuses: HashSet<(&str, &str)> = HashSet::default();
+        let mut valid_droplets: HashSet<(&str, String)> = HashSet::default();
+        let mut valid_tags: HashSet<(&str, &str)> = HashSet::default();
+        for firewall in lock.iter() {
+            let name = firewall.name.as_str();
+            self.metrics
+                .rules_gauge
+                .with_label_values(&[name, "inbound"])
+                .set(firewall.inbound_rules as f64);
+            self.metrics
+                .rules_gauge
+                .with_label_values(&[name, "outbound"])
+                .set(firewall.outbound_rules as f64);
+            self.metrics
+                .status_gauge
+                .with_label_values(&[name, firewall.status.as_str()])
+                .set(1_f64);
+            let covered = droplets.iter().filter(|x| firewall.applies_to(x)).count();
+            self.metrics
+                .droplets_gauge
+                .with_label_values(&[name])
+                .set(covered as f64);
+
+            for droplet_id in firewall.droplet_ids.iter() {
+                // droplets can be created after the last droplets loading, so we fall back to the id
+                let droplet = droplet_names
+                    .get(droplet_id)
+                    .map(|x| x.to_string())
+                    .unwrap_or_else(|| droplet_id.to_string());
+                self.metrics
+                    .droplet_info_gauge
+                    .with_label_values(&[name, droplet.as_str()])
+                    .set(1_f64);
+                valid_droplets.insert((name, droplet));
+            }
+            for tag in firewall.tags.iter() {
+                self.metrics
+                    .tag_info_gauge
+                    .with_label_values(&[name, tag.as_str()])
+                    .set(1_f64);
+                valid_tags.insert((name, tag.as_str()));
+            }
+
+            valid_firewalls.insert(name);
+            valid_statuses.insert((name, firewall.status.as_str()));
+        }
+
+        for droplet in droplets.iter() {
+            let firewalled = lock.iter().any(|x| x.applies_to(droplet));
+            self.metrics
+                .firewalled_gauge
+                .with_label_values(&[droplet.name.as_str()])
+                .set(if firewalled { 1_f64 } else { 0_f64 });
+        }
+
+        // to prevent phantom firewalls, droplets and stale statuses
+        utils::retain_gauge_series(&self.metrics.rules_gauge, |labels| {
+            valid_firewalls.contains(firewall_label(labels))
+        });
+        utils::retain_gauge_series(&self.metrics.droplets_gauge, |labels| {
+            valid_firewalls.contains(firewall_label(labels))
+        });
+        utils::retain_gauge_series(&self.metrics.status_gauge, |labels| {
+            let status = labels.get("status").copied().unwrap_or_default();
+            valid_statuses.contains(&(firewall_label(labels), status))
+        });
+        utils::retain_gauge_series(&self.metrics.droplet_info_gauge, |labels| {
+            let droplet = labels.get("droplet").copied().unwrap_or_default();
+            valid_droplets.contains(&(firewall_label(labels), droplet.to_string()))
+        });
+        utils::retain_gauge_series(&self.metrics.tag_info_gauge, |labels| {
+            let tag = labels.get("tag").copied().unwrap_or_default();
+            valid_tags.contains(&(firewall_label(labels), tag))
+        });
+        let droplet_names: HashSet<&str> = droplets.iter().map(|x| x.name.as_str()).collect();
+        utils::remove_old_droplets(&self.metrics.firewalled_gauge, &droplet_names);
+    }
+
+    fn list_firewalls(&self) -> Vec<BasicFirewallInfo> {
+        self.store.read().clone()
+    }
+
+    fn is_loaded(&self) -> bool {
+        self.loaded.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{Links, ListFirewallsResponse, Pages};
+    use crate::metrics::droplet_store::MockDropletStore;
+
+    fn firewall(name: &str, droplet_ids: Vec<u64>, tags: Vec<&str>) -> FirewallResponse {
+        FirewallResponse {
+            id: format!("{name}-id"),
+            name: name.to_string(),
+            status: "succeeded".to_string(),
+            inbound_rules: vec![serde_json::json!({"protocol": "tcp", "ports": "22"})],
+            outbound_rules: vec![],
+            droplet_ids,
+            tags: tags.into_iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_firewalls_multiple_pages() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        // First page
+        mock_client
+            .expect_list_firewalls()
+            .withf(|per_page, page| *per_page == 100 && *page == 1)
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListFirewallsResponse {
+                    firewalls: vec![firewall("fw-1", vec![123], vec![])],
+                    links: Links {
+                        pages: Pages {
+                            next: Some("http://next".to_string()),
+                            ..Default::default()
+                        },
+                    },
+                })
+            });
+
+        // Second page
+        mock_client
+            .expect_list_firewalls()
+            .withf(|per_page, page| *per_page == 100 && *page == 2)
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListFirewallsResponse {
+                    firewalls: vec![firewall("fw-2", vec![], vec!["web"])],
+                    links: Links::default(),
+                })
+            });
+
+        let registry = prometheus::Registry::new();
+        let store = FirewallStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockDropletStore::new()),
+            registry,
+        )
+        .unwrap();
+
+        let result = store.load_firewalls().await;
+        assert!(result.is_ok());
+
+        let firewalls = store.list_firewalls();
+        assert_eq!(firewalls.len(), 2);
+        assert_eq!(firewalls[0].inbound_rules, 1);
+        assert_eq!(firewalls[1].tags, vec!["web".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_record_firewall_metrics() {
+        let mock_client = MockDigitalOceanClient::new();
+        let mut mock_store = MockDropletStore::new();
+
+        mock_store.expect_list_droplets().returning(|| {
+            vec![
+                BasicDropletInfo {
+                    id: 1,
+                    name: "direct".to_string(),
+                    ..Default::default()
+                },
+                BasicDropletInfo {
+                    id: 2,
+                    name: "tagged".to_string(),
+                    tags: vec!["web".to_string()],
+                    ..Default::default()
+                },
+                BasicDropletInfo {
+                    id: 3,
+                    name: "exposed".to_string(),
+                    tags: vec!["db".to_string()],
+                    ..Default::default()
+                },
+            ]
+        });
+
+        let registry = prometheus::Registry::new();
+        let store =
+            FirewallStoreImpl::new(Arc::new(mock_client), Arc::new(mock_store), registry).unwrap();
+
+        store.save_firewalls(vec![
+            firewall("fw-1", vec![1], vec![]).into(),
+            firewall("fw-2", vec![], vec!["web"]).into(),
+        ]);
+        store.record_firewall_metrics();
+
+        let firewalled = |droplet: &str| {
+            store
+                .metrics
+                .firewalled_gauge
+                .with_label_values(&[droplet])
+                .get()
+        };
+        assert_eq!(firewalled("direct"), 1_f64);
+        assert_eq!(firewalled("tagged"), 1_f64);
+        assert_eq!(firewalled("exposed"), 0_f64);
+
+        let covered = store
+            .metrics
+            .droplets_gauge
+            .with_label_values(&["fw-2"])
+            .get();
+        assert_eq!(covered, 1_f64);
+        let rules = store
+            .metrics
+            .rules_gauge
+            .with_label_values(&["fw-1", "inbound"])
+            .get();
+        assert_eq!(rules, 1_f64);
+    }
+}
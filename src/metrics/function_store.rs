@@ -0,0 +1,366 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::{FunctionNamespaceResponse, FunctionTriggerResponse};
+use crate::metrics::utils;
+use ahash::HashSet;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait FunctionStore: Send + Sync {
+    async fn load_namespaces(&self) -> anyhow::Result<()>;
+
+    fn record_function_metrics(&self);
+}
+
+#[derive(Clone, Default)]
+pub struct BasicFunctionNamespaceInfo {
+    pub namespace: String,
+    pub label: String,
+    pub region: String,
+    pub triggers: Vec<BasicFunctionTriggerInfo>,
+}
+
+impl From<FunctionNamespaceResponse> for BasicFunctionNamespaceInfo {
+    fn from(value: FunctionNamespaceResponse) -> Self {
+        Self {
+            namespace: value.namespace,
+            label: value.label,
+            region: value.region,
+            triggers: vec![],
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct BasicFunctionTriggerInfo {
+    pub name: String,
+    pub function: String,
+    pub trigger_type: String,
+    pub enabled: bool,
+    // None until the first run
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+impl From<FunctionTriggerResponse> for BasicFunctionTriggerInfo {
+    fn from(value: FunctionTriggerResponse) -> Self {
+        let (last_run_at, next_run_at) = value
+            .scheduled_runs
+            .map(|x| (x.last_run_at, x.next_run_at))
+            .unwrap_or_default();
+        Self {
+            name: value.name,
+            function: value.function,
+            trigger_type: value.trigger_type,
+            enabled: value.is_enabled,
+            last_run_at,
+            next_run_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct FunctionStoreImpl {
+    store: Arc<RwLock<Vec<BasicFunctionNamespaceInfo>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    metrics: FunctionMetrics,
+}
+
+impl FunctionStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            client,
+            metrics: FunctionMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct FunctionMetrics {
+    namespace_info_gauge: prometheus::GaugeVec,
+    namespace_triggers_gauge: prometheus::GaugeVec,
+    trigger_enabled_gauge: prometheus::GaugeVec,
+    trigger_last_run_gauge: prometheus::GaugeVec,
+    trigger_next_run_gauge: prometheus::GaugeVec,
+}
+
+impl FunctionMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let namespace_info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_function_namespace_info",
+                "Functions namespace. Value is always 1.",
+            ),
+            &["namespace", "label", "region"],
+        )?;
+        let namespace_triggers_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_function_namespace_triggers",
+                "Count of triggers in Functions namespace",
+            ),
+            &["namespace"],
+        )?;
+        let trigger_enabled_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_function_trigger_enabled",
+                "Functions trigger. 1 - enabled, 0 - disabled",
+            ),
+            &["namespace", "trigger", "function", "type"],
+        )?;
+        let trigger_last_run_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_function_trigger_last_run_timestamp_seconds",
+                "Unix timestamp of the trigger's last run",
+            ),
+            &["namespace", "trigger"],
+        )?;
+        let trigger_next_run_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_function_trigger_next_run_timestamp_seconds",
+                "Unix timestamp of the trigger's next run",
+            ),
+            &["namespace", "trigger"],
+        )?;
+
+        registry.register(Box::new(namespace_info_gauge.clone()))?;
+        registry.register(Box::new(namespace_triggers_gauge.clone()))?;
+        registry.register(Box::new(trigger_enabled_gauge.clone()))?;
+        registry.register(Box::new(trigger_last_run_gauge.clone()))?;
+        registry.register(Box::new(trigger_next_run_gauge.clone()))?;
+
+        let result = Self {
+            namespace_info_gauge,
+            namespace_triggers_gauge,
+            trigger_enabled_gauge,
+            trigger_last_run_gauge,
+            trigger_next_run_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl FunctionStoreImpl {
+    fn save_namespaces(&self, namespaces: Vec<BasicFunctionNamespaceInfo>) {
+        *self.store.write() = namespaces;
+    }
+}
+
+#[async_trait]
+impl FunctionStore for FunctionStoreImpl {
+    async fn load_namespaces(&self) -> anyhow::Result<()> {
+        let loaded = self.client.list_function_namespaces().await?;
+        let mut result: Vec<BasicFunctionNamespaceInfo> = loaded
+            .namespaces
+            .into_iter()
+            .map(BasicFunctionNamespaceInfo::from)
+            .collect();
+        // one request per namespace
+        for namespace in result.iter_mut() {
+            let loaded = self
+                .client
+                .list_function_triggers(namespace.namespace.clone())
+                .await?;
+            namespace.triggers = loaded
+                .triggers
+                .into_iter()
+                .map(BasicFunctionTriggerInfo::from)
+                .collect();
+        }
+        self.save_namespaces(result);
+        Ok(())
+    }
+
+    fn record_function_metrics(&self) {
+        let lock = self.store.read();
+        let mut valid_namespaces: HashSet<[&str; 3]> = HashSet::default();
+        let mut valid_triggers: HashSet<[&str; 4]> = HashSet::default();
+        for namespace in lock.iter() {
+            let info = [
+                namespace.namespace.as_str(),
+                namespace.label.as_str(),
+                namespace.region.as_str(),
+            ];
+            self.metrics
+                .namespace_info_gauge
+                .with_label_values(&info)
+                .set(1_f64);
+            valid_namespaces.insert(info);
+            self.metrics
+                .namespace_triggers_gauge
+                .with_label_values(&[namespace.namespace.as_str()])
+                .set(namespace.triggers.len() as f64);
+
+            for trigger in namespace.triggers.iter() {
+                let labels = [
+                    namespace.namespace.as_str(),
+                    trigger.name.as_str(),
+                    trigger.function.as_str(),
+                    trigger.trigger_type.as_str(),
+                ];
+                let enabled = if trigger.enabled { 1_f64 } else { 0_f64 };
+                self.metrics
+                    .trigger_enabled_gauge
+                    .with_label_values(&labels)
+                    .set(enabled);
+                valid_triggers.insert(labels);
+
+                let labels = [namespace.namespace.as_str(), trigger.name.as_str()];
+                if let Some(last_run_at) = trigger.last_run_at {
+                    self.metrics
+                        .trigger_last_run_gauge
+                        .with_label_values(&labels)
+                        .set(last_run_at.timestamp() as f64);
+                }
+                if let Some(next_run_at) = trigger.next_run_at {
+                    self.metrics
+                        .trigger_next_run_gauge
+                        .with_label_values(&labels)
+                        .set(next_run_at.timestamp() as f64);
+                }
+            }
+        }
+
+        // to prevent phantom namespaces and triggers
+        utils::retain_gauge_series(&self.metrics.namespace_info_gauge, |labels| {
+            let get = |name: &str| labels.get(name).copied().unwrap_or_default();
+            valid_namespaces.contains(&[get("namespace"), get("label"), get("region")])
+        });
+        let namespaces: HashSet<&str> = lock.iter().map(|x| x.namespace.as_str()).collect();
+        utils::retain_gauge_series(&self.metrics.namespace_triggers_gauge, |labels| {
+            let namespace = labels.get("namespace").copied().unwrap_or_default();
+            namespaces.contains(namespace)
+        });
+        utils::retain_gauge_series(&self.metrics.trigger_enabled_gauge, |labels| {
+            let get = |name: &str| labels.get(name).copied().unwrap_or_default();
+            valid_triggers.contains(&[
+                get("namespace"),
+                get("trigger"),
+                get("function"),
+                get("type"),
+            ])
+        });
+        let triggers: HashSet<(&str, &str)> = valid_triggers.iter().map(|x| (x[0], x[1])).collect();
+        let keep = |labels: &std::collections::HashMap<&str, &str>| {
+            let namespace = labels.get("namespace").copied().unwrap_or_default();
+            let trigger = labels.get("trigger").copied().unwrap_or_default();
+            triggers.contains(&(namespace, trigger))
+        };
+        utils::retain_gauge_series(&self.metrics.trigger_last_run_gauge, keep);
+        utils::retain_gauge_series(&self.metrics.trigger_next_run_gauge, keep);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{
+        FunctionScheduledRunsResponse, ListFunctionNamespacesResponse, ListFunctionTriggersResponse,
+    };
+    use prometheus::core::Collector;
+
+    fn namespace(id: &str, region: &str) -> FunctionNamespaceResponse {
+        FunctionNamespaceResponse {
+            namespace: id.to_string(),
+            label: format!("{id}-label"),
+            region: region.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_function_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_function_namespaces()
+            .times(1)
+            .returning(|| {
+                Ok(ListFunctionNamespacesResponse {
+                    namespaces: vec![namespace("fn-1", "nyc1"), namespace("fn-2", "fra1")],
+                })
+            });
+        mock_client
+            .expect_list_function_triggers()
+            .withf(|namespace| namespace == "fn-1")
+            .times(1)
+            .returning(|_| {
+                Ok(ListFunctionTriggersResponse {
+                    triggers: vec![
+                        FunctionTriggerResponse {
+                            name: "nightly".to_string(),
+                            function: "cleanup".to_string(),
+                            trigger_type: "SCHEDULED".to_string(),
+                            is_enabled: true,
+                            scheduled_runs: Some(FunctionScheduledRunsResponse {
+                                last_run_at: Some("2022-11-11T04:16:45Z".parse().unwrap()),
+                                next_run_at: Some("2022-11-12T04:16:45Z".parse().unwrap()),
+                            }),
+                        },
+                        FunctionTriggerResponse {
+                            name: "paused".to_string(),
+                            function: "cleanup".to_string(),
+                            trigger_type: "SCHEDULED".to_string(),
+                            is_enabled: false,
+                            scheduled_runs: None,
+                        },
+                    ],
+                })
+            });
+        mock_client
+            .expect_list_function_triggers()
+            .withf(|namespace| namespace == "fn-2")
+            .times(1)
+            .returning(|_| Ok(ListFunctionTriggersResponse { triggers: vec![] }));
+
+        let registry = prometheus::Registry::new();
+        let store = FunctionStoreImpl::new(Arc::new(mock_client), registry).unwrap();
+
+        store.load_namespaces().await.unwrap();
+        store.record_function_metrics();
+
+        let info = store
+            .metrics
+            .namespace_info_gauge
+            .with_label_values(&["fn-2", "fn-2-label", "fra1"])
+            .get();
+        assert_eq!(info, 1_f64);
+        let triggers = store
+            .metrics
+            .namespace_triggers_gauge
+            .with_label_values(&["fn-1"])
+            .get();
+        assert_eq!(triggers, 2_f64);
+        let enabled = store
+            .metrics
+            .trigger_enabled_gauge
+            .with_label_values(&["fn-1", "paused", "cleanup", "SCHEDULED"])
+            .get();
+        assert_eq!(enabled, 0_f64);
+        let last_run = store
+            .metrics
+            .trigger_last_run_gauge
+            .with_label_values(&["fn-1", "nightly"])
+            .get();
+        assert_eq!(last_run, 1668140205_f64);
+        // the paused trigger has never run
+        let series = store.metrics.trigger_last_run_gauge.collect();
+        assert_eq!(series[0].get_metric().len(), 1);
+
+        // the first namespace has been deleted
+        store.save_namespaces(vec![namespace("fn-2", "fra1").into()]);
+        store.record_function_metrics();
+
+        let series = store.metrics.trigger_enabled_gauge.collect();
+        assert!(series[0].get_metric().is_empty());
+        let series = store.metrics.namespace_info_gauge.collect();
+        assert_eq!(series[0].get_metric().len(), 1);
+    }
+}
@@ -0,0 +1,333 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::ImageResponse;
+use crate::metrics::droplet_store::DropletStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ImageStore: Send + Sync {
+    async fn load_images(&self) -> anyhow::Result<()>;
+
+    fn record_image_metrics(&self);
+
+    // true after the first successful load
+    fn is_loaded(&self) -> bool;
+}
+
+#[derive(Clone)]
+pub struct BasicImageInfo {
+    pub id: u64,
+    pub name: String,
+    pub distribution: String,
+    pub created_at: DateTime<Utc>,
+    pub regions: Vec<String>,
+    pub size_gigabytes: Option<f64>,
+    pub status: String,
+}
+
+impl From<ImageResponse> for BasicImageInfo {
+    fn from(value: ImageResponse) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            distribution: value.distribution,
+            created_at: value.created_at,
+            regions: value.regions,
+            size_gigabytes: value.size_gigabytes,
+            status: value.status,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ImageStoreImpl {
+    store: Arc<RwLock<Vec<BasicImageInfo>>>,
+    loaded: Arc<AtomicBool>,
+    client: Arc<dyn DigitalOceanClient>,
+    droplet_store: Arc<dyn DropletStore>,
+    metrics: ImageMetrics,
+}
+
+impl ImageStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        droplet_store: Arc<dyn DropletStore>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            loaded: Arc::new(AtomicBool::new(false)),
+            client,
+            droplet_store,
+            metrics: ImageMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct ImageMetrics {
+    info_gauge: prometheus::GaugeVec,
+    created_gauge: prometheus::GaugeVec,
+    size_gauge: prometheus::GaugeVec,
+    droplets_gauge: prometheus::GaugeVec,
+}
+
+impl ImageMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let labels = &["image_id", "image"];
+        let info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_image_info",
+                "Settings of custom image. Value is always 1.",
+            ),
+            &["image_id", "image", "distribution", "status", "regions"],
+        )?;
+        let created_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_image_created_timestamp_seconds",
+                "Creation time of the custom image in seconds since the epoch",
+            ),
+            labels,
+        )?;
+        let size_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_image_size_gigabytes",
+                "Billable size of the custom image in gigabytes",
+            ),
+            labels,
+        )?;
+        let droplets_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_image_droplets",
+                "Count of droplets running the custom image",
+            ),
+            labels,
+        )?;
+
+        registry.register(Box::new(info_gauge.clone()))?;
+        registry.register(Box::new(created_gauge.clone()))?;
+        registry.register(Box::new(size_gauge.clone()))?;
+        registry.register(Box::new(droplets_gauge.clone()))?;
+
+        let result = Self {
+            info_gauge,
+            created_gauge,
+            size_gauge,
+            droplets_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl ImageStoreImpl {
+    fn save_images(&self, images: Vec<BasicImageInfo>) {
+        *self.store.write() = images;
+        self.loaded.store(true, Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl ImageStore for ImageStoreImpl {
+    async fn load_images(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicImageInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_private_images(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.images.into_iter().map(BasicImageInfo::from));
+            page += 1;
+        }
+        self.save_images(result);
+        Ok(())
+    }
+
+    fn record_image_metrics(&self) {
+        let mut droplets: HashMap<u64, u64> = HashMap::default();
+        for image_id in self
+            .droplet_store
+            .list_droplets()
+            .into_iter()
+            .filter_map(|x| x.image_id)
+        {
+            *droplets.entry(image_id).or_default() += 1;
+        }
+
+        let lock = self.store.read();
+        let mut valid_series: HashSet<[String; 2]> = HashSet::default();
+        let mut valid_info: HashSet<[String; 5]> = HashSet::default();
+        for image in lock.iter() {
+            let id = image.id.to_string();
+            let labels = [id.as_str(), image.name.as_str()];
+            let info = [
+                id.clone(),
+                image.name.clone(),
+                image.distribution.clone(),
+                image.status.clone(),
+                image.regions.join(","),
+            ];
+            self.metrics.info_gauge.with_label_values(&info).set(1_f64);
+            self.metrics
+                .created_gauge
+                .with_label_values(&labels)
+                .set(image.created_at.timestamp() as f64);
+            // unknown until the import is finished
+            match image.size_gigabytes {
+                Some(size) => self.metrics.size_gauge.with_label_values(&labels).set(size),
+                None => {
+                    let _ = self.metrics.size_gauge.remove_label_values(&labels);
+                }
+            }
+            let count = droplets.get(&image.id).copied().unwrap_or_default();
+            self.metrics
+                .droplets_gauge
+                .with_label_values(&labels)
+                .set(count as f64);
+            valid_info.insert(info);
+            valid_series.insert([id.clone(), image.name.clone()]);
+        }
+
+        // to prevent phantom and renamed images
+        let keep = |labels: &std::collections::HashMap<&str, &str>| {
+            let get = |name: &str| labels.get(name).copied().unwrap_or_default().to_string();
+            valid_series.contains(&[get("image_id"), get("image")])
+        };
+        utils::retain_gauge_series(&self.metrics.created_gauge, keep);
+        utils::retain_gauge_series(&self.metrics.size_gauge, keep);
+        utils::retain_gauge_series(&self.metrics.droplets_gauge, keep);
+        utils::retain_gauge_series(&self.metrics.info_gauge, |labels| {
+            let get = |name: &str| labels.get(name).copied().unwrap_or_default().to_string();
+            valid_info.contains(&[
+                get("image_id"),
+                get("image"),
+                get("distribution"),
+                get("status"),
+                get("regions"),
+            ])
+        });
+    }
+
+    fn is_loaded(&self) -> bool {
+        self.loaded.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{Links, ListImagesResponse};
+    use crate::metrics::droplet_store::{BasicDropletInfo, MockDropletStore};
+    use prometheus::core::Collector;
+
+    fn image(id: u64, name: &str, size_gigabytes: Option<f64>) -> ImageResponse {
+        ImageResponse {
+            id,
+            name: name.to_string(),
+            distribution: "Ubuntu".to_string(),
+            created_at: "2024-05-02T10:00:00Z".parse().unwrap(),
+            regions: vec!["nyc3".to_string(), "fra1".to_string()],
+            size_gigabytes,
+            status: "available".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_image_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_private_images()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListImagesResponse {
+                    images: vec![
+                        image(1, "golden-2024-05", Some(2.34)),
+                        image(2, "golden-2024-06", None),
+                    ],
+                    links: Links::default(),
+                })
+            });
+
+        let mut mock_droplet_store = MockDropletStore::new();
+        mock_droplet_store.expect_list_droplets().returning(|| {
+            let droplet = |id: u64, image_id: Option<u64>| BasicDropletInfo {
+                id,
+                name: format!("web-{id}"),
+                image_id,
+                ..Default::default()
+            };
+            vec![
+                droplet(1, Some(1)),
+                droplet(2, Some(1)),
+                // public distribution image, not in the list
+                droplet(3, Some(119383150)),
+                droplet(4, None),
+            ]
+        });
+
+        let registry = prometheus::Registry::new();
+        let store = ImageStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_droplet_store),
+            registry,
+        )
+        .unwrap();
+
+        store.load_images().await.unwrap();
+        store.record_image_metrics();
+
+        let droplets = store
+            .metrics
+            .droplets_gauge
+            .with_label_values(&["1", "golden-2024-05"])
+            .get();
+        assert_eq!(droplets, 2_f64);
+        let droplets = store
+            .metrics
+            .droplets_gauge
+            .with_label_values(&["2", "golden-2024-06"])
+            .get();
+        assert_eq!(droplets, 0_f64);
+        let info = store
+            .metrics
+            .info_gauge
+            .with_label_values(&["1", "golden-2024-05", "Ubuntu", "available", "nyc3,fra1"])
+            .get();
+        assert_eq!(info, 1_f64);
+        let created = store
+            .metrics
+            .created_gauge
+            .with_label_values(&["1", "golden-2024-05"])
+            .get();
+        assert_eq!(created, 1714644000_f64);
+        // the size of the pending image is unknown yet
+        let sizes = store.metrics.size_gauge.collect();
+        assert_eq!(sizes[0].get_metric().len(), 1);
+
+        // the first image has been deleted
+        store.save_images(vec![image(2, "golden-2024-06", None).into()]);
+        store.record_image_metrics();
+
+        let series = store.metrics.droplets_gauge.collect();
+        assert_eq!(series[0].get_metric().len(), 1);
+        let series = store.metrics.info_gauge.collect();
+        assert_eq!(series[0].get_metric().len(), 1);
+
+        // the size is unknown again, e.g. the image is being re-imported
+        store.save_images(vec![image(2, "golden-2024-06", Some(1.5)).into()]);
+        store.record_image_metrics();
+        store.save_images(vec![image(2, "golden-2024-06", None).into()]);
+        store.record_image_metrics();
+        let sizes = store.metrics.size_gauge.collect();
+        assert!(sizes[0].get_metric().is_empty());
+    }
+}
@@ -1,10 +1,31 @@
 use crate::config::config_model::{AgentMetricsType, AppSettings};
+use crate::metrics::action_store::ActionStore;
 use crate::metrics::agent_metrics::AgentMetricsService;
+use crate::metrics::alert_policy_store::AlertPolicyStore;
+use crate::metrics::app_deployment_store::AppDeploymentStore;
 use crate::metrics::app_metrics_loader::AppMetricsService;
 use crate::metrics::app_store::AppStore;
+use crate::metrics::autoscale_store::AutoscaleStore;
+use crate::metrics::cdn_store::CdnStore;
+use crate::metrics::certificate_store::CertificateStore;
+use crate::metrics::database_event_store::DatabaseEventStore;
+use crate::metrics::database_store::DatabaseStore;
+use crate::metrics::domain_store::DomainStore;
 use crate::metrics::droplet_metrics_loader::DropletMetricsService;
+use crate::metrics::droplet_neighbor_store::DropletNeighborStore;
 use crate::metrics::droplet_store::DropletStore;
+use crate::metrics::firewall_store::FirewallStore;
+use crate::metrics::function_store::FunctionStore;
+use crate::metrics::image_store::ImageStore;
+use crate::metrics::project_store::ProjectStore;
+use crate::metrics::reserved_ip_store::ReservedIpStore;
+use crate::metrics::security_store::SecurityStore;
+use crate::metrics::size_store::SizeStore;
+use crate::metrics::snapshot_store::SnapshotStore;
+use crate::metrics::spaces_store::SpacesStore;
 use crate::metrics::utils::DROXPORTER_DEFAULT_BUCKETS;
+use crate::metrics::vpc_store::VpcStore;
+use crate::metrics::waste_store::WasteStore;
 use async_trait::async_trait;
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use prometheus::{HistogramOpts, Opts, Registry};
@@ -27,6 +48,28 @@ pub trait MetricsScheduler: Send + Sync {
     async fn run_app_cpu_percentage_metrics_loading(&self) -> anyhow::Result<()>;
     async fn run_app_memory_percentage_metrics_loading(&self) -> anyhow::Result<()>;
     async fn run_app_restart_count_metrics_loading(&self) -> anyhow::Result<()>;
+    async fn run_app_bandwidth_daily_metrics_loading(&self) -> anyhow::Result<()>;
+    async fn run_certificates_loading(&self) -> anyhow::Result<()>;
+    async fn run_reserved_ips_loading(&self) -> anyhow::Result<()>;
+    async fn run_snapshots_loading(&self) -> anyhow::Result<()>;
+    async fn run_projects_loading(&self) -> anyhow::Result<()>;
+    async fn run_autoscale_loading(&self) -> anyhow::Result<()>;
+    async fn run_firewalls_loading(&self) -> anyhow::Result<()>;
+    async fn run_vpcs_loading(&self) -> anyhow::Result<()>;
+    async fn run_app_deployments_loading(&self) -> anyhow::Result<()>;
+    async fn run_actions_loading(&self) -> anyhow::Result<()>;
+    async fn run_droplet_neighbors_loading(&self) -> anyhow::Result<()>;
+    async fn run_alert_policies_loading(&self) -> anyhow::Result<()>;
+    async fn run_databases_loading(&self) -> anyhow::Result<()>;
+    async fn run_database_events_loading(&self) -> anyhow::Result<()>;
+    async fn run_domains_loading(&self) -> anyhow::Result<()>;
+    async fn run_cdn_loading(&self) -> anyhow::Result<()>;
+    async fn run_spaces_loading(&self) -> anyhow::Result<()>;
+    async fn run_sizes_loading(&self) -> anyhow::Result<()>;
+    async fn run_images_loading(&self) -> anyhow::Result<()>;
+    async fn run_functions_loading(&self) -> anyhow::Result<()>;
+    async fn run_security_loading(&self) -> anyhow::Result<()>;
+    async fn run_waste_loading(&self) -> anyhow::Result<()>;
 }
 
 pub struct MetricsSchedulerImpl {
@@ -36,12 +79,34 @@ pub struct MetricsSchedulerImpl {
     droplet_metrics_service: Arc<dyn DropletMetricsService>,
     app_metrics_service: Arc<dyn AppMetricsService>,
     agent_service: Arc<dyn AgentMetricsService>,
+    certificate_store: Arc<dyn CertificateStore>,
+    reserved_ip_store: Arc<dyn ReservedIpStore>,
+    snapshot_store: Arc<dyn SnapshotStore>,
+    project_store: Arc<dyn ProjectStore>,
+    autoscale_store: Arc<dyn AutoscaleStore>,
+    firewall_store: Arc<dyn FirewallStore>,
+    vpc_store: Arc<dyn VpcStore>,
+    app_deployment_store: Arc<dyn AppDeploymentStore>,
+    action_store: Arc<dyn ActionStore>,
+    droplet_neighbor_store: Arc<dyn DropletNeighborStore>,
+    alert_policy_store: Arc<dyn AlertPolicyStore>,
+    database_store: Arc<dyn DatabaseStore>,
+    database_event_store: Arc<dyn DatabaseEventStore>,
+    domain_store: Arc<dyn DomainStore>,
+    cdn_store: Arc<dyn CdnStore>,
+    spaces_store: Arc<dyn SpacesStore>,
+    size_store: Arc<dyn SizeStore>,
+    image_store: Arc<dyn ImageStore>,
+    function_store: Arc<dyn FunctionStore>,
+    security_store: Arc<dyn SecurityStore>,
+    waste_store: Arc<dyn WasteStore>,
 
     jobs_counter: prometheus::CounterVec,
     jobs_histogram: prometheus::HistogramVec,
 }
 
 impl MetricsSchedulerImpl {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         configs: &'static AppSettings,
         droplet_store: Arc<dyn DropletStore>,
@@ -49,6 +114,27 @@ impl MetricsSchedulerImpl {
         droplet_metrics_service: Arc<dyn DropletMetricsService>,
         app_metrics_service: Arc<dyn AppMetricsService>,
         agent_service: Arc<dyn AgentMetricsService>,
+        certificate_store: Arc<dyn CertificateStore>,
+        reserved_ip_store: Arc<dyn ReservedIpStore>,
+        snapshot_store: Arc<dyn SnapshotStore>,
+        project_store: Arc<dyn ProjectStore>,
+        autoscale_store: Arc<dyn AutoscaleStore>,
+        firewall_store: Arc<dyn FirewallStore>,
+        vpc_store: Arc<dyn VpcStore>,
+        app_deployment_store: Arc<dyn AppDeploymentStore>,
+        action_store: Arc<dyn ActionStore>,
+        droplet_neighbor_store: Arc<dyn DropletNeighborStore>,
+        alert_policy_store: Arc<dyn AlertPolicyStore>,
+        database_store: Arc<dyn DatabaseStore>,
+        database_event_store: Arc<dyn DatabaseEventStore>,
+        domain_store: Arc<dyn DomainStore>,
+        cdn_store: Arc<dyn CdnStore>,
+        spaces_store: Arc<dyn SpacesStore>,
+        size_store: Arc<dyn SizeStore>,
+        image_store: Arc<dyn ImageStore>,
+        function_store: Arc<dyn FunctionStore>,
+        security_store: Arc<dyn SecurityStore>,
+        waste_store: Arc<dyn WasteStore>,
         registry: Registry,
     ) -> anyhow::Result<Self> {
         let jobs_counter = prometheus::CounterVec::new(
@@ -73,6 +159,27 @@ impl MetricsSchedulerImpl {
             droplet_metrics_service,
             app_metrics_service,
             agent_service,
+            certificate_store,
+            reserved_ip_store,
+            snapshot_store,
+            project_store,
+            autoscale_store,
+            firewall_store,
+            vpc_store,
+            app_deployment_store,
+            action_store,
+            droplet_neighbor_store,
+            alert_policy_store,
+            database_store,
+            database_event_store,
+            domain_store,
+            cdn_store,
+            spaces_store,
+            size_store,
+            image_store,
+            function_store,
+            security_store,
+            waste_store,
             jobs_counter,
             jobs_histogram,
         };
@@ -125,6 +232,17 @@ impl MetricsScheduler for MetricsSchedulerImpl {
                 continue;
             }
             self.droplet_store.record_droplets_metrics();
+            // firewalls, VPCs and images are reloaded much less often,
+            // but their droplet counts have to follow the droplets
+            if self.configs.firewalls.enabled && self.firewall_store.is_loaded() {
+                self.firewall_store.record_firewall_metrics();
+            }
+            if self.configs.vpcs.enabled && self.vpc_store.is_loaded() {
+                self.vpc_store.record_vpc_metrics();
+            }
+            if self.configs.images.enabled && self.image_store.is_loaded() {
+                self.image_store.record_image_metrics();
+            }
 
             self.record_job_metrics("droplet_loading", true, start)
         }
@@ -448,4 +566,653 @@ impl MetricsScheduler for MetricsSchedulerImpl {
         }
         Ok(())
     }
+
+    async fn run_app_bandwidth_daily_metrics_loading(&self) -> anyhow::Result<()> {
+        if let Some(app_bandwidth_daily) = self.configs.app_metrics.bandwidth_daily.as_ref() {
+            if !app_bandwidth_daily.enabled {
+                info!("Apps app_bandwidth_daily metrics are disabled");
+                return Ok(());
+            }
+            info!("Starting Apps app_bandwidth_daily metrics loading loop");
+
+            // timeout for initial load
+            let first_delay = Duration::from_secs(10).min(app_bandwidth_daily.interval);
+            let mut first = true;
+            loop {
+                let timeout = if first {
+                    first_delay
+                } else {
+                    app_bandwidth_daily.interval
+                };
+                first = false;
+                tokio::time::sleep(timeout).await;
+                let start = Instant::now();
+
+                if let Err(e) = self.app_metrics_service.load_bandwidth_daily().await {
+                    error!("Apps app_bandwidth_daily metrics loading failed with err {e}");
+                    self.record_job_metrics("app_bandwidth_daily", false, start);
+                    continue;
+                }
+                self.record_job_metrics("app_bandwidth_daily", true, start);
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_certificates_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.certificates.enabled {
+            info!("Certificates loading is disabled");
+            return Ok(());
+        }
+        info!("Starting certificates loading loop");
+
+        let mut first = true;
+        loop {
+            if !first {
+                tokio::time::sleep(self.configs.certificates.interval).await;
+            }
+            first = false;
+            let start = Instant::now();
+
+            if let Err(e) = self.certificate_store.load_certificates().await {
+                error!("Certificates loading failed with err {e}");
+                self.record_job_metrics("certificate_loading", false, start);
+                continue;
+            }
+            self.certificate_store.record_certificate_metrics();
+
+            self.record_job_metrics("certificate_loading", true, start)
+        }
+    }
+
+    async fn run_reserved_ips_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.reserved_ips.enabled {
+            info!("Reserved IPs loading is disabled");
+            return Ok(());
+        }
+        info!("Starting reserved IPs loading loop");
+
+        // droplet names are resolved from the droplet store, so give it a chance to load first
+        // looks ugly, but simple =)
+        let first_delay = Duration::from_secs(10).min(self.configs.reserved_ips.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.reserved_ips.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.reserved_ip_store.load_reserved_ips().await {
+                error!("Reserved IPs loading failed with err {e}");
+                self.record_job_metrics("reserved_ip_loading", false, start);
+                continue;
+            }
+            self.reserved_ip_store.record_reserved_ip_metrics();
+
+            self.record_job_metrics("reserved_ip_loading", true, start)
+        }
+    }
+
+    async fn run_snapshots_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.snapshots.enabled {
+            info!("Snapshots loading is disabled");
+            return Ok(());
+        }
+        info!("Starting snapshots loading loop");
+
+        // droplet names are resolved from the droplet store, so give it a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.snapshots.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.snapshots.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.snapshot_store.load_snapshots().await {
+                error!("Snapshots loading failed with err {e}");
+                self.record_job_metrics("snapshot_loading", false, start);
+                continue;
+            }
+            self.snapshot_store.record_snapshot_metrics();
+
+            self.record_job_metrics("snapshot_loading", true, start)
+        }
+    }
+
+    async fn run_projects_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.projects.enabled {
+            info!("Projects loading is disabled");
+            return Ok(());
+        }
+        info!("Starting projects loading loop");
+
+        let mut first = true;
+        loop {
+            if !first {
+                tokio::time::sleep(self.configs.projects.interval).await;
+            }
+            first = false;
+            let start = Instant::now();
+
+            if let Err(e) = self.project_store.load_projects().await {
+                error!("Projects loading failed with err {e}");
+                self.record_job_metrics("project_loading", false, start);
+                continue;
+            }
+            self.project_store.record_project_metrics();
+            // droplets and apps have their own intervals, so refresh the project label right away
+            if self.configs.projects.label {
+                self.droplet_store.record_droplets_metrics();
+                self.app_store.record_app_metrics();
+            }
+
+            self.record_job_metrics("project_loading", true, start)
+        }
+    }
+
+    async fn run_autoscale_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.autoscale.enabled {
+            info!("Autoscale pools loading is disabled");
+            return Ok(());
+        }
+        info!("Starting autoscale pools loading loop");
+
+        // droplet names are resolved from the droplet store, so give it a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.autoscale.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.autoscale.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.autoscale_store.load_autoscale_pools().await {
+                error!("Autoscale pools loading failed with err {e}");
+                self.record_job_metrics("autoscale_loading", false, start);
+                continue;
+            }
+            self.autoscale_store.record_autoscale_metrics();
+
+            self.record_job_metrics("autoscale_loading", true, start)
+        }
+    }
+
+    async fn run_firewalls_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.firewalls.enabled {
+            info!("Firewalls loading is disabled");
+            return Ok(());
+        }
+        info!("Starting firewalls loading loop");
+
+        // firewall coverage is calculated for droplets from the droplet store, so give it a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.firewalls.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.firewalls.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.firewall_store.load_firewalls().await {
+                error!("Firewalls loading failed with err {e}");
+                self.record_job_metrics("firewall_loading", false, start);
+                continue;
+            }
+            self.firewall_store.record_firewall_metrics();
+
+            self.record_job_metrics("firewall_loading", true, start)
+        }
+    }
+
+    async fn run_vpcs_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.vpcs.enabled {
+            info!("VPCs loading is disabled");
+            return Ok(());
+        }
+        info!("Starting VPCs loading loop");
+
+        // droplets are counted from the droplet store, so give it a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.vpcs.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.vpcs.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.vpc_store.load_vpcs().await {
+                error!("VPCs loading failed with err {e}");
+                self.record_job_metrics("vpc_loading", false, start);
+                continue;
+            }
+            self.vpc_store.record_vpc_metrics();
+
+            self.record_job_metrics("vpc_loading", true, start)
+        }
+    }
+
+    async fn run_app_deployments_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.app_deployments.enabled {
+            info!("App deployments loading is disabled");
+            return Ok(());
+        }
+        info!("Starting app deployments loading loop");
+
+        // deployments are loaded for the apps from the app store, so give it a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.app_deployments.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.app_deployments.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.app_deployment_store.load_deployments().await {
+                error!("App deployments loading failed with err {e}");
+                self.record_job_metrics("app_deployment_loading", false, start);
+                continue;
+            }
+            self.app_deployment_store.record_deployment_metrics();
+
+            self.record_job_metrics("app_deployment_loading", true, start)
+        }
+    }
+
+    async fn run_actions_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.actions.enabled {
+            info!("Actions loading is disabled");
+            return Ok(());
+        }
+        info!("Starting actions loading loop");
+
+        let mut first = true;
+        loop {
+            if !first {
+                tokio::time::sleep(self.configs.actions.interval).await;
+            }
+            first = false;
+            let start = Instant::now();
+
+            if let Err(e) = self.action_store.load_actions().await {
+                error!("Actions loading failed with err {e}");
+                self.record_job_metrics("action_loading", false, start);
+                continue;
+            }
+            self.action_store.record_action_metrics();
+
+            self.record_job_metrics("action_loading", true, start)
+        }
+    }
+
+    async fn run_droplet_neighbors_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.droplet_neighbors.enabled {
+            info!("Droplet neighbors loading is disabled");
+            return Ok(());
+        }
+        info!("Starting droplet neighbors loading loop");
+
+        // droplet names are taken from the droplet store, so give it a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.droplet_neighbors.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.droplet_neighbors.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.droplet_neighbor_store.load_neighbors().await {
+                error!("Droplet neighbors loading failed with err {e}");
+                self.record_job_metrics("droplet_neighbor_loading", false, start);
+                continue;
+            }
+            self.droplet_neighbor_store.record_neighbor_metrics();
+
+            self.record_job_metrics("droplet_neighbor_loading", true, start)
+        }
+    }
+
+    async fn run_alert_policies_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.alert_policies.enabled {
+            info!("Alert policies loading is disabled");
+            return Ok(());
+        }
+        info!("Starting alert policies loading loop");
+
+        let mut first = true;
+        loop {
+            if !first {
+                tokio::time::sleep(self.configs.alert_policies.interval).await;
+            }
+            first = false;
+            let start = Instant::now();
+
+            if let Err(e) = self.alert_policy_store.load_policies().await {
+                error!("Alert policies loading failed with err {e}");
+                self.record_job_metrics("alert_policy_loading", false, start);
+                continue;
+            }
+            self.alert_policy_store.record_policy_metrics();
+
+            self.record_job_metrics("alert_policy_loading", true, start)
+        }
+    }
+
+    async fn run_databases_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.databases.enabled {
+            info!("Databases loading is disabled");
+            return Ok(());
+        }
+        info!("Starting databases loading loop");
+
+        let mut first = true;
+        loop {
+            if !first {
+                tokio::time::sleep(self.configs.databases.interval).await;
+            }
+            first = false;
+            let start = Instant::now();
+
+            if let Err(e) = self.database_store.load_databases().await {
+                error!("Databases loading failed with err {e}");
+                self.record_job_metrics("database_loading", false, start);
+                continue;
+            }
+            self.database_store.record_database_metrics();
+
+            self.record_job_metrics("database_loading", true, start)
+        }
+    }
+
+    async fn run_database_events_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.database_events.enabled {
+            info!("Database events loading is disabled");
+            return Ok(());
+        }
+        info!("Starting database events loading loop");
+
+        // events are loaded for the clusters from the database store, so give it a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.database_events.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.database_events.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.database_event_store.load_events().await {
+                error!("Database events loading failed with err {e}");
+                self.record_job_metrics("database_event_loading", false, start);
+                continue;
+            }
+            self.database_event_store.record_event_metrics();
+
+            self.record_job_metrics("database_event_loading", true, start)
+        }
+    }
+
+    async fn run_domains_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.domains.enabled {
+            info!("Domains loading is disabled");
+            return Ok(());
+        }
+        info!("Starting domains loading loop");
+
+        // records are checked against the droplet and reserved IP stores, so give them a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.domains.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.domains.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.domain_store.load_domains().await {
+                error!("Domains loading failed with err {e}");
+                self.record_job_metrics("domain_loading", false, start);
+                continue;
+            }
+            self.domain_store.record_domain_metrics();
+
+            self.record_job_metrics("domain_loading", true, start)
+        }
+    }
+
+    async fn run_cdn_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.cdn.enabled {
+            info!("CDN loading is disabled");
+            return Ok(());
+        }
+        info!("Starting CDN loading loop");
+
+        // certificate names are taken from the certificate store, so give it a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.cdn.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.cdn.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.cdn_store.load_endpoints().await {
+                error!("CDN loading failed with err {e}");
+                self.record_job_metrics("cdn_loading", false, start);
+                continue;
+            }
+            self.cdn_store.record_endpoint_metrics();
+
+            self.record_job_metrics("cdn_loading", true, start)
+        }
+    }
+
+    async fn run_spaces_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.spaces.enabled {
+            info!("Spaces loading is disabled");
+            return Ok(());
+        }
+        info!("Starting Spaces loading loop");
+
+        let mut first = true;
+        loop {
+            if !first {
+                tokio::time::sleep(self.configs.spaces.interval).await;
+            }
+            first = false;
+            let start = Instant::now();
+
+            if let Err(e) = self.spaces_store.load_buckets().await {
+                error!("Spaces loading failed with err {e}");
+                self.record_job_metrics("spaces_loading", false, start);
+                continue;
+            }
+            self.spaces_store.record_bucket_metrics();
+
+            self.record_job_metrics("spaces_loading", true, start)
+        }
+    }
+
+    async fn run_sizes_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.sizes.enabled {
+            info!("Sizes loading is disabled");
+            return Ok(());
+        }
+        info!("Starting sizes loading loop");
+
+        let mut first = true;
+        loop {
+            if !first {
+                tokio::time::sleep(self.configs.sizes.interval).await;
+            }
+            first = false;
+            let start = Instant::now();
+
+            if let Err(e) = self.size_store.load_sizes().await {
+                error!("Sizes loading failed with err {e}");
+                self.record_job_metrics("size_loading", false, start);
+                continue;
+            }
+            self.size_store.record_size_metrics();
+
+            self.record_job_metrics("size_loading", true, start)
+        }
+    }
+
+    async fn run_images_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.images.enabled {
+            info!("Images loading is disabled");
+            return Ok(());
+        }
+        info!("Starting images loading loop");
+
+        // droplet counts are taken from the droplet store, so give it a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.images.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.images.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.image_store.load_images().await {
+                error!("Images loading failed with err {e}");
+                self.record_job_metrics("image_loading", false, start);
+                continue;
+            }
+            self.image_store.record_image_metrics();
+
+            self.record_job_metrics("image_loading", true, start)
+        }
+    }
+
+    async fn run_functions_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.functions.enabled {
+            info!("Functions loading is disabled");
+            return Ok(());
+        }
+        info!("Starting functions loading loop");
+
+        let mut first = true;
+        loop {
+            if !first {
+                tokio::time::sleep(self.configs.functions.interval).await;
+            }
+            first = false;
+            let start = Instant::now();
+
+            if let Err(e) = self.function_store.load_namespaces().await {
+                error!("Functions loading failed with err {e}");
+                self.record_job_metrics("function_loading", false, start);
+                continue;
+            }
+            self.function_store.record_function_metrics();
+
+            self.record_job_metrics("function_loading", true, start)
+        }
+    }
+
+    async fn run_security_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.security.enabled {
+            info!("Security loading is disabled");
+            return Ok(());
+        }
+        info!("Starting security loading loop");
+
+        // findings are built from the other stores, so give them a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.security.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.security.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.security_store.load_security().await {
+                error!("Security loading failed with err {e}");
+                self.record_job_metrics("security_loading", false, start);
+                continue;
+            }
+            self.security_store.record_security_metrics();
+
+            self.record_job_metrics("security_loading", true, start)
+        }
+    }
+
+    async fn run_waste_loading(&self) -> anyhow::Result<()> {
+        if !self.configs.waste.enabled {
+            info!("Waste loading is disabled");
+            return Ok(());
+        }
+        info!("Starting waste loading loop");
+
+        // the report is built from the other stores, so give them a chance to load first
+        let first_delay = Duration::from_secs(10).min(self.configs.waste.interval);
+        let mut first = true;
+        loop {
+            let timeout = if first {
+                first_delay
+            } else {
+                self.configs.waste.interval
+            };
+            first = false;
+            tokio::time::sleep(timeout).await;
+            let start = Instant::now();
+
+            if let Err(e) = self.waste_store.load_waste().await {
+                error!("Waste loading failed with err {e}");
+                self.record_job_metrics("waste_loading", false, start);
+                continue;
+            }
+            self.waste_store.record_waste_metrics();
+
+            self.record_job_metrics("waste_loading", true, start)
+        }
+    }
 }
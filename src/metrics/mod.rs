@@ -1,7 +1,28 @@
+pub mod action_store;
 pub mod agent_metrics;
+pub mod alert_policy_store;
+pub mod app_deployment_store;
 pub mod app_metrics_loader;
 pub mod app_store;
+pub mod autoscale_store;
+pub mod cdn_store;
+pub mod certificate_store;
+pub mod database_event_store;
+pub mod database_store;
+pub mod domain_store;
 pub mod droplet_metrics_loader;
+pub mod droplet_neighbor_store;
 pub mod droplet_store;
+pub mod firewall_store;
+pub mod function_store;
+pub mod image_store;
 pub mod jobs_scheduler;
+pub mod project_store;
+pub mod reserved_ip_store;
+pub mod security_store;
+pub mod size_store;
+pub mod snapshot_store;
+pub mod spaces_store;
 pub mod utils;
+pub mod vpc_store;
+pub mod waste_store;
@@ -0,0 +1,243 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ProjectStore: Send + Sync {
+    async fn load_projects(&self) -> anyhow::Result<()>;
+
+    fn record_project_metrics(&self);
+
+    // returns the project's name for the resource URN, e.g. `do:droplet:13457723`
+    fn get_project(&self, urn: &str) -> Option<String>;
+}
+
+#[derive(Clone)]
+pub struct ProjectStoreImpl {
+    // urn -> project name
+    store: Arc<RwLock<HashMap<String, String>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    metrics: ProjectMetrics,
+}
+
+impl ProjectStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(HashMap::default())),
+            client,
+            metrics: ProjectMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct ProjectMetrics {
+    resources_gauge: prometheus::GaugeVec,
+}
+
+impl ProjectMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let resources_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_project_resources",
+                "Count of resources assigned to the project",
+            ),
+            &["project", "resource_type"],
+        )?;
+
+        registry.register(Box::new(resources_gauge.clone()))?;
+
+        let result = Self { resources_gauge };
+        Ok(result)
+    }
+}
+
+impl ProjectStoreImpl {
+    fn save_projects(&self, projects: HashMap<String, String>) {
+        *self.store.write() = projects;
+    }
+
+    async fn load_project_resources(&self, project_id: &str) -> anyhow::Result<Vec<String>> {
+        let mut result: Vec<String> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self
+                .client
+                .list_project_resources(project_id.to_string(), per_page, page)
+                .await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.resources.into_iter().map(|x| x.urn));
+            page += 1;
+        }
+        Ok(result)
+    }
+}
+
+// do:droplet:13457723 -> droplet
+fn resource_type(urn: &str) -> &str {
+    urn.split(':').nth(1).unwrap_or_default()
+}
+
+#[async_trait]
+impl ProjectStore for ProjectStoreImpl {
+    async fn load_projects(&self) -> anyhow::Result<()> {
+        let mut projects = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_projects(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            projects.extend(loaded.projects);
+            page += 1;
+        }
+
+        let mut result: HashMap<String, String> = HashMap::default();
+        for project in projects {
+            for urn in self.load_project_resources(project.id.as_str()).await? {
+                result.insert(urn, project.name.clone());
+            }
+        }
+        self.save_projects(result);
+        Ok(())
+    }
+
+    fn record_project_metrics(&self) {
+        let lock = self.store.read();
+
+        let mut counts: HashMap<(&str, &str), u64> = HashMap::default();
+        for (urn, project) in lock.iter() {
+            *counts
+                .entry((project.as_str(), resource_type(urn)))
+                .or_default() += 1;
+        }
+
+        for ((project, resource_type), count) in counts.iter() {
+            self.metrics
+                .resources_gauge
+                .with_label_values(&[project, resource_type])
+                .set(*count as f64);
+        }
+
+        // to prevent phantom projects and resource types
+        let valid_series: HashSet<&(&str, &str)> = counts.keys().collect();
+        utils::retain_gauge_series(&self.metrics.resources_gauge, |labels| {
+            let key = (
+                labels.get("project").copied().unwrap_or_default(),
+                labels.get("resource_type").copied().unwrap_or_default(),
+            );
+            valid_series.contains(&key)
+        });
+    }
+
+    fn get_project(&self, urn: &str) -> Option<String> {
+        self.store.read().get(urn).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{
+        Links, ListProjectResourcesResponse, ListProjectsResponse, ProjectResourceResponse,
+        ProjectResponse,
+    };
+
+    fn project(id: &str, name: &str) -> ProjectResponse {
+        ProjectResponse {
+            id: id.to_string(),
+            name: name.to_string(),
+            is_default: false,
+        }
+    }
+
+    fn resources(urns: &[&str]) -> ListProjectResourcesResponse {
+        ListProjectResourcesResponse {
+            resources: urns
+                .iter()
+                .map(|urn| ProjectResourceResponse {
+                    urn: urn.to_string(),
+                })
+                .collect(),
+            links: Links::default(),
+        }
+    }
+
+    fn create_loaded_store() -> ProjectStoreImpl {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        mock_client
+            .expect_list_projects()
+            .withf(|per_page, page| *per_page == 100 && *page == 1)
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListProjectsResponse {
+                    projects: vec![project("p1", "web"), project("p2", "empty")],
+                    links: Links::default(),
+                })
+            });
+        mock_client
+            .expect_list_project_resources()
+            .withf(|id, _, _| id == "p1")
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(resources(&[
+                    "do:droplet:123",
+                    "do:droplet:456",
+                    "do:app:app-123",
+                ]))
+            });
+        mock_client
+            .expect_list_project_resources()
+            .withf(|id, _, _| id == "p2")
+            .times(1)
+            .returning(|_, _, _| Ok(resources(&[])));
+
+        let registry = prometheus::Registry::new();
+        ProjectStoreImpl::new(Arc::new(mock_client), registry).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_load_projects() {
+        let store = create_loaded_store();
+
+        let result = store.load_projects().await;
+        assert!(result.is_ok());
+
+        assert_eq!(store.get_project("do:droplet:123"), Some("web".to_string()));
+        assert_eq!(store.get_project("do:app:app-123"), Some("web".to_string()));
+        assert_eq!(store.get_project("do:droplet:789"), None);
+    }
+
+    #[tokio::test]
+    async fn test_record_project_metrics() {
+        let store = create_loaded_store();
+        store.load_projects().await.unwrap();
+        store.record_project_metrics();
+
+        let droplets = store
+            .metrics
+            .resources_gauge
+            .with_label_values(&["web", "droplet"])
+            .get();
+        assert_eq!(droplets, 2_f64);
+        let apps = store
+            .metrics
+            .resources_gauge
+            .with_label_values(&["web", "app"])
+            .get();
+        assert_eq!(apps, 1_f64);
+    }
+}
@@ -0,0 +1,328 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::ReservedIpResponse;
+use crate::metrics::droplet_store::DropletStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ReservedIpStore: Send + Sync {
+    async fn load_reserved_ips(&self) -> anyhow::Result<()>;
+
+    fn record_reserved_ip_metrics(&self);
+
+    fn list_reserved_ips(&self) -> Vec<BasicReservedIpInfo>;
+
+    // true after the first successful load
+    fn is_loaded(&self) -> bool;
+}
+
+#[derive(Clone)]
+pub struct BasicReservedIpInfo {
+    pub ip: String,
+    pub region: String,
+    pub locked: bool,
+    pub droplet_id: Option<u64>,
+}
+
+impl From<ReservedIpResponse> for BasicReservedIpInfo {
+    fn from(value: ReservedIpResponse) -> Self {
+        Self {
+            ip: value.ip,
+            region: value.region.slug,
+            locked: value.locked,
+            droplet_id: value.droplet.map(|x| x.id),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ReservedIpStoreImpl {
+    store: Arc<RwLock<Vec<BasicReservedIpInfo>>>,
+    loaded: Arc<AtomicBool>,
+    client: Arc<dyn DigitalOceanClient>,
+    droplet_store: Arc<dyn DropletStore>,
+    metrics: ReservedIpMetrics,
+}
+
+impl ReservedIpStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        droplet_store: Arc<dyn DropletStore>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            loaded: Arc::new(AtomicBool::new(false)),
+            client,
+            droplet_store,
+            metrics: ReservedIpMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct ReservedIpMetrics {
+    info_gauge: prometheus::GaugeVec,
+    unassigned_gauge: prometheus::Gauge,
+}
+
+impl ReservedIpMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_reserved_ip_info",
+                "Reserved IP with its region, locked state and assigned droplet. Values is always 1.",
+            ),
+            &["ip", "region", "locked", "droplet"],
+        )?;
+        let unassigned_gauge = prometheus::Gauge::new(
+            "droxporter_reserved_ips_unassigned",
+            "Count of reserved IPs that are not assigned to any droplet",
+        )?;
+
+        registry.register(Box::new(info_gauge.clone()))?;
+        registry.register(Box::new(unassigned_gauge.clone()))?;
+
+        let result = Self {
+            info_gauge,
+            unassigned_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl ReservedIpStoreImpl {
+    fn save_reserved_ips(&self, reserved_ips: Vec<BasicReservedIpInfo>) {
+        *self.store.write() = reserved_ips;
+        self.loaded.store(true, Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl ReservedIpStore for ReservedIpStoreImpl {
+    async fn load_reserved_ips(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicReservedIpInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_reserved_ips(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(
+                loaded
+                    .reserved_ips
+                    .into_iter()
+                    .map(BasicReservedIpInfo::from),
+            );
+            page += 1;
+        }
+        self.save_reserved_ips(result);
+        Ok(())
+    }
+
+    fn record_reserved_ip_metrics(&self) {
+        let droplets: HashMap<u64, String> = self
+            .droplet_store
+            .list_droplets()
+            .into_iter()
+            .map(|x| (x.id, x.name))
+            .collect();
+
+        let lock = self.store.read();
+        let mut valid_series: HashSet<[String; 4]> = HashSet::default();
+        let mut unassigned = 0u64;
+        for reserved_ip in lock.iter() {
+            // droplets can be created after the last droplets loading, so we fall back to the id
+            let droplet = match reserved_ip.droplet_id {
+                None => {
+                    unassigned += 1;
+                    String::new()
+                }
+                Some(id) => droplets.get(&id).cloned().unwrap_or_else(|| id.to_string()),
+            };
+            let locked = if reserved_ip.locked { "true" } else { "false" };
+            let labels = [
+                reserved_ip.ip.clone(),
+                reserved_ip.region.clone(),
+                locked.to_string(),
+                droplet,
+            ];
+            self.metrics
+                .info_gauge
+                .with_label_values(&labels)
+                .set(1_f64);
+            valid_series.insert(labels);
+        }
+        self.metrics.unassigned_gauge.set(unassigned as f64);
+
+        // to prevent phantom ips, stale assignments and lock states
+        utils::retain_gauge_series(&self.metrics.info_gauge, |labels| {
+            let get = |name: &str| labels.get(name).copied().unwrap_or_default().to_string();
+            valid_series.contains(&[get("ip"), get("region"), get("locked"), get("droplet")])
+        });
+    }
+
+    fn list_reserved_ips(&self) -> Vec<BasicReservedIpInfo> {
+        self.store.read().clone()
+    }
+
+    fn is_loaded(&self) -> bool {
+        self.loaded.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{
+        Links, ListReservedIpsResponse, RegionSlugResponse, ResourceIdResponse,
+    };
+    use crate::metrics::droplet_store::{BasicDropletInfo, MockDropletStore};
+    use prometheus::core::Collector;
+
+    #[tokio::test]
+    async fn test_load_reserved_ips_single_page() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        let mock_store = MockDropletStore::new();
+
+        mock_client
+            .expect_list_reserved_ips()
+            .withf(|per_page, page| *per_page == 100 && *page == 1)
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListReservedIpsResponse {
+                    reserved_ips: vec![
+                        ReservedIpResponse {
+                            ip: "45.55.96.47".to_string(),
+                            region: RegionSlugResponse {
+                                slug: "nyc3".to_string(),
+                            },
+                            droplet: None,
+                            locked: false,
+                        },
+                        ReservedIpResponse {
+                            ip: "45.55.96.48".to_string(),
+                            region: RegionSlugResponse {
+                                slug: "fra1".to_string(),
+                            },
+                            droplet: Some(ResourceIdResponse { id: 123 }),
+                            locked: true,
+                        },
+                    ],
+                    links: Links::default(),
+                })
+            });
+
+        let registry = prometheus::Registry::new();
+        let store = ReservedIpStoreImpl::new(Arc::new(mock_client), Arc::new(mock_store), registry)
+            .unwrap();
+
+        let result = store.load_reserved_ips().await;
+        assert!(result.is_ok());
+
+        let reserved_ips = store.list_reserved_ips();
+        assert_eq!(reserved_ips.len(), 2);
+        assert_eq!(reserved_ips[0].ip, "45.55.96.47");
+        assert_eq!(reserved_ips[0].droplet_id, None);
+        assert_eq!(reserved_ips[1].region, "fra1");
+        assert_eq!(reserved_ips[1].droplet_id, Some(123));
+    }
+
+    #[tokio::test]
+    async fn test_record_reserved_ip_metrics() {
+        let mock_client = MockDigitalOceanClient::new();
+        let mut mock_store = MockDropletStore::new();
+
+        mock_store.expect_list_droplets().returning(|| {
+            vec![BasicDropletInfo {
+                id: 123,
+                name: "test-droplet".to_string(),
+                memory: 1024,
+                vcpus: 1,
+                disk: 25,
+                locked: false,
+                status: "active".to_string(),
+                ..Default::default()
+            }]
+        });
+
+        let registry = prometheus::Registry::new();
+        let store = ReservedIpStoreImpl::new(Arc::new(mock_client), Arc::new(mock_store), registry)
+            .unwrap();
+
+        store.save_reserved_ips(vec![
+            BasicReservedIpInfo {
+                ip: "45.55.96.47".to_string(),
+                region: "nyc3".to_string(),
+                locked: false,
+                droplet_id: None,
+            },
+            BasicReservedIpInfo {
+                ip: "45.55.96.48".to_string(),
+                region: "nyc3".to_string(),
+                locked: false,
+                droplet_id: Some(123),
+            },
+        ]);
+        store.record_reserved_ip_metrics();
+
+        assert_eq!(store.metrics.unassigned_gauge.get(), 1_f64);
+        let assigned = store
+            .metrics
+            .info_gauge
+            .with_label_values(&["45.55.96.48", "nyc3", "false", "test-droplet"])
+            .get();
+        assert_eq!(assigned, 1_f64);
+
+        // the ip has been released
+        store.save_reserved_ips(vec![BasicReservedIpInfo {
+            ip: "45.55.96.48".to_string(),
+            region: "nyc3".to_string(),
+            locked: false,
+            droplet_id: None,
+        }]);
+        store.record_reserved_ip_metrics();
+
+        let metrics: Vec<_> = store
+            .metrics
+            .info_gauge
+            .collect()
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .collect();
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(store.metrics.unassigned_gauge.get(), 1_f64);
+
+        // the ip has been locked
+        store.save_reserved_ips(vec![BasicReservedIpInfo {
+            ip: "45.55.96.48".to_string(),
+            region: "nyc3".to_string(),
+            locked: true,
+            droplet_id: None,
+        }]);
+        store.record_reserved_ip_metrics();
+
+        let metrics: Vec<_> = store
+            .metrics
+            .info_gauge
+            .collect()
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .collect();
+        assert_eq!(metrics.len(), 1);
+        let locked = store
+            .metrics
+            .info_gauge
+            .with_label_values(&["45.55.96.48", "nyc3", "true", ""])
+            .get();
+        assert_eq!(locked, 1_f64);
+    }
+}
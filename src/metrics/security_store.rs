@@ -0,0 +1,479 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::config::config_model::AppSettings;
+use crate::metrics::certificate_store::CertificateStore;
+use crate::metrics::database_store::DatabaseStore;
+use crate::metrics::droplet_store::{BasicDropletInfo, DropletStore};
+use crate::metrics::firewall_store::FirewallStore;
+use crate::metrics::utils;
+use ahash::HashSet;
+use async_trait::async_trait;
+use chrono::Utc;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use tracing::error;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SecurityStore: Send + Sync {
+    async fn load_security(&self) -> anyhow::Result<()>;
+
+    fn record_security_metrics(&self);
+
+    fn list_findings(&self) -> Vec<SecurityFinding>;
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SecurityFinding {
+    pub resource: String,
+    pub check: &'static str,
+    pub severity: &'static str,
+}
+
+impl SecurityFinding {
+    fn new(resource: &str, check: &'static str, severity: &'static str) -> Self {
+        Self {
+            resource: resource.to_string(),
+            check,
+            severity,
+        }
+    }
+}
+
+// data which isn't loaded by the other stores
+#[derive(Clone, Default)]
+struct SecurityState {
+    // database names with the count of their trusted sources
+    database_trusted_sources: Vec<(String, usize)>,
+    ssh_keys: Option<usize>,
+}
+
+#[derive(Clone)]
+pub struct SecurityStoreImpl {
+    store: Arc<RwLock<SecurityState>>,
+    client: Arc<dyn DigitalOceanClient>,
+    droplet_store: Arc<dyn DropletStore>,
+    firewall_store: Arc<dyn FirewallStore>,
+    database_store: Arc<dyn DatabaseStore>,
+    certificate_store: Arc<dyn CertificateStore>,
+    configs: &'static AppSettings,
+    metrics: SecurityMetrics,
+}
+
+impl SecurityStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        droplet_store: Arc<dyn DropletStore>,
+        firewall_store: Arc<dyn FirewallStore>,
+        database_store: Arc<dyn DatabaseStore>,
+        certificate_store: Arc<dyn CertificateStore>,
+        configs: &'static AppSettings,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(SecurityState::default())),
+            client,
+            droplet_store,
+            firewall_store,
+            database_store,
+            certificate_store,
+            configs,
+            metrics: SecurityMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct SecurityMetrics {
+    finding_gauge: prometheus::GaugeVec,
+    ssh_keys_gauge: prometheus::Gauge,
+}
+
+impl SecurityMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let finding_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_security_finding",
+                "Failed security check. Value is always 1.",
+            ),
+            &["resource", "check", "severity"],
+        )?;
+        let ssh_keys_gauge = prometheus::Gauge::with_opts(Opts::new(
+            "droxporter_security_ssh_keys",
+            "Count of SSH keys in the account",
+        ))?;
+
+        registry.register(Box::new(finding_gauge.clone()))?;
+        registry.register(Box::new(ssh_keys_gauge.clone()))?;
+
+        let result = Self {
+            finding_gauge,
+            ssh_keys_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl SecurityStoreImpl {
+    fn save_state(&self, state: SecurityState) {
+        *self.store.write() = state;
+    }
+
+    async fn load_ssh_keys(&self) -> anyhow::Result<usize> {
+        let mut result = 0usize;
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_ssh_keys(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result += loaded.ssh_keys.len();
+            page += 1;
+        }
+        Ok(result)
+    }
+
+    fn has_public_ipv4(droplet: &BasicDropletInfo) -> bool {
+        droplet.ips.iter().any(|x| {
+            x.parse::<Ipv4Addr>()
+                .is_ok_and(|ip| !ip.is_private() && !ip.is_loopback() && !ip.is_link_local())
+        })
+    }
+
+    fn collect_findings(&self) -> Vec<SecurityFinding> {
+        let mut result: Vec<SecurityFinding> = Vec::new();
+
+        let droplets = self.droplet_store.list_droplets();
+        // without loaded firewalls every droplet would look unprotected
+        if self.configs.firewalls.enabled && self.firewall_store.is_loaded() {
+            let firewalls = self.firewall_store.list_firewalls();
+            for droplet in droplets.iter() {
+                if !firewalls.iter().any(|x| x.applies_to(droplet)) {
+                    result.push(SecurityFinding::new(
+                        &droplet.name,
+                        "droplet_without_firewall",
+                        "high",
+                    ));
+                }
+            }
+        }
+        for droplet in droplets.iter() {
+            if droplet.vpc_uuid.is_none() && Self::has_public_ipv4(droplet) {
+                result.push(SecurityFinding::new(
+                    &droplet.name,
+                    "public_droplet_without_vpc",
+                    "medium",
+                ));
+            }
+            if droplet.locked {
+                result.push(SecurityFinding::new(&droplet.name, "droplet_locked", "low"));
+            }
+        }
+
+        for (database, trusted_sources) in self.store.read().database_trusted_sources.iter() {
+            if *trusted_sources == 0 {
+                result.push(SecurityFinding::new(
+                    database,
+                    "database_without_trusted_sources",
+                    "high",
+                ));
+            }
+        }
+
+        let now = Utc::now();
+        let expiry = chrono::Duration::from_std(self.configs.security.certificate_expiry)
+            .unwrap_or(chrono::Duration::MAX);
+        for certificate in self.certificate_store.list_certificates() {
+            if certificate.not_after <= now {
+                result.push(SecurityFinding::new(
+                    &certificate.name,
+                    "certificate_expired",
+                    "high",
+                ));
+            } else if certificate.not_after - now <= expiry {
+                result.push(SecurityFinding::new(
+                    &certificate.name,
+                    "certificate_expiring",
+                    "medium",
+                ));
+            }
+        }
+
+        result
+    }
+}
+
+#[async_trait]
+impl SecurityStore for SecurityStoreImpl {
+    async fn load_security(&self) -> anyhow::Result<()> {
+        let mut state = SecurityState::default();
+        if self.configs.databases.enabled {
+            for database in self.database_store.list_databases() {
+                // a single failed cluster shouldn't hide the findings of the others
+                match self
+                    .client
+                    .list_database_firewall_rules(database.id.clone())
+                    .await
+                {
+                    Ok(loaded) => state
+                        .database_trusted_sources
+                        .push((database.name, loaded.rules.len())),
+                    Err(e) => error!(
+                        "Database {} trusted sources loading failed with err {e}",
+                        database.name
+                    ),
+                }
+            }
+        }
+        state.ssh_keys = Some(self.load_ssh_keys().await?);
+        self.save_state(state);
+        Ok(())
+    }
+
+    fn record_security_metrics(&self) {
+        let findings = self.list_findings();
+        for finding in findings.iter() {
+            self.metrics
+                .finding_gauge
+                .with_label_values(&[finding.resource.as_str(), finding.check, finding.severity])
+                .set(1_f64);
+        }
+        if let Some(ssh_keys) = self.store.read().ssh_keys {
+            self.metrics.ssh_keys_gauge.set(ssh_keys as f64);
+        }
+
+        // to prevent fixed findings
+        let valid: HashSet<(&str, &str)> = findings
+            .iter()
+            .map(|x| (x.resource.as_str(), x.check))
+            .collect();
+        utils::retain_gauge_series(&self.metrics.finding_gauge, |labels| {
+            let resource = labels.get("resource").copied().unwrap_or_default();
+            let check = labels.get("check").copied().unwrap_or_default();
+            valid.contains(&(resource, check))
+        });
+    }
+
+    fn list_findings(&self) -> Vec<SecurityFinding> {
+        self.collect_findings()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{
+        DatabaseFirewallRuleResponse, Links, ListDatabaseFirewallRulesResponse,
+        ListSshKeysResponse, SshKeyResponse,
+    };
+    use crate::metrics::certificate_store::{BasicCertificateInfo, MockCertificateStore};
+    use crate::metrics::database_store::{BasicDatabaseInfo, MockDatabaseStore};
+    use crate::metrics::droplet_store::MockDropletStore;
+    use crate::metrics::firewall_store::{BasicFirewallInfo, MockFirewallStore};
+    use prometheus::core::Collector;
+
+    fn create_test_config() -> &'static AppSettings {
+        let mut config = AppSettings::default();
+        config.security.enabled = true;
+        config.security.certificate_expiry = std::time::Duration::from_secs(14 * 24 * 60 * 60);
+        config.firewalls.enabled = true;
+        config.databases.enabled = true;
+        Box::leak(Box::new(config))
+    }
+
+    fn droplet(id: u64, name: &str) -> BasicDropletInfo {
+        BasicDropletInfo {
+            id,
+            name: name.to_string(),
+            vpc_uuid: Some("vpc".to_string()),
+            ips: vec!["10.114.0.3".to_string(), "164.90.185.107".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn certificate(name: &str, days: i64) -> BasicCertificateInfo {
+        BasicCertificateInfo {
+            id: name.to_string(),
+            name: name.to_string(),
+            certificate_type: "custom".to_string(),
+            state: "verified".to_string(),
+            not_after: Utc::now() + chrono::Duration::days(days),
+            dns_names: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_security_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_database_firewall_rules()
+            .withf(|id| id == "db-open")
+            .times(1)
+            .returning(|_| Ok(ListDatabaseFirewallRulesResponse { rules: vec![] }));
+        mock_client
+            .expect_list_database_firewall_rules()
+            .withf(|id| id == "db-closed")
+            .times(1)
+            .returning(|_| {
+                Ok(ListDatabaseFirewallRulesResponse {
+                    rules: vec![DatabaseFirewallRuleResponse {
+                        rule_type: "tag".to_string(),
+                        value: "backend".to_string(),
+                    }],
+                })
+            });
+        mock_client
+            .expect_list_ssh_keys()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListSshKeysResponse {
+                    ssh_keys: vec![
+                        SshKeyResponse {
+                            id: 1,
+                            name: "ci".to_string(),
+                            fingerprint: "aa".to_string(),
+                        },
+                        SshKeyResponse {
+                            id: 2,
+                            name: "admin".to_string(),
+                            fingerprint: "bb".to_string(),
+                        },
+                    ],
+                    links: Links::default(),
+                })
+            });
+
+        let mut mock_droplet_store = MockDropletStore::new();
+        mock_droplet_store.expect_list_droplets().returning(|| {
+            vec![
+                BasicDropletInfo {
+                    tags: vec!["web".to_string()],
+                    ..droplet(1, "web")
+                },
+                droplet(2, "worker"),
+                BasicDropletInfo {
+                    vpc_uuid: None,
+                    locked: true,
+                    ..droplet(3, "legacy")
+                },
+            ]
+        });
+        let mut mock_firewall_store = MockFirewallStore::new();
+        mock_firewall_store.expect_is_loaded().returning(|| true);
+        mock_firewall_store.expect_list_firewalls().returning(|| {
+            vec![BasicFirewallInfo {
+                name: "web".to_string(),
+                status: "succeeded".to_string(),
+                inbound_rules: 1,
+                outbound_rules: 1,
+                droplet_ids: vec![3],
+                tags: vec!["web".to_string()],
+            }]
+        });
+        let mut mock_database_store = MockDatabaseStore::new();
+        mock_database_store.expect_list_databases().returning(|| {
+            vec![
+                BasicDatabaseInfo {
+                    id: "db-open".to_string(),
+                    name: "analytics".to_string(),
+                    ..Default::default()
+                },
+                BasicDatabaseInfo {
+                    id: "db-closed".to_string(),
+                    name: "backend".to_string(),
+                    ..Default::default()
+                },
+            ]
+        });
+        let mut mock_certificate_store = MockCertificateStore::new();
+        mock_certificate_store
+            .expect_list_certificates()
+            .returning(|| {
+                vec![
+                    certificate("fresh", 60),
+                    certificate("expiring", 7),
+                    certificate("expired", -1),
+                ]
+            });
+
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let store = SecurityStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_droplet_store),
+            Arc::new(mock_firewall_store),
+            Arc::new(mock_database_store),
+            Arc::new(mock_certificate_store),
+            config,
+            registry,
+        )
+        .unwrap();
+
+        store.load_security().await.unwrap();
+        store.record_security_metrics();
+
+        let mut findings = store.list_findings();
+        findings.sort_by(|a, b| (&a.resource, a.check).cmp(&(&b.resource, b.check)));
+        assert_eq!(
+            findings,
+            vec![
+                SecurityFinding::new("analytics", "database_without_trusted_sources", "high"),
+                SecurityFinding::new("expired", "certificate_expired", "high"),
+                SecurityFinding::new("expiring", "certificate_expiring", "medium"),
+                SecurityFinding::new("legacy", "droplet_locked", "low"),
+                SecurityFinding::new("legacy", "public_droplet_without_vpc", "medium"),
+                SecurityFinding::new("worker", "droplet_without_firewall", "high"),
+            ]
+        );
+        let finding = store
+            .metrics
+            .finding_gauge
+            .with_label_values(&["worker", "droplet_without_firewall", "high"])
+            .get();
+        assert_eq!(finding, 1_f64);
+        assert_eq!(store.metrics.ssh_keys_gauge.get(), 2_f64);
+
+        // trusted sources have been added to the analytics cluster
+        store.save_state(SecurityState {
+            database_trusted_sources: vec![("analytics".to_string(), 1)],
+            ssh_keys: Some(2),
+        });
+        store.record_security_metrics();
+
+        let series = store.metrics.finding_gauge.collect();
+        assert_eq!(series[0].get_metric().len(), 5);
+    }
+
+    #[test]
+    fn test_skip_firewall_check_before_firewalls_loaded() {
+        let mut mock_droplet_store = MockDropletStore::new();
+        mock_droplet_store
+            .expect_list_droplets()
+            .returning(|| vec![droplet(1, "web")]);
+        let mut mock_firewall_store = MockFirewallStore::new();
+        mock_firewall_store.expect_is_loaded().returning(|| false);
+        mock_firewall_store.expect_list_firewalls().never();
+        let mut mock_certificate_store = MockCertificateStore::new();
+        mock_certificate_store
+            .expect_list_certificates()
+            .returning(Vec::new);
+
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let store = SecurityStoreImpl::new(
+            Arc::new(MockDigitalOceanClient::new()),
+            Arc::new(mock_droplet_store),
+            Arc::new(mock_firewall_store),
+            Arc::new(MockDatabaseStore::new()),
+            Arc::new(mock_certificate_store),
+            config,
+            registry,
+        )
+        .unwrap();
+
+        assert!(store.collect_findings().is_empty());
+    }
+}
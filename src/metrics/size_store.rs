@@ -0,0 +1,253 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::{RegionResponse, SizeResponse};
+use crate::config::config_model::AppSettings;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SizeStore: Send + Sync {
+    async fn load_sizes(&self) -> anyhow::Result<()>;
+
+    fn record_size_metrics(&self);
+}
+
+#[derive(Clone)]
+pub struct BasicSizeAvailability {
+    pub region: String,
+    pub size: String,
+    pub available: bool,
+}
+
+#[derive(Clone)]
+pub struct SizeStoreImpl {
+    store: Arc<RwLock<Vec<BasicSizeAvailability>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    configs: &'static AppSettings,
+    metrics: SizeMetrics,
+}
+
+impl SizeStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        configs: &'static AppSettings,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            client,
+            configs,
+            metrics: SizeMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct SizeMetrics {
+    available_gauge: prometheus::GaugeVec,
+}
+
+impl SizeMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let available_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_size_available",
+                "Size can be created in region. 1 - available, 0 - not",
+            ),
+            &["region", "size"],
+        )?;
+
+        registry.register(Box::new(available_gauge.clone()))?;
+
+        let result = Self { available_gauge };
+        Ok(result)
+    }
+}
+
+impl SizeStoreImpl {
+    fn save_availability(&self, availability: Vec<BasicSizeAvailability>) {
+        *self.store.write() = availability;
+    }
+
+    async fn load_regions(&self) -> anyhow::Result<Vec<RegionResponse>> {
+        let mut result: Vec<RegionResponse> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_regions(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.regions);
+            page += 1;
+        }
+        Ok(result)
+    }
+
+    async fn load_size_list(&self) -> anyhow::Result<Vec<SizeResponse>> {
+        let mut result: Vec<SizeResponse> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_sizes(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.sizes);
+            page += 1;
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl SizeStore for SizeStoreImpl {
+    async fn load_sizes(&self) -> anyhow::Result<()> {
+        let regions = self.load_regions().await?;
+        let sizes = self.load_size_list().await?;
+        let sizes: HashMap<&str, &SizeResponse> =
+            sizes.iter().map(|x| (x.slug.as_str(), x)).collect();
+
+        // watched sizes are kept even if they are gone from the list, so their absence can be alerted on
+        let watched: Vec<&str> = if self.configs.sizes.sizes.is_empty() {
+            sizes.keys().copied().collect()
+        } else {
+            self.configs
+                .sizes
+                .sizes
+                .iter()
+                .map(|x| x.as_str())
+                .collect()
+        };
+
+        let mut result: Vec<BasicSizeAvailability> = Vec::new();
+        for region in regions.iter() {
+            for slug in watched.iter() {
+                // both lists should agree, but a size is treated as available only if both say so
+                let available = region.available
+                    && region.sizes.iter().any(|x| x == slug)
+                    && sizes
+                        .get(slug)
+                        .is_some_and(|size| size.available && size.regions.contains(&region.slug));
+                result.push(BasicSizeAvailability {
+                    region: region.slug.clone(),
+                    size: slug.to_string(),
+                    available,
+                });
+            }
+        }
+        self.save_availability(result);
+        Ok(())
+    }
+
+    fn record_size_metrics(&self) {
+        let lock = self.store.read();
+        for item in lock.iter() {
+            let value = if item.available { 1_f64 } else { 0_f64 };
+            self.metrics
+                .available_gauge
+                .with_label_values(&[item.region.as_str(), item.size.as_str()])
+                .set(value);
+        }
+
+        // to prevent phantom regions and sizes which are no longer watched
+        let valid: HashSet<(&str, &str)> = lock
+            .iter()
+            .map(|x| (x.region.as_str(), x.size.as_str()))
+            .collect();
+        utils::retain_gauge_series(&self.metrics.available_gauge, |labels| {
+            let region = labels.get("region").copied().unwrap_or_default();
+            let size = labels.get("size").copied().unwrap_or_default();
+            valid.contains(&(region, size))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{Links, ListRegionsResponse, ListSizesResponse};
+
+    fn create_test_config() -> &'static AppSettings {
+        let mut config = AppSettings::default();
+        config.sizes.enabled = true;
+        config.sizes.sizes = vec![
+            "s-1vcpu-1gb".to_string(),
+            "c-2".to_string(),
+            "retired-size".to_string(),
+        ];
+        Box::leak(Box::new(config))
+    }
+
+    fn region(slug: &str, available: bool, sizes: &[&str]) -> RegionResponse {
+        RegionResponse {
+            slug: slug.to_string(),
+            available,
+            sizes: sizes.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    fn size(slug: &str, available: bool, regions: &[&str]) -> SizeResponse {
+        SizeResponse {
+            slug: slug.to_string(),
+            available,
+            regions: regions.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_size_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_regions()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListRegionsResponse {
+                    regions: vec![
+                        region("fra1", true, &["s-1vcpu-1gb", "c-2"]),
+                        region("nyc3", true, &["s-1vcpu-1gb"]),
+                        region("sfo1", false, &[]),
+                    ],
+                    links: Links::default(),
+                })
+            });
+        mock_client.expect_list_sizes().times(1).returning(|_, _| {
+            Ok(ListSizesResponse {
+                sizes: vec![
+                    size("s-1vcpu-1gb", true, &["fra1", "nyc3"]),
+                    size("c-2", true, &["fra1"]),
+                    size("s-8vcpu-16gb", true, &["fra1", "nyc3"]),
+                ],
+                links: Links::default(),
+            })
+        });
+
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let store = SizeStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+
+        store.load_sizes().await.unwrap();
+        store.record_size_metrics();
+
+        let get = |region: &str, size: &str| {
+            store
+                .metrics
+                .available_gauge
+                .with_label_values(&[region, size])
+                .get()
+        };
+        assert_eq!(get("fra1", "s-1vcpu-1gb"), 1_f64);
+        assert_eq!(get("fra1", "c-2"), 1_f64);
+        assert_eq!(get("nyc3", "s-1vcpu-1gb"), 1_f64);
+        assert_eq!(get("nyc3", "c-2"), 0_f64);
+        assert_eq!(get("sfo1", "s-1vcpu-1gb"), 0_f64);
+        // watched, but not listed anymore
+        assert_eq!(get("fra1", "retired-size"), 0_f64);
+        // 3 regions * 3 watched sizes, unwatched sizes are skipped
+        assert_eq!(store.store.read().len(), 9);
+    }
+}
@@ -0,0 +1,328 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::SnapshotResponse;
+use crate::metrics::droplet_store::DropletStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SnapshotStore: Send + Sync {
+    async fn load_snapshots(&self) -> anyhow::Result<()>;
+
+    fn record_snapshot_metrics(&self);
+
+    fn list_snapshots(&self) -> Vec<BasicSnapshotInfo>;
+}
+
+#[derive(Clone)]
+pub struct BasicSnapshotInfo {
+    pub id: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub resource_id: String,
+    pub resource_type: String,
+    pub min_disk_size: u64,
+    pub size_gigabytes: f64,
+}
+
+impl From<SnapshotResponse> for BasicSnapshotInfo {
+    fn from(value: SnapshotResponse) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            created_at: value.created_at,
+            resource_id: value.resource_id,
+            resource_type: value.resource_type,
+            min_disk_size: value.min_disk_size,
+            size_gigabytes: value.size_gigabytes,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SnapshotStoreImpl {
+    store: Arc<RwLock<Vec<BasicSnapshotInfo>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    droplet_store: Arc<dyn DropletStore>,
+    metrics: SnapshotMetrics,
+}
+
+impl SnapshotStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        droplet_store: Arc<dyn DropletStore>,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            client,
+            droplet_store,
+            metrics: SnapshotMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct SnapshotMetrics {
+    created_gauge: prometheus::GaugeVec,
+    size_gauge: prometheus::GaugeVec,
+    min_disk_size_gauge: prometheus::GaugeVec,
+}
+
+impl SnapshotMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let labels = &["snapshot_id", "snapshot", "resource_type", "resource"];
+        let created_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_snapshot_created_timestamp_seconds",
+                "Creation time of the snapshot in seconds since the epoch",
+            ),
+            labels,
+        )?;
+        let size_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_snapshot_size_gigabytes",
+                "Billable size of the snapshot in gigabytes",
+            ),
+            labels,
+        )?;
+        let min_disk_size_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_snapshot_min_disk_size_gigabytes",
+                "Minimum disk size in gigabytes required to restore the snapshot",
+            ),
+            labels,
+        )?;
+
+        registry.register(Box::new(created_gauge.clone()))?;
+        registry.register(Box::new(size_gauge.clone()))?;
+        registry.register(Box::new(min_disk_size_gauge.clone()))?;
+
+        let result = Self {
+            created_gauge,
+            size_gauge,
+            min_disk_size_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl SnapshotStoreImpl {
+    fn save_snapshots(&self, snapshots: Vec<BasicSnapshotInfo>) {
+        *self.store.write() = snapshots;
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for SnapshotStoreImpl {
+    async fn load_snapshots(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicSnapshotInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_snapshots(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.snapshots.into_iter().map(BasicSnapshotInfo::from));
+            page += 1;
+        }
+        self.save_snapshots(result);
+        Ok(())
+    }
+
+    fn record_snapshot_metrics(&self) {
+        let droplets: HashMap<String, String> = self
+            .droplet_store
+            .list_droplets()
+            .into_iter()
+            .map(|x| (x.id.to_string(), x.name))
+            .collect();
+
+        let lock = self.store.read();
+        let mut valid_series: HashSet<[String; 4]> = HashSet::default();
+        for snapshot in lock.iter() {
+            // volumes are not loaded and droplets can be already destroyed, so we fall back to the id
+            let resource = match snapshot.resource_type.as_str() {
+                "droplet" => droplets
+                    .get(&snapshot.resource_id)
+                    .cloned()
+                    .unwrap_or_else(|| snapshot.resource_id.clone()),
+                _ => snapshot.resource_id.clone(),
+            };
+            let labels = [
+                snapshot.id.as_str(),
+                snapshot.name.as_str(),
+                snapshot.resource_type.as_str(),
+                resource.as_str(),
+            ];
+            self.metrics
+                .created_gauge
+                .with_label_values(&labels)
+                .set(snapshot.created_at.timestamp() as f64);
+            self.metrics
+                .size_gauge
+                .with_label_values(&labels)
+                .set(snapshot.size_gigabytes);
+            self.metrics
+                .min_disk_size_gauge
+                .with_label_values(&labels)
+                .set(snapshot.min_disk_size as f64);
+            valid_series.insert(labels.map(|x| x.to_string()));
+        }
+
+        // to prevent phantom and renamed snapshots
+        let keep = |labels: &std::collections::HashMap<&str, &str>| {
+            let get = |name: &str| labels.get(name).copied().unwrap_or_default().to_string();
+            valid_series.contains(&[
+                get("snapshot_id"),
+                get("snapshot"),
+                get("resource_type"),
+                get("resource"),
+            ])
+        };
+        utils::retain_gauge_series(&self.metrics.created_gauge, keep);
+        utils::retain_gauge_series(&self.metrics.size_gauge, keep);
+        utils::retain_gauge_series(&self.metrics.min_disk_size_gauge, keep);
+    }
+
+    fn list_snapshots(&self) -> Vec<BasicSnapshotInfo> {
+        self.store.read().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{Links, ListSnapshotsResponse, Pages};
+    use crate::metrics::droplet_store::{BasicDropletInfo, MockDropletStore};
+    use prometheus::core::Collector;
+
+    fn snapshot(id: &str, resource_id: &str, resource_type: &str) -> SnapshotResponse {
+        SnapshotResponse {
+            id: id.to_string(),
+            name: format!("{id}-name"),
+            created_at: "2020-07-28T16:47:44Z".parse().unwrap(),
+            resource_id: resource_id.to_string(),
+            resource_type: resource_type.to_string(),
+            min_disk_size: 25,
+            size_gigabytes: 2.34,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_snapshots_multiple_pages() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        let mock_store = MockDropletStore::new();
+
+        // First page
+        mock_client
+            .expect_list_snapshots()
+            .withf(|per_page, page| *per_page == 100 && *page == 1)
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListSnapshotsResponse {
+                    snapshots: vec![snapshot("1", "123", "droplet")],
+                    links: Links {
+                        pages: Pages {
+                            next: Some("http://next".to_string()),
+                            ..Default::default()
+                        },
+                    },
+                })
+            });
+
+        // Second page
+        mock_client
+            .expect_list_snapshots()
+            .withf(|per_page, page| *per_page == 100 && *page == 2)
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListSnapshotsResponse {
+                    snapshots: vec![snapshot("2", "volume-id", "volume")],
+                    links: Links::default(),
+                })
+            });
+
+        let registry = prometheus::Registry::new();
+        let store =
+            SnapshotStoreImpl::new(Arc::new(mock_client), Arc::new(mock_store), registry).unwrap();
+
+        let result = store.load_snapshots().await;
+        assert!(result.is_ok());
+
+        let snapshots = store.list_snapshots();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].resource_type, "droplet");
+        assert_eq!(snapshots[1].resource_type, "volume");
+    }
+
+    #[tokio::test]
+    async fn test_record_snapshot_metrics() {
+        let mock_client = MockDigitalOceanClient::new();
+        let mut mock_store = MockDropletStore::new();
+
+        mock_store.expect_list_droplets().returning(|| {
+            vec![BasicDropletInfo {
+                id: 123,
+                name: "test-droplet".to_string(),
+                ..Default::default()
+            }]
+        });
+
+        let registry = prometheus::Registry::new();
+        let store =
+            SnapshotStoreImpl::new(Arc::new(mock_client), Arc::new(mock_store), registry).unwrap();
+
+        store.save_snapshots(vec![
+            snapshot("1", "123", "droplet").into(),
+            snapshot("2", "volume-id", "volume").into(),
+        ]);
+        store.record_snapshot_metrics();
+
+        let size = store
+            .metrics
+            .size_gauge
+            .with_label_values(&["1", "1-name", "droplet", "test-droplet"])
+            .get();
+        assert_eq!(size, 2.34);
+        let created = store
+            .metrics
+            .created_gauge
+            .with_label_values(&["2", "2-name", "volume", "volume-id"])
+            .get();
+        assert_eq!(created, 1595954864_f64);
+
+        // the first snapshot has been deleted and the second one renamed
+        store.save_snapshots(vec![
+            SnapshotResponse {
+                name: "2-renamed".to_string(),
+                ..snapshot("2", "volume-id", "volume")
+            }
+            .into(),
+        ]);
+        store.record_snapshot_metrics();
+
+        let metrics: Vec<_> = store
+            .metrics
+            .size_gauge
+            .collect()
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .collect();
+        assert_eq!(metrics.len(), 1);
+        let names: Vec<_> = metrics[0]
+            .get_label()
+            .iter()
+            .filter(|l| l.name() == "snapshot")
+            .map(|l| l.value().to_string())
+            .collect();
+        assert_eq!(names, vec!["2-renamed".to_string()]);
+    }
+}
@@ -0,0 +1,244 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::config::config_model::{AppSettings, SpacesBucketConfig};
+use crate::metrics::utils;
+use ahash::HashSet;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+use tracing::error;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SpacesStore: Send + Sync {
+    async fn load_buckets(&self) -> anyhow::Result<()>;
+
+    fn record_bucket_metrics(&self);
+}
+
+#[derive(Clone)]
+pub struct BasicBucketInfo {
+    pub name: String,
+    pub region: String,
+    pub bytes: u64,
+    pub objects: u64,
+}
+
+#[derive(Clone)]
+pub struct SpacesStoreImpl {
+    store: Arc<RwLock<Vec<BasicBucketInfo>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    configs: &'static AppSettings,
+    metrics: SpacesMetrics,
+}
+
+impl SpacesStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        configs: &'static AppSettings,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            client,
+            configs,
+            metrics: SpacesMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct SpacesMetrics {
+    bytes_gauge: prometheus::GaugeVec,
+    objects_gauge: prometheus::GaugeVec,
+}
+
+impl SpacesMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let bytes_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_spaces_bucket_bytes",
+                "Total size of objects in Spaces bucket",
+            ),
+            &["bucket", "region"],
+        )?;
+        let objects_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_spaces_bucket_objects",
+                "Count of objects in Spaces bucket",
+            ),
+            &["bucket", "region"],
+        )?;
+
+        registry.register(Box::new(bytes_gauge.clone()))?;
+        registry.register(Box::new(objects_gauge.clone()))?;
+
+        let result = Self {
+            bytes_gauge,
+            objects_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl SpacesStoreImpl {
+    fn save_buckets(&self, buckets: Vec<BasicBucketInfo>) {
+        *self.store.write() = buckets;
+    }
+
+    async fn load_bucket(&self, bucket: &SpacesBucketConfig) -> anyhow::Result<BasicBucketInfo> {
+        let mut result = BasicBucketInfo {
+            name: bucket.name.clone(),
+            region: bucket.region.clone(),
+            bytes: 0,
+            objects: 0,
+        };
+        let mut continuation_token: Option<String> = None;
+        loop {
+            let loaded = self
+                .client
+                .list_spaces_objects(
+                    bucket.region.clone(),
+                    bucket.name.clone(),
+                    continuation_token,
+                )
+                .await?;
+            result.objects += loaded.contents.len() as u64;
+            result.bytes += loaded.contents.iter().map(|x| x.size).sum::<u64>();
+            continuation_token = loaded.next_continuation_token;
+            if !loaded.is_truncated || continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl SpacesStore for SpacesStoreImpl {
+    async fn load_buckets(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicBucketInfo> = Vec::new();
+        for bucket in self.configs.spaces.buckets.iter() {
+            // a single inaccessible bucket shouldn't hide the others
+            match self.load_bucket(bucket).await {
+                Ok(loaded) => result.push(loaded),
+                Err(e) => error!("Bucket {} loading failed with err {e}", bucket.name),
+            }
+        }
+        self.save_buckets(result);
+        Ok(())
+    }
+
+    fn record_bucket_metrics(&self) {
+        let lock = self.store.read();
+        for bucket in lock.iter() {
+            let labels = [bucket.name.as_str(), bucket.region.as_str()];
+            self.metrics
+                .bytes_gauge
+                .with_label_values(&labels)
+                .set(bucket.bytes as f64);
+            self.metrics
+                .objects_gauge
+                .with_label_values(&labels)
+                .set(bucket.objects as f64);
+        }
+
+        // to prevent phantom buckets
+        let valid: HashSet<(&str, &str)> = lock
+            .iter()
+            .map(|x| (x.name.as_str(), x.region.as_str()))
+            .collect();
+        for gauge in [&self.metrics.bytes_gauge, &self.metrics.objects_gauge] {
+            utils::retain_gauge_series(gauge, |labels| {
+                let bucket = labels.get("bucket").copied().unwrap_or_default();
+                let region = labels.get("region").copied().unwrap_or_default();
+                valid.contains(&(bucket, region))
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::spaces_xml_protocol::{ListBucketResult, SpacesObjectResponse};
+
+    fn create_test_config() -> &'static AppSettings {
+        let mut config = AppSettings::default();
+        config.spaces.enabled = true;
+        config.spaces.buckets = vec![
+            SpacesBucketConfig {
+                name: "assets".to_string(),
+                region: "fra1".to_string(),
+            },
+            SpacesBucketConfig {
+                name: "private".to_string(),
+                region: "nyc3".to_string(),
+            },
+        ];
+        Box::leak(Box::new(config))
+    }
+
+    fn object(key: &str, size: u64) -> SpacesObjectResponse {
+        SpacesObjectResponse {
+            key: key.to_string(),
+            size,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_buckets_with_pages() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_spaces_objects()
+            .withf(|_, bucket, token| bucket == "assets" && token.is_none())
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(ListBucketResult {
+                    contents: vec![object("a.png", 100), object("b.png", 200)],
+                    is_truncated: true,
+                    next_continuation_token: Some("next".to_string()),
+                })
+            });
+        mock_client
+            .expect_list_spaces_objects()
+            .withf(|_, bucket, token| bucket == "assets" && token.as_deref() == Some("next"))
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(ListBucketResult {
+                    contents: vec![object("c.png", 300)],
+                    is_truncated: false,
+                    next_continuation_token: None,
+                })
+            });
+        mock_client
+            .expect_list_spaces_objects()
+            .withf(|_, bucket, _| bucket == "private")
+            .times(1)
+            .returning(|_, _, _| Err(anyhow::Error::msg("Access Denied")));
+
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let store = SpacesStoreImpl::new(Arc::new(mock_client), config, registry).unwrap();
+
+        store.load_buckets().await.unwrap();
+        store.record_bucket_metrics();
+
+        let bytes = store
+            .metrics
+            .bytes_gauge
+            .with_label_values(&["assets", "fra1"])
+            .get();
+        assert_eq!(bytes, 600_f64);
+        let objects = store
+            .metrics
+            .objects_gauge
+            .with_label_values(&["assets", "fra1"])
+            .get();
+        assert_eq!(objects, 3_f64);
+        // the inaccessible bucket is skipped
+        assert_eq!(store.store.read().len(), 1);
+    }
+}
@@ -79,6 +79,55 @@ pub fn remove_old_apps_for_counter_metric(
     }
 }
 
+// removes every series for which `keep` returns false.
+// useful for "info" like gauges where a label value (status, state, etc.) can change over time
+pub fn retain_gauge_series<F>(gauge: &prometheus::GaugeVec, keep: F)
+where
+    F: Fn(&std::collections::HashMap<&str, &str>) -> bool,
+{
+    let metrics: Vec<_> = gauge
+        .collect()
+        .iter()
+        .flat_map(|m| m.get_metric().to_vec())
+        .collect();
+
+    for m in metrics.iter() {
+        let labels: std::collections::HashMap<_, _> = m
+            .get_label()
+            .iter()
+            .map(|l| (l.name(), l.value()))
+            .collect();
+
+        if !keep(&labels) {
+            let _ = gauge.remove(&labels);
+        }
+    }
+}
+
+// same as `retain_gauge_series`, but for counters of removed resources
+pub fn retain_counter_series<F>(counter: &prometheus::CounterVec, keep: F)
+where
+    F: Fn(&std::collections::HashMap<&str, &str>) -> bool,
+{
+    let metrics: Vec<_> = counter
+        .collect()
+        .iter()
+        .flat_map(|m| m.get_metric().to_vec())
+        .collect();
+
+    for m in metrics.iter() {
+        let labels: std::collections::HashMap<_, _> = m
+            .get_label()
+            .iter()
+            .map(|l| (l.name(), l.value()))
+            .collect();
+
+        if !keep(&labels) {
+            let _ = counter.remove(&labels);
+        }
+    }
+}
+
 // Personally, I prefer Summaries because they are more accurate, but in Rust I have no choice =(
 pub const DROXPORTER_DEFAULT_BUCKETS: &[f64; 16] = &[
     0.001, 0.004, 0.008, 0.016, 0.032, 0.064, 0.128, 0.256, 0.512, 1.024, 2.048, 8.192, 16.384,
@@ -207,6 +256,58 @@ mod tests {
         assert!(!app_names.contains(&"app-2"));
     }
 
+    #[test]
+    fn test_retain_gauge_series() {
+        let gauge = create_test_gauge_vec("test_certificates", &["certificate", "state"]);
+
+        gauge.with_label_values(&["cert-1", "verified"]).set(1.0);
+        gauge.with_label_values(&["cert-1", "pending"]).set(1.0);
+        gauge.with_label_values(&["cert-2", "pending"]).set(1.0);
+
+        // keep only the current state of cert-1
+        retain_gauge_series(&gauge, |labels| {
+            labels.get("certificate") == Some(&"cert-1") && labels.get("state") == Some(&"verified")
+        });
+
+        let metric_families = gauge.collect();
+        let metrics: Vec<_> = metric_families
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .collect();
+
+        assert_eq!(metrics.len(), 1);
+        let state = metrics[0]
+            .get_label()
+            .iter()
+            .find(|l| l.name() == "state")
+            .map(|l| l.value());
+        assert_eq!(state, Some("verified"));
+    }
+
+    #[test]
+    fn test_retain_counter_series() {
+        let counter = create_test_counter_vec("test_events", &["cluster", "event_type"]);
+
+        counter.with_label_values(&["db-1", "cluster_create"]).inc();
+        counter.with_label_values(&["db-2", "cluster_create"]).inc();
+
+        retain_counter_series(&counter, |labels| labels.get("cluster") == Some(&"db-1"));
+
+        let metrics: Vec<_> = counter
+            .collect()
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .collect();
+
+        assert_eq!(metrics.len(), 1);
+        let cluster = metrics[0]
+            .get_label()
+            .iter()
+            .find(|l| l.name() == "cluster")
+            .map(|l| l.value());
+        assert_eq!(cluster, Some("db-1"));
+    }
+
     #[test]
     fn test_remove_old_droplets_empty_valid_set() {
         let gauge = create_test_gauge_vec("test_droplets", &["droplet"]);
@@ -0,0 +1,450 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::{VpcPeeringResponse, VpcResponse};
+use crate::config::config_model::AppSettings;
+use crate::metrics::droplet_store::DropletStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait VpcStore: Send + Sync {
+    async fn load_vpcs(&self) -> anyhow::Result<()>;
+
+    fn record_vpc_metrics(&self);
+
+    // true after the first successful load
+    fn is_loaded(&self) -> bool;
+}
+
+#[derive(Clone)]
+pub struct BasicVpcInfo {
+    pub id: String,
+    pub name: String,
+    pub region: String,
+    pub ip_range: String,
+    pub default: bool,
+}
+
+impl From<VpcResponse> for BasicVpcInfo {
+    fn from(value: VpcResponse) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            region: value.region,
+            ip_range: value.ip_range,
+            default: value.default,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BasicVpcPeeringInfo {
+    pub name: String,
+    pub vpc_ids: Vec<String>,
+    pub status: String,
+}
+
+impl From<VpcPeeringResponse> for BasicVpcPeeringInfo {
+    fn from(value: VpcPeeringResponse) -> Self {
+        Self {
+            name: value.name,
+            vpc_ids: value.vpc_ids,
+            status: value.status,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct VpcStoreImpl {
+    store: Arc<RwLock<Vec<BasicVpcInfo>>>,
+    loaded: Arc<AtomicBool>,
+    peerings: Arc<RwLock<Vec<BasicVpcPeeringInfo>>>,
+    client: Arc<dyn DigitalOceanClient>,
+    droplet_store: Arc<dyn DropletStore>,
+    configs: &'static AppSettings,
+    metrics: VpcMetrics,
+}
+
+impl VpcStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        droplet_store: Arc<dyn DropletStore>,
+        configs: &'static AppSettings,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(vec![])),
+            loaded: Arc::new(AtomicBool::new(false)),
+            peerings: Arc::new(RwLock::new(vec![])),
+            client,
+            droplet_store,
+            configs,
+            metrics: VpcMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct VpcMetrics {
+    info_gauge: prometheus::GaugeVec,
+    droplets_gauge: prometheus::GaugeVec,
+    default_vpc_gauge: prometheus::GaugeVec,
+    peering_gauge: prometheus::GaugeVec,
+}
+
+impl VpcMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let info_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_vpc_info",
+                "VPC with its region, ip range and default flag. Values is always 1.",
+            ),
+            &["vpc", "region", "ip_range", "default"],
+        )?;
+        let droplets_gauge = prometheus::GaugeVec::new(
+            Opts::new("droxporter_vpc_droplets", "Count of droplets in VPC"),
+            &["vpc"],
+        )?;
+        let default_vpc_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_droplet_in_default_vpc",
+                "1 if droplet is placed in the default VPC of its region, otherwise 0",
+            ),
+            &["droplet"],
+        )?;
+        let peering_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_vpc_peering_info",
+                "VPC peering with its status and peered VPCs. Values is always 1.",
+            ),
+            &["peering", "status", "vpcs"],
+        )?;
+
+        registry.register(Box::new(info_gauge.clone()))?;
+        registry.register(Box::new(droplets_gauge.clone()))?;
+        registry.register(Box::new(default_vpc_gauge.clone()))?;
+        registry.register(Box::new(peering_gauge.clone()))?;
+
+        let result = Self {
+            info_gauge,
+            droplets_gauge,
+            default_vpc_gauge,
+            peering_gauge,
+        };
+        Ok(result)
+    }
+}
+
+impl VpcStoreImpl {
+    fn save_vpcs(&self, vpcs: Vec<BasicVpcInfo>, peerings: Vec<BasicVpcPeeringInfo>) {
+        *self.store.write() = vpcs;
+        *self.peerings.write() = peerings;
+        self.loaded.store(true, Ordering::Relaxed);
+    }
+
+    async fn load_peerings(&self) -> anyhow::Result<Vec<BasicVpcPeeringInfo>> {
+        let mut result: Vec<BasicVpcPeeringInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_vpc_peerings(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(
+                loaded
+                    .vpc_peerings
+                    .into_iter()
+                    .map(BasicVpcPeeringInfo::from),
+            );
+            page += 1;
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl VpcStore for VpcStoreImpl {
+    async fn load_vpcs(&self) -> anyhow::Result<()> {
+        let mut result: Vec<BasicVpcInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_vpcs(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.vpcs.into_iter().map(BasicVpcInfo::from));
+            page += 1;
+        }
+        let peerings = if self.configs.vpcs.peerings {
+            self.load_peerings().await?
+        } else {
+            vec![]
+        };
+        self.save_vpcs(result, peerings);
+        Ok(())
+    }
+
+    fn record_vpc_metrics(&self) {
+        let droplets = self.droplet_store.list_droplets();
+        let lock = self.store.read();
+        let vpcs: HashMap<&str, &BasicVpcInfo> = lock.iter().map(|x| (x.id.as_str(), x)).collect();
+
+        let mut droplets_count: HashMap<&str, u64> = HashMap::default();
+        let mut valid_info: HashSet<[&str; 4]> = HashSet::default();
+        for vpc in lock.iter() {
+            let default = if vpc.default { "true" } else { "false" };
+            let labels = [
+                vpc.name.as_str(),
+                vpc.region.as_str(),
+                vpc.ip_range.as_str(),
+                default,
+            ];
+            self.metrics
+                .info_gauge
+                .with_label_values(&labels)
+                .set(1_f64);
+            valid_info.insert(labels);
+            // to keep empty VPCs visible
+            droplets_count.insert(vpc.name.as_str(), 0);
+        }
+
+        for droplet in droplets.iter() {
+            let vpc = droplet
+                .vpc_uuid
+                .as_deref()
+                .and_then(|x| vpcs.get(x).copied());
+            if let Some(vpc) = vpc {
+                *droplets_count.entry(vpc.name.as_str()).or_default() += 1;
+            }
+            let in_default_vpc = vpc.map(|x| x.default).unwrap_or_default();
+            self.metrics
+                .default_vpc_gauge
+                .with_label_values(&[droplet.name.as_str()])
+                .set(if in_default_vpc { 1_f64 } else { 0_f64 });
+        }
+        for (vpc, count) in droplets_count.iter() {
+            self.metrics
+                .droplets_gauge
+                .with_label_values(&[vpc])
+                .set(*count as f64);
+        }
+
+        let peerings = self.peerings.read();
+        let mut valid_peerings: HashSet<(&str, &str, String)> = HashSet::default();
+        for peering in peerings.iter() {
+            // deleted VPCs are reported with their ids
+            let mut peered: Vec<&str> = peering
+                .vpc_ids
+                .iter()
+                .map(|id| vpcs.get(id.as_str()).map(|x| x.name.as_str()).unwrap_or(id))
+                .collect();
+            peered.sort();
+            let peered = peered.join(",");
+            self.metrics
+                .peering_gauge
+                .with_label_values(&[
+                    peering.name.as_str(),
+                    peering.status.as_str(),
+                    peered.as_str(),
+                ])
+                .set(1_f64);
+            valid_peerings.insert((peering.name.as_str(), peering.status.as_str(), peered));
+        }
+
+        // to prevent phantom VPCs, droplets and peerings
+        utils::retain_gauge_series(&self.metrics.info_gauge, |labels| {
+            let get = |name: &str| labels.get(name).copied().unwrap_or_default();
+            valid_info.contains(&[get("vpc"), get("region"), get("ip_range"), get("default")])
+        });
+        utils::retain_gauge_series(&self.metrics.droplets_gauge, |labels| {
+            droplets_count.contains_key(labels.get("vpc").copied().unwrap_or_default())
+        });
+        utils::retain_gauge_series(&self.metrics.peering_gauge, |labels| {
+            let key = (
+                labels.get("peering").copied().unwrap_or_default(),
+                labels.get("status").copied().unwrap_or_default(),
+                labels.get("vpcs").copied().unwrap_or_default().to_string(),
+            );
+            valid_peerings.contains(&key)
+        });
+        let droplet_names: HashSet<&str> = droplets.iter().map(|x| x.name.as_str()).collect();
+        utils::remove_old_droplets(&self.metrics.default_vpc_gauge, &droplet_names);
+    }
+
+    fn is_loaded(&self) -> bool {
+        self.loaded.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{Links, ListVpcPeeringsResponse, ListVpcsResponse};
+    use crate::metrics::droplet_store::{BasicDropletInfo, MockDropletStore};
+    use prometheus::core::Collector;
+
+    fn create_test_config(peerings: bool) -> &'static AppSettings {
+        let mut config = AppSettings::default();
+        config.vpcs.enabled = true;
+        config.vpcs.peerings = peerings;
+        Box::leak(Box::new(config))
+    }
+
+    fn vpc(id: &str, name: &str, default: bool) -> VpcResponse {
+        VpcResponse {
+            id: id.to_string(),
+            name: name.to_string(),
+            region: "nyc1".to_string(),
+            ip_range: "10.10.10.0/24".to_string(),
+            default,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_vpcs_without_peerings() {
+        let mut mock_client = MockDigitalOceanClient::new();
+
+        mock_client
+            .expect_list_vpcs()
+            .withf(|per_page, page| *per_page == 100 && *page == 1)
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListVpcsResponse {
+                    vpcs: vec![vpc("vpc-1", "prod", false)],
+                    links: Links::default(),
+                })
+            });
+        mock_client.expect_list_vpc_peerings().never();
+
+        let config = create_test_config(false);
+        let registry = prometheus::Registry::new();
+        let store = VpcStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(MockDropletStore::new()),
+            config,
+            registry,
+        )
+        .unwrap();
+
+        let result = store.load_vpcs().await;
+        assert!(result.is_ok());
+
+        let vpcs = store.store.read().clone();
+        assert_eq!(vpcs.len(), 1);
+        assert_eq!(vpcs[0].name, "prod");
+    }
+
+    #[tokio::test]
+    async fn test_record_vpc_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        let mut mock_store = MockDropletStore::new();
+
+        mock_client.expect_list_vpcs().returning(|_, _| {
+            Ok(ListVpcsResponse {
+                vpcs: vec![
+                    vpc("vpc-1", "prod", false),
+                    vpc("vpc-2", "default-nyc1", true),
+                    vpc("vpc-3", "empty", false),
+                ],
+                links: Links::default(),
+            })
+        });
+        mock_client.expect_list_vpc_peerings().returning(|_, _| {
+            Ok(ListVpcPeeringsResponse {
+                vpc_peerings: vec![VpcPeeringResponse {
+                    id: "peering-id".to_string(),
+                    name: "prod-to-default".to_string(),
+                    vpc_ids: vec!["vpc-2".to_string(), "vpc-1".to_string()],
+                    status: "ACTIVE".to_string(),
+                }],
+                links: Links::default(),
+            })
+        });
+        mock_store.expect_list_droplets().returning(|| {
+            vec![
+                BasicDropletInfo {
+                    id: 1,
+                    name: "web".to_string(),
+                    vpc_uuid: Some("vpc-1".to_string()),
+                    ..Default::default()
+                },
+                BasicDropletInfo {
+                    id: 2,
+                    name: "mistake".to_string(),
+                    vpc_uuid: Some("vpc-2".to_string()),
+                    ..Default::default()
+                },
+            ]
+        });
+
+        let config = create_test_config(true);
+        let registry = prometheus::Registry::new();
+        let store = VpcStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_store),
+            config,
+            registry,
+        )
+        .unwrap();
+        store.load_vpcs().await.unwrap();
+        store.record_vpc_metrics();
+
+        let droplets = |vpc: &str| store.metrics.droplets_gauge.with_label_values(&[vpc]).get();
+        assert_eq!(droplets("prod"), 1_f64);
+        assert_eq!(droplets("default-nyc1"), 1_f64);
+        assert_eq!(droplets("empty"), 0_f64);
+
+        let in_default = store
+            .metrics
+            .default_vpc_gauge
+            .with_label_values(&["mistake"])
+            .get();
+        assert_eq!(in_default, 1_f64);
+        let in_default = store
+            .metrics
+            .default_vpc_gauge
+            .with_label_values(&["web"])
+            .get();
+        assert_eq!(in_default, 0_f64);
+
+        let peering = store
+            .metrics
+            .peering_gauge
+            .with_label_values(&["prod-to-default", "ACTIVE", "default-nyc1,prod"])
+            .get();
+        assert_eq!(peering, 1_f64);
+
+        // the empty VPC has become the default one
+        store.save_vpcs(
+            vec![
+                vpc("vpc-1", "prod", false).into(),
+                vpc("vpc-3", "empty", true).into(),
+            ],
+            vec![],
+        );
+        store.record_vpc_metrics();
+        let defaults: Vec<String> = store
+            .metrics
+            .info_gauge
+            .collect()
+            .iter()
+            .flat_map(|m| m.get_metric().to_vec())
+            .filter(|m| m.get_label().iter().any(|l| l.value() == "empty"))
+            .flat_map(|m| {
+                m.get_label()
+                    .iter()
+                    .filter(|l| l.name() == "default")
+                    .map(|l| l.value().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(defaults, vec!["true".to_string()]);
+    }
+}
@@ -0,0 +1,577 @@
+use crate::client::do_client::DigitalOceanClient;
+use crate::client::do_json_protocol::{DropletDataResponse, VolumeResponse};
+use crate::config::config_model::AppSettings;
+use crate::metrics::droplet_store::DropletStore;
+use crate::metrics::reserved_ip_store::ReservedIpStore;
+use crate::metrics::snapshot_store::SnapshotStore;
+use crate::metrics::utils;
+use ahash::{HashMap, HashSet};
+use async_trait::async_trait;
+use chrono::Utc;
+use parking_lot::RwLock;
+use prometheus::Opts;
+use serde::Serialize;
+use std::fmt::Write;
+use std::sync::Arc;
+use tracing::error;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait WasteStore: Send + Sync {
+    async fn load_waste(&self) -> anyhow::Result<()>;
+
+    fn record_waste_metrics(&self);
+
+    fn list_waste(&self) -> Vec<WasteEntry>;
+}
+
+#[derive(Clone, Serialize, PartialEq, Debug)]
+pub struct WasteEntry {
+    pub resource_type: &'static str,
+    pub resource: String,
+    pub reason: &'static str,
+    pub monthly_cost: f64,
+}
+
+impl WasteEntry {
+    fn new(
+        resource_type: &'static str,
+        resource: &str,
+        reason: &'static str,
+        monthly_cost: f64,
+    ) -> Self {
+        Self {
+            resource_type,
+            resource: resource.to_string(),
+            reason,
+            monthly_cost,
+        }
+    }
+}
+
+// summing of floats starts from -0.0, which looks odd in an empty report
+fn total_monthly_cost(entries: &[WasteEntry]) -> f64 {
+    entries.iter().fold(0_f64, |acc, x| acc + x.monthly_cost)
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct WasteReport {
+    pub total_monthly_cost: f64,
+    pub entries: Vec<WasteEntry>,
+}
+
+impl WasteReport {
+    pub fn new(mut entries: Vec<WasteEntry>) -> Self {
+        // the most expensive first
+        entries.sort_by(|a, b| b.monthly_cost.total_cmp(&a.monthly_cost));
+        Self {
+            total_monthly_cost: total_monthly_cost(&entries),
+            entries,
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut result = String::new();
+        for entry in self.entries.iter() {
+            let _ = writeln!(
+                result,
+                "{}\t{}\t{}\t{:.2}",
+                entry.resource_type, entry.resource, entry.reason, entry.monthly_cost
+            );
+        }
+        let _ = writeln!(result, "total\t\t\t{:.2}", self.total_monthly_cost);
+        result
+    }
+}
+
+#[derive(Clone)]
+pub struct BasicVolumeInfo {
+    pub name: String,
+    pub size_gigabytes: u64,
+    pub attached: bool,
+}
+
+impl From<VolumeResponse> for BasicVolumeInfo {
+    fn from(value: VolumeResponse) -> Self {
+        Self {
+            name: value.name,
+            size_gigabytes: value.size_gigabytes,
+            attached: !value.droplet_ids.is_empty(),
+        }
+    }
+}
+
+// data which isn't loaded by the other stores
+#[derive(Clone, Default)]
+struct WasteState {
+    volumes: Vec<BasicVolumeInfo>,
+    // droplet ids with their average cpu usage in percents over the idle window
+    droplets_cpu: HashMap<u64, f64>,
+}
+
+#[derive(Clone)]
+pub struct WasteStoreImpl {
+    store: Arc<RwLock<WasteState>>,
+    client: Arc<dyn DigitalOceanClient>,
+    droplet_store: Arc<dyn DropletStore>,
+    reserved_ip_store: Arc<dyn ReservedIpStore>,
+    snapshot_store: Arc<dyn SnapshotStore>,
+    configs: &'static AppSettings,
+    metrics: WasteMetrics,
+}
+
+impl WasteStoreImpl {
+    pub fn new(
+        client: Arc<dyn DigitalOceanClient>,
+        droplet_store: Arc<dyn DropletStore>,
+        reserved_ip_store: Arc<dyn ReservedIpStore>,
+        snapshot_store: Arc<dyn SnapshotStore>,
+        configs: &'static AppSettings,
+        registry: prometheus::Registry,
+    ) -> anyhow::Result<Self> {
+        let result = Self {
+            store: Arc::new(RwLock::new(WasteState::default())),
+            client,
+            droplet_store,
+            reserved_ip_store,
+            snapshot_store,
+            configs,
+            metrics: WasteMetrics::new(registry)?,
+        };
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+struct WasteMetrics {
+    monthly_cost_gauge: prometheus::GaugeVec,
+    total_monthly_cost_gauge: prometheus::Gauge,
+}
+
+impl WasteMetrics {
+    fn new(registry: prometheus::Registry) -> anyhow::Result<Self> {
+        let monthly_cost_gauge = prometheus::GaugeVec::new(
+            Opts::new(
+                "droxporter_waste_monthly_cost",
+                "Estimated monthly cost of billable but idle resource in USD",
+            ),
+            &["resource_type", "resource", "reason"],
+        )?;
+        let total_monthly_cost_gauge = prometheus::Gauge::with_opts(Opts::new(
+            "droxporter_waste_total_monthly_cost",
+            "Estimated monthly cost of all billable but idle resources in USD",
+        ))?;
+
+        registry.register(Box::new(monthly_cost_gauge.clone()))?;
+        registry.register(Box::new(total_monthly_cost_gauge.clone()))?;
+
+        let result = Self {
+            monthly_cost_gauge,
+            total_monthly_cost_gauge,
+        };
+        Ok(result)
+    }
+}
+
+// cpu metrics are counters of seconds per mode, so the usage is the share of non idle time between the first and the last points
+fn cpu_usage_percent(response: &DropletDataResponse) -> Option<f64> {
+    let mut idle = 0_f64;
+    let mut total = 0_f64;
+    for metric in response.data.result.iter() {
+        let first = metric.values.iter().min_by_key(|x| x.timestamp);
+        let last = metric.values.iter().max_by_key(|x| x.timestamp);
+        let (Some(first), Some(last)) = (first, last) else {
+            continue;
+        };
+        let (Ok(first), Ok(last)) = (first.value.parse::<f64>(), last.value.parse::<f64>()) else {
+            continue;
+        };
+        let delta = (last - first).max(0_f64);
+        total += delta;
+        if metric.metric.mode.as_deref() == Some("idle") {
+            idle += delta;
+        }
+    }
+    if total <= 0_f64 {
+        return None;
+    }
+    Some((1_f64 - idle / total) * 100_f64)
+}
+
+impl WasteStoreImpl {
+    fn save_state(&self, state: WasteState) {
+        *self.store.write() = state;
+    }
+
+    fn idle_check_enabled(&self) -> bool {
+        self.configs
+            .droplet_metrics
+            .cpu
+            .as_ref()
+            .is_some_and(|x| x.enabled)
+    }
+
+    async fn load_volumes(&self) -> anyhow::Result<Vec<BasicVolumeInfo>> {
+        let mut result: Vec<BasicVolumeInfo> = Vec::new();
+        let mut fetch_next = true;
+        let mut page = 1u64;
+        let per_page: u64 = 100u64;
+        while fetch_next {
+            let loaded = self.client.list_volumes(per_page, page).await?;
+            fetch_next = loaded.links.pages.next.is_some();
+            result.extend(loaded.volumes.into_iter().map(BasicVolumeInfo::from));
+            page += 1;
+        }
+        Ok(result)
+    }
+
+    async fn load_droplets_cpu(&self) -> HashMap<u64, f64> {
+        let mut result: HashMap<u64, f64> = HashMap::default();
+        let end = Utc::now();
+        let start = end
+            - chrono::Duration::from_std(self.configs.waste.idle_window)
+                .unwrap_or(chrono::Duration::days(7));
+        // powered off droplets are reported anyway
+        for droplet in self
+            .droplet_store
+            .list_droplets()
+            .into_iter()
+            .filter(|x| x.status == "active")
+        {
+            // a single failed droplet shouldn't hide the others
+            match self.client.get_droplet_cpu(droplet.id, start, end).await {
+                Ok(loaded) => {
+                    if let Some(usage) = cpu_usage_percent(&loaded) {
+                        result.insert(droplet.id, usage);
+                    }
+                }
+                Err(e) => error!(
+                    "Droplet {} cpu usage loading failed with err {e}",
+                    droplet.name
+                ),
+            }
+        }
+        result
+    }
+
+    fn collect_waste(&self) -> Vec<WasteEntry> {
+        let settings = &self.configs.waste;
+        let mut result: Vec<WasteEntry> = Vec::new();
+        let lock = self.store.read();
+
+        for droplet in self.droplet_store.list_droplets() {
+            // powered off droplets are still billed
+            if droplet.status == "off" {
+                result.push(WasteEntry::new(
+                    "droplet",
+                    &droplet.name,
+                    "powered_off",
+                    droplet.price_monthly,
+                ));
+            } else if lock
+                .droplets_cpu
+                .get(&droplet.id)
+                .is_some_and(|x| *x < settings.idle_cpu_percent)
+            {
+                result.push(WasteEntry::new(
+                    "droplet",
+                    &droplet.name,
+                    "idle",
+                    droplet.price_monthly,
+                ));
+            }
+        }
+
+        for volume in lock.volumes.iter().filter(|x| !x.attached) {
+            result.push(WasteEntry::new(
+                "volume",
+                &volume.name,
+                "unattached",
+                volume.size_gigabytes as f64 * settings.volume_gigabyte_price,
+            ));
+        }
+
+        // without loaded reserved IPs and snapshots there is nothing to check
+        if self.configs.reserved_ips.enabled {
+            for ip in self
+                .reserved_ip_store
+                .list_reserved_ips()
+                .into_iter()
+                .filter(|x| x.droplet_id.is_none())
+            {
+                result.push(WasteEntry::new(
+                    "reserved_ip",
+                    &ip.ip,
+                    "unassigned",
+                    settings.reserved_ip_price,
+                ));
+            }
+        }
+        if self.configs.snapshots.enabled {
+            let now = Utc::now();
+            let max_age =
+                chrono::Duration::from_std(settings.snapshot_age).unwrap_or(chrono::Duration::MAX);
+            for snapshot in self
+                .snapshot_store
+                .list_snapshots()
+                .into_iter()
+                .filter(|x| now - x.created_at > max_age)
+            {
+                result.push(WasteEntry::new(
+                    "snapshot",
+                    &snapshot.name,
+                    "old",
+                    snapshot.size_gigabytes * settings.snapshot_gigabyte_price,
+                ));
+            }
+        }
+
+        result
+    }
+}
+
+#[async_trait]
+impl WasteStore for WasteStoreImpl {
+    async fn load_waste(&self) -> anyhow::Result<()> {
+        let volumes = self.load_volumes().await?;
+        let droplets_cpu = if self.idle_check_enabled() {
+            self.load_droplets_cpu().await
+        } else {
+            HashMap::default()
+        };
+        self.save_state(WasteState {
+            volumes,
+            droplets_cpu,
+        });
+        Ok(())
+    }
+
+    fn record_waste_metrics(&self) {
+        let waste = self.list_waste();
+        for entry in waste.iter() {
+            self.metrics
+                .monthly_cost_gauge
+                .with_label_values(&[entry.resource_type, entry.resource.as_str(), entry.reason])
+                .set(entry.monthly_cost);
+        }
+        self.metrics
+            .total_monthly_cost_gauge
+            .set(total_monthly_cost(&waste));
+
+        // to prevent resources which are deleted or in use again
+        let valid: HashSet<(&str, &str, &str)> = waste
+            .iter()
+            .map(|x| (x.resource_type, x.resource.as_str(), x.reason))
+            .collect();
+        utils::retain_gauge_series(&self.metrics.monthly_cost_gauge, |labels| {
+            let get = |name: &str| labels.get(name).copied().unwrap_or_default();
+            valid.contains(&(get("resource_type"), get("resource"), get("reason")))
+        });
+    }
+
+    fn list_waste(&self) -> Vec<WasteEntry> {
+        self.collect_waste()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::do_client::MockDigitalOceanClient;
+    use crate::client::do_json_protocol::{
+        DropletDataResult, DropletMetricMetaInfo, DropletMetricsResponse, Links,
+        ListVolumesResponse, MetricPoint, RegionSlugResponse,
+    };
+    use crate::config::config_model::CpuSettings;
+    use crate::metrics::droplet_store::{BasicDropletInfo, MockDropletStore};
+    use crate::metrics::reserved_ip_store::{BasicReservedIpInfo, MockReservedIpStore};
+    use crate::metrics::snapshot_store::{BasicSnapshotInfo, MockSnapshotStore};
+    use prometheus::core::Collector;
+
+    fn create_test_config() -> &'static AppSettings {
+        let mut config = AppSettings::default();
+        config.waste.enabled = true;
+        config.waste.snapshot_age = std::time::Duration::from_secs(30 * 24 * 60 * 60);
+        config.waste.idle_cpu_percent = 2.0;
+        config.waste.idle_window = std::time::Duration::from_secs(7 * 24 * 60 * 60);
+        config.waste.volume_gigabyte_price = 0.10;
+        config.waste.snapshot_gigabyte_price = 0.06;
+        config.waste.reserved_ip_price = 5.0;
+        config.droplet_metrics.cpu = Some(CpuSettings {
+            keys: vec![],
+            interval: std::time::Duration::from_secs(45),
+            enabled: true,
+        });
+        config.reserved_ips.enabled = true;
+        config.snapshots.enabled = true;
+        Box::leak(Box::new(config))
+    }
+
+    fn droplet(id: u64, name: &str, status: &str) -> BasicDropletInfo {
+        BasicDropletInfo {
+            id,
+            name: name.to_string(),
+            status: status.to_string(),
+            price_monthly: 12.0,
+            ..Default::default()
+        }
+    }
+
+    fn cpu_metric(mode: &str, first: &str, last: &str) -> DropletMetricsResponse {
+        DropletMetricsResponse {
+            metric: DropletMetricMetaInfo {
+                host_id: "1".to_string(),
+                mode: Some(mode.to_string()),
+                ..Default::default()
+            },
+            values: vec![
+                MetricPoint {
+                    timestamp: 1682246520,
+                    value: first.to_string(),
+                },
+                MetricPoint {
+                    timestamp: 1682851320,
+                    value: last.to_string(),
+                },
+            ],
+        }
+    }
+
+    fn cpu_response(idle: f64, user: f64) -> DropletDataResponse {
+        DropletDataResponse {
+            status: "success".to_string(),
+            data: DropletDataResult {
+                result: vec![
+                    cpu_metric("idle", "1000", &(1000_f64 + idle).to_string()),
+                    cpu_metric("user", "10", &(10_f64 + user).to_string()),
+                ],
+            },
+        }
+    }
+
+    fn volume(name: &str, size_gigabytes: u64, droplet_ids: Vec<u64>) -> VolumeResponse {
+        VolumeResponse {
+            id: name.to_string(),
+            name: name.to_string(),
+            region: RegionSlugResponse {
+                slug: "fra1".to_string(),
+            },
+            size_gigabytes,
+            droplet_ids,
+        }
+    }
+
+    fn snapshot(name: &str, days: i64, size_gigabytes: f64) -> BasicSnapshotInfo {
+        BasicSnapshotInfo {
+            id: name.to_string(),
+            name: name.to_string(),
+            created_at: Utc::now() - chrono::Duration::days(days),
+            resource_id: "1".to_string(),
+            resource_type: "droplet".to_string(),
+            min_disk_size: 25,
+            size_gigabytes,
+        }
+    }
+
+    #[test]
+    fn test_cpu_usage_percent() {
+        let usage = cpu_usage_percent(&cpu_response(990.0, 10.0)).unwrap();
+        assert!((usage - 1.0).abs() < 1e-9);
+        assert_eq!(cpu_usage_percent(&cpu_response(0.0, 0.0)), None);
+    }
+
+    #[tokio::test]
+    async fn test_record_waste_metrics() {
+        let mut mock_client = MockDigitalOceanClient::new();
+        mock_client
+            .expect_list_volumes()
+            .times(1)
+            .returning(|_, _| {
+                Ok(ListVolumesResponse {
+                    volumes: vec![volume("orphan", 100, vec![]), volume("data", 50, vec![1])],
+                    links: Links::default(),
+                })
+            });
+        mock_client
+            .expect_get_droplet_cpu()
+            .withf(|id, _, _| *id == 1)
+            .times(1)
+            .returning(|_, _, _| Ok(cpu_response(990.0, 10.0)));
+        mock_client
+            .expect_get_droplet_cpu()
+            .withf(|id, _, _| *id == 2)
+            .times(1)
+            .returning(|_, _, _| Ok(cpu_response(500.0, 500.0)));
+
+        let mut mock_droplet_store = MockDropletStore::new();
+        mock_droplet_store.expect_list_droplets().returning(|| {
+            vec![
+                droplet(1, "idle", "active"),
+                droplet(2, "busy", "active"),
+                droplet(3, "stopped", "off"),
+            ]
+        });
+        let mut mock_reserved_ip_store = MockReservedIpStore::new();
+        mock_reserved_ip_store
+            .expect_list_reserved_ips()
+            .returning(|| {
+                let ip = |ip: &str, droplet_id: Option<u64>| BasicReservedIpInfo {
+                    ip: ip.to_string(),
+                    region: "fra1".to_string(),
+                    locked: false,
+                    droplet_id,
+                };
+                vec![ip("45.55.96.47", None), ip("45.55.96.48", Some(2))]
+            });
+        let mut mock_snapshot_store = MockSnapshotStore::new();
+        mock_snapshot_store
+            .expect_list_snapshots()
+            .returning(|| vec![snapshot("ancient", 90, 2.5), snapshot("recent", 1, 2.5)]);
+
+        let config = create_test_config();
+        let registry = prometheus::Registry::new();
+        let store = WasteStoreImpl::new(
+            Arc::new(mock_client),
+            Arc::new(mock_droplet_store),
+            Arc::new(mock_reserved_ip_store),
+            Arc::new(mock_snapshot_store),
+            config,
+            registry,
+        )
+        .unwrap();
+
+        store.load_waste().await.unwrap();
+        store.record_waste_metrics();
+
+        let report = WasteReport::new(store.list_waste());
+        assert_eq!(
+            report.entries,
+            vec![
+                WasteEntry::new("droplet", "idle", "idle", 12.0),
+                WasteEntry::new("droplet", "stopped", "powered_off", 12.0),
+                WasteEntry::new("volume", "orphan", "unattached", 10.0),
+                WasteEntry::new("reserved_ip", "45.55.96.47", "unassigned", 5.0),
+                WasteEntry::new("snapshot", "ancient", "old", 0.15),
+            ]
+        );
+        assert!((report.total_monthly_cost - 39.15).abs() < 1e-9);
+        assert!(report.to_text().starts_with("droplet\tidle\tidle\t12.00\n"));
+        let cost = store
+            .metrics
+            .monthly_cost_gauge
+            .with_label_values(&["volume", "orphan", "unattached"])
+            .get();
+        assert_eq!(cost, 10_f64);
+
+        // the orphan volume has been deleted
+        store.save_state(WasteState {
+            volumes: vec![],
+            droplets_cpu: HashMap::default(),
+        });
+        store.record_waste_metrics();
+
+        let series = store.metrics.monthly_cost_gauge.collect();
+        assert_eq!(series[0].get_metric().len(), 3);
+        assert!((store.metrics.total_monthly_cost_gauge.get() - 17.15).abs() < 1e-9);
+    }
+}